@@ -4,6 +4,7 @@
 //!   - Tool dispatch (XML parsing, native parsing)
 //!   - Memory store/recall cycles (SQLite backend)
 //!   - Agent turn cycle (full orchestration loop)
+//!   - Channel send dispatch (boxed `dyn Channel` vs. native async fn)
 //!
 //! Run: `cargo bench`
 //!
@@ -15,6 +16,8 @@ use std::sync::{Arc, Mutex};
 
 use zeroclaw::agent::agent::Agent;
 use zeroclaw::agent::dispatcher::{NativeToolDispatcher, ToolDispatcher, XmlToolDispatcher};
+use zeroclaw::channels::traits::{Channel, ChannelEvent, ChannelResult, SendReceipt};
+use zeroclaw::channels::SendMessage;
 use zeroclaw::config::MemoryConfig;
 use zeroclaw::memory;
 use zeroclaw::memory::{Memory, MemoryCategory};
@@ -134,6 +137,35 @@ impl Tool for NoopTool {
     }
 }
 
+struct BenchChannel;
+
+impl BenchChannel {
+    /// Same body as `Channel::send`, as a native `async fn` with no boxing.
+    async fn send_native(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
+        Ok(SendReceipt {
+            message_id: None,
+            chat_id: Some(message.recipient.clone()),
+        })
+    }
+}
+
+#[async_trait]
+impl Channel for BenchChannel {
+    fn name(&self) -> &str {
+        "bench"
+    }
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
+        self.send_native(message).await
+    }
+    async fn listen(
+        &self,
+        _tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
+        Ok(())
+    }
+}
+
 fn make_memory() -> Arc<dyn Memory> {
     let cfg = MemoryConfig {
         backend: "none".into(),
@@ -340,11 +372,35 @@ fn bench_agent_turn(c: &mut Criterion) {
     });
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Benchmark: Channel send dispatch
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn bench_channel_send(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let channel: Arc<dyn Channel> = Arc::new(BenchChannel);
+    let message = SendMessage::new("benchmark message", "oc_bench_chat");
+
+    c.bench_function("channel_send_dyn_boxed", |b| {
+        b.iter(|| rt.block_on(channel.send(black_box(&message))).unwrap())
+    });
+
+    c.bench_function("channel_send_native_static", |b| {
+        b.iter(|| {
+            rt.block_on(BenchChannel.send_native(black_box(&message)))
+                .unwrap()
+        })
+    });
+}
+
 criterion_group!(
     benches,
     bench_xml_parsing,
     bench_native_parsing,
     bench_memory_operations,
     bench_agent_turn,
+    bench_channel_send,
 );
 criterion_main!(benches);
@@ -792,11 +792,15 @@ impl Agent {
         let (tx, mut rx) = tokio::sync::mpsc::channel(32);
         let cli = crate::channels::CliChannel::new();
 
+        let (_shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
         let listen_handle = tokio::spawn(async move {
-            let _ = crate::channels::Channel::listen(&cli, tx).await;
+            let _ = crate::channels::Channel::listen(&cli, tx, shutdown_rx).await;
         });
 
-        while let Some(msg) = rx.recv().await {
+        while let Some(event) = rx.recv().await {
+            let Some(msg) = crate::channels::traits::ChannelEvent::into_message(event) else {
+                continue;
+            };
             let response = match self.turn(&msg.content).await {
                 Ok(resp) => resp,
                 Err(e) => {
@@ -691,7 +691,7 @@ fn maybe_inject_cron_add_delivery(
     if tool_name != "cron_add"
         || !AUTO_CRON_DELIVERY_CHANNELS
             .iter()
-            .any(|supported| supported == &channel_name)
+            .any(|supported| *supported == channel_name.split(':').next().unwrap_or(channel_name))
     {
         return;
     }
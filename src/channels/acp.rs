@@ -4,7 +4,7 @@
 //! ACP server via `opencode acp` command for JSON-RPC 2.0 communication over stdio.
 //! This allows users to control OpenCode behavior from any channel via social apps.
 
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelEvent, ChannelResult, SendMessage, SendReceipt};
 use crate::config::schema::AcpConfig;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -441,7 +441,7 @@ impl Channel for AcpChannel {
         "acp"
     }
 
-    async fn send(&self, message: &SendMessage) -> Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         const MAX_SEND_ATTEMPTS: usize = 2;
 
         let _send_guard = self.send_operation_lock.lock().await;
@@ -452,7 +452,7 @@ impl Channel for AcpChannel {
                 "ACP: ignoring message from unauthorized user: {}",
                 message.recipient
             );
-            return Ok(());
+            return Ok(SendReceipt::default());
         }
 
         // Strip tool call tags from outgoing messages
@@ -493,7 +493,7 @@ impl Channel for AcpChannel {
                         );
                     }
 
-                    return Ok(());
+                    return Ok(SendReceipt::default());
                 }
                 Err(error) => {
                     // Drop unhealthy process on failure and retry once with a fresh process.
@@ -511,10 +511,16 @@ impl Channel for AcpChannel {
             }
         }
 
-        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("ACP send failed with unknown error")))
+        Err(last_error
+            .unwrap_or_else(|| anyhow::anyhow!("ACP send failed with unknown error"))
+            .into())
     }
 
-    async fn listen(&self, _tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn listen(
+        &self,
+        _tx: mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         // ACP is primarily a client-side protocol where we send prompts
         // and receive responses. For channel listening, we might need to
         // handle incoming messages from other sources that should trigger
@@ -769,14 +775,15 @@ mod tests {
                 "mock"
             }
 
-            async fn send(&self, _message: &SendMessage) -> Result<()> {
-                Ok(())
+            async fn send(&self, _message: &SendMessage) -> ChannelResult<SendReceipt> {
+                Ok(SendReceipt::default())
             }
 
             async fn listen(
                 &self,
-                _tx: tokio::sync::mpsc::Sender<crate::channels::traits::ChannelMessage>,
-            ) -> Result<()> {
+                _tx: tokio::sync::mpsc::Sender<crate::channels::traits::ChannelEvent>,
+                _shutdown: tokio::sync::watch::Receiver<bool>,
+            ) -> ChannelResult<()> {
                 Ok(())
             }
 
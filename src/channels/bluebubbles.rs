@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use parking_lot::Mutex;
 use std::collections::{HashMap, VecDeque};
@@ -519,6 +521,14 @@ impl BlueBubblesChannel {
             channel: "bluebubbles".to_string(),
             timestamp,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         });
 
         messages
@@ -741,7 +751,7 @@ impl Channel for BlueBubblesChannel {
     ///
     /// `message.recipient` must be a chat GUID (e.g. `iMessage;-;+15_551_234_567`).
     /// Authentication is via `?password=` query param (not a Bearer header).
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let url = self.api_url("/api/v1/message/text");
 
         // Strip [EFFECT:name] tag from content before rendering
@@ -778,21 +788,21 @@ impl Channel for BlueBubblesChannel {
             .await?;
 
         if resp.status().is_success() {
-            return Ok(());
+            return Ok(SendReceipt::default());
         }
 
         let status = resp.status();
         let error_body = resp.text().await.unwrap_or_default();
         let sanitized = crate::providers::sanitize_api_error(&error_body);
         tracing::error!("BlueBubbles send failed: {status} — {sanitized}");
-        anyhow::bail!("BlueBubbles API error: {status}");
+        return Err(anyhow::anyhow!("BlueBubbles API error: {status}").into());
     }
 
     /// Send a typing indicator to the given chat GUID via the BB Private API.
     /// BB typing indicators expire in ~5 s; this method spawns a background
     /// loop that re-fires every 4 s so the indicator stays visible while the
     /// LLM is processing.
-    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+    async fn start_typing(&self, recipient: &str) -> ChannelResult<()> {
         self.stop_typing(recipient).await?;
 
         let client = self.client.clone();
@@ -819,7 +829,7 @@ impl Channel for BlueBubblesChannel {
     }
 
     /// Stop the typing indicator background loop for the given recipient.
-    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+    async fn stop_typing(&self, recipient: &str) -> ChannelResult<()> {
         if let Some(handle) = self.typing_handles.lock().remove(recipient) {
             handle.abort();
         }
@@ -827,7 +837,11 @@ impl Channel for BlueBubblesChannel {
     }
 
     /// Keepalive placeholder — actual messages arrive via the `/bluebubbles` webhook.
-    async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        _tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         tracing::info!(
             "BlueBubbles channel active (webhook mode). \
             Configure your BlueBubbles server to POST webhooks to /bluebubbles."
@@ -0,0 +1,484 @@
+//! Circuit breaker that stops hammering a channel during a platform outage.
+//!
+//! [`CircuitBreakerChannel`] wraps any [`Channel`]. After `failure_threshold`
+//! consecutive failed outbound calls the circuit opens and further calls fail
+//! immediately with [`ChannelError::CircuitOpen`] instead of waiting on
+//! retries and timeouts. Once `cooldown` has passed the circuit is half-open:
+//! the next call goes through as a probe, closing the circuit on success and
+//! re-opening it on failure. A failing [`Channel::health_check`] counts as a
+//! failure and a passing one closes the circuit.
+//!
+//! Errors that say nothing about the platform's health (`UserNotAllowed`,
+//! `NotSupported`) do not count as failures.
+
+use super::traits::{
+    Channel, ChannelCapabilities, ChannelError, ChannelEvent, ChannelMessage, ChannelResult,
+    ConfigReload, SendMessage, SendReceipt,
+};
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Observable state of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls pass through; failures are being counted.
+    Closed,
+    /// Calls are rejected until the cooldown elapses.
+    Open,
+    /// The cooldown elapsed; the next call probes the platform.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+/// Consecutive-failure circuit breaker shared by clones.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    inner: Arc<parking_lot::Mutex<BreakerInner>>,
+}
+
+impl CircuitBreaker {
+    /// Open after `failure_threshold` consecutive failures (at least 1) and
+    /// probe again after `cooldown`.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            inner: Arc::new(parking_lot::Mutex::new(BreakerInner {
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_in_flight: false,
+            })),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let inner = self.inner.lock();
+        match inner.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Admit a call, or return how long until the circuit may half-open.
+    ///
+    /// In the half-open state only one probe is admitted at a time.
+    fn try_acquire(&self) -> Result<(), Duration> {
+        let mut inner = self.inner.lock();
+        let Some(opened_at) = inner.opened_at else {
+            return Ok(());
+        };
+        let elapsed = opened_at.elapsed();
+        if elapsed < self.cooldown {
+            return Err(self.cooldown - elapsed);
+        }
+        if inner.probe_in_flight {
+            return Err(Duration::ZERO);
+        }
+        inner.probe_in_flight = true;
+        Ok(())
+    }
+
+    fn record_success(&self) {
+        let mut inner = self.inner.lock();
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_in_flight = false;
+    }
+
+    /// Count a failure; returns `true` when this call opened the circuit.
+    fn record_failure(&self) -> bool {
+        let mut inner = self.inner.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let was_open = inner.opened_at.is_some();
+        let probe_failed = std::mem::take(&mut inner.probe_in_flight);
+        if probe_failed || inner.consecutive_failures >= self.failure_threshold {
+            inner.opened_at = Some(Instant::now());
+            return !was_open || probe_failed;
+        }
+        false
+    }
+
+    /// Release a half-open probe whose outcome said nothing about health.
+    fn record_neutral(&self) {
+        self.inner.lock().probe_in_flight = false;
+    }
+}
+
+/// Frees the half-open probe slot if a guarded call is cancelled mid-flight.
+struct ProbeRelease<'a>(Option<&'a CircuitBreaker>);
+
+impl Drop for ProbeRelease<'_> {
+    fn drop(&mut self) {
+        if let Some(breaker) = self.0 {
+            breaker.record_neutral();
+        }
+    }
+}
+
+fn counts_as_failure(err: &ChannelError) -> bool {
+    !matches!(
+        err,
+        ChannelError::UserNotAllowed(_)
+            | ChannelError::NotSupported(_)
+            | ChannelError::CircuitOpen { .. }
+    )
+}
+
+/// [`Channel`] wrapper that short-circuits outbound calls while the wrapped
+/// channel keeps failing.
+///
+/// `send`, drafts, edits, and approval prompts go through the breaker;
+/// `listen`, typing indicators, reactions, downloads, and history are passed
+/// straight through.
+pub struct CircuitBreakerChannel {
+    inner: Arc<dyn Channel>,
+    breaker: CircuitBreaker,
+}
+
+impl CircuitBreakerChannel {
+    pub fn new(inner: Arc<dyn Channel>, failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            inner,
+            breaker: CircuitBreaker::new(failure_threshold, cooldown),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.breaker.state()
+    }
+
+    async fn guarded<T>(&self, call: impl Future<Output = ChannelResult<T>>) -> ChannelResult<T> {
+        if let Err(retry_after) = self.breaker.try_acquire() {
+            return Err(ChannelError::CircuitOpen {
+                channel: self.inner.name().to_string(),
+                retry_after,
+            });
+        }
+        let mut probe = ProbeRelease(Some(&self.breaker));
+        let result = call.await;
+        probe.0 = None;
+        match &result {
+            Ok(_) => self.breaker.record_success(),
+            Err(err) if counts_as_failure(err) => {
+                if self.breaker.record_failure() {
+                    tracing::warn!(
+                        "Channel {} circuit opened for {:?} after error: {err}",
+                        self.inner.name(),
+                        self.breaker.cooldown
+                    );
+                }
+            }
+            Err(_) => self.breaker.record_neutral(),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl Channel for CircuitBreakerChannel {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
+        self.guarded(self.inner.send(message)).await
+    }
+
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
+        self.inner.listen(tx, shutdown).await
+    }
+
+    async fn health_check(&self) -> bool {
+        let healthy = self.inner.health_check().await;
+        if healthy {
+            self.breaker.record_success();
+        } else if self.breaker.record_failure() {
+            tracing::warn!(
+                "Channel {} circuit opened after failed health check",
+                self.inner.name()
+            );
+        }
+        healthy
+    }
+
+    async fn reload_config(&self, config: &crate::config::Config) -> ConfigReload {
+        self.inner.reload_config(config).await
+    }
+
+    fn locale(&self) -> super::locale::Locale {
+        self.inner.locale()
+    }
+
+    fn capabilities(&self) -> ChannelCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn start_typing(&self, recipient: &str) -> ChannelResult<()> {
+        self.inner.start_typing(recipient).await
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> ChannelResult<()> {
+        self.inner.stop_typing(recipient).await
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.inner.supports_draft_updates()
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> ChannelResult<Option<String>> {
+        self.guarded(self.inner.send_draft(message)).await
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> ChannelResult<Option<String>> {
+        self.guarded(self.inner.update_draft(recipient, message_id, text))
+            .await
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> ChannelResult<()> {
+        self.guarded(self.inner.finalize_draft(recipient, message_id, text))
+            .await
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> ChannelResult<()> {
+        self.inner.cancel_draft(recipient, message_id).await
+    }
+
+    async fn edit_message(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        new_content: &SendMessage,
+    ) -> ChannelResult<()> {
+        self.guarded(self.inner.edit_message(recipient, message_id, new_content))
+            .await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> ChannelResult<()> {
+        self.guarded(self.inner.delete_message(message_id)).await
+    }
+
+    async fn download_attachment(&self, message_id: &str, key: &str) -> ChannelResult<Vec<u8>> {
+        self.inner.download_attachment(message_id, key).await
+    }
+
+    async fn fetch_history(
+        &self,
+        chat_id: &str,
+        limit: usize,
+    ) -> ChannelResult<Vec<ChannelMessage>> {
+        self.inner.fetch_history(chat_id, limit).await
+    }
+
+    async fn send_approval_prompt(
+        &self,
+        recipient: &str,
+        request_id: &str,
+        tool_name: &str,
+        arguments: &serde_json::Value,
+        thread_ts: Option<String>,
+    ) -> ChannelResult<()> {
+        self.guarded(
+            self.inner
+                .send_approval_prompt(recipient, request_id, tool_name, arguments, thread_ts),
+        )
+        .await
+    }
+
+    async fn add_reaction(&self, message_id: &str, emoji: &str) -> ChannelResult<String> {
+        self.inner.add_reaction(message_id, emoji).await
+    }
+
+    async fn remove_reaction(&self, message_id: &str, reaction_id: &str) -> ChannelResult<()> {
+        self.inner.remove_reaction(message_id, reaction_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use tokio::sync::{mpsc, watch};
+
+    /// Fails every call while `failing` is set.
+    #[derive(Default)]
+    struct FlakyChannel {
+        failing: AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Channel for FlakyChannel {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> ChannelResult<SendReceipt> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.failing.load(Ordering::SeqCst) {
+                Err(ChannelError::api(500, "outage"))
+            } else {
+                Ok(SendReceipt::default())
+            }
+        }
+
+        async fn listen(
+            &self,
+            _tx: mpsc::Sender<ChannelEvent>,
+            _shutdown: watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> bool {
+            !self.failing.load(Ordering::SeqCst)
+        }
+    }
+
+    fn breaker_over(inner: &Arc<FlakyChannel>) -> CircuitBreakerChannel {
+        CircuitBreakerChannel::new(
+            Arc::clone(inner) as Arc<dyn Channel>,
+            2,
+            Duration::from_secs(30),
+        )
+    }
+
+    /// Pretend `by` has passed since the circuit opened.
+    fn elapse(breaker: &CircuitBreaker, by: Duration) {
+        let mut inner = breaker.inner.lock();
+        inner.opened_at = inner.opened_at.and_then(|at| at.checked_sub(by));
+    }
+
+    fn message() -> SendMessage {
+        SendMessage::new("hello", "chat")
+    }
+
+    #[tokio::test]
+    async fn opens_after_threshold_and_short_circuits() {
+        let inner = Arc::new(FlakyChannel::default());
+        inner.failing.store(true, Ordering::SeqCst);
+        let channel = breaker_over(&inner);
+
+        assert!(channel.send(&message()).await.is_err());
+        assert_eq!(channel.state(), CircuitState::Closed);
+        assert!(channel.send(&message()).await.is_err());
+        assert_eq!(channel.state(), CircuitState::Open);
+
+        let err = channel.send(&message()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ChannelError::CircuitOpen { ref channel, retry_after }
+                if channel == "flaky" && retry_after > Duration::from_secs(29)
+        ));
+        assert!(err.is_retryable());
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn half_open_probe_closes_on_success() {
+        let inner = Arc::new(FlakyChannel::default());
+        inner.failing.store(true, Ordering::SeqCst);
+        let channel = breaker_over(&inner);
+        for _ in 0..2 {
+            let _ = channel.send(&message()).await;
+        }
+
+        elapse(&channel.breaker, Duration::from_secs(30));
+        assert_eq!(channel.state(), CircuitState::HalfOpen);
+
+        inner.failing.store(false, Ordering::SeqCst);
+        assert!(channel.send(&message()).await.is_ok());
+        assert_eq!(channel.state(), CircuitState::Closed);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn failed_probe_reopens_for_a_full_cooldown() {
+        let inner = Arc::new(FlakyChannel::default());
+        inner.failing.store(true, Ordering::SeqCst);
+        let channel = breaker_over(&inner);
+        for _ in 0..2 {
+            let _ = channel.send(&message()).await;
+        }
+
+        elapse(&channel.breaker, Duration::from_secs(30));
+        assert!(matches!(
+            channel.send(&message()).await,
+            Err(ChannelError::Api { .. })
+        ));
+        assert_eq!(channel.state(), CircuitState::Open);
+
+        elapse(&channel.breaker, Duration::from_secs(10));
+        assert!(matches!(
+            channel.send(&message()).await,
+            Err(ChannelError::CircuitOpen { retry_after, .. }) if retry_after > Duration::from_secs(19) && retry_after <= Duration::from_secs(20)
+        ));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn half_open_admits_a_single_probe() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(5));
+        assert!(breaker.record_failure());
+        elapse(&breaker, Duration::from_secs(5));
+
+        assert!(breaker.try_acquire().is_ok());
+        assert_eq!(breaker.try_acquire(), Err(Duration::ZERO));
+        breaker.record_neutral();
+        assert!(breaker.try_acquire().is_ok());
+    }
+
+    #[tokio::test]
+    async fn health_checks_drive_the_breaker() {
+        let inner = Arc::new(FlakyChannel::default());
+        let channel = breaker_over(&inner);
+
+        inner.failing.store(true, Ordering::SeqCst);
+        assert!(!channel.health_check().await);
+        assert!(!channel.health_check().await);
+        assert_eq!(channel.state(), CircuitState::Open);
+
+        inner.failing.store(false, Ordering::SeqCst);
+        assert!(channel.health_check().await);
+        assert_eq!(channel.state(), CircuitState::Closed);
+        assert!(channel.send(&message()).await.is_ok());
+    }
+
+    #[test]
+    fn only_platform_errors_count_as_failures() {
+        assert!(!counts_as_failure(&ChannelError::NotSupported("editing")));
+        assert!(!counts_as_failure(&ChannelError::UserNotAllowed(
+            "ou_x".into()
+        )));
+        assert!(counts_as_failure(&ChannelError::RateLimited {
+            retry_after: None
+        }));
+        assert!(counts_as_failure(&ChannelError::Other(anyhow::anyhow!(
+            "timeout"
+        ))));
+    }
+}
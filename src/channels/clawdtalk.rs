@@ -5,7 +5,7 @@
 
 use crate::config::traits::ChannelConfig;
 
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelEvent, ChannelResult, SendMessage, SendReceipt};
 use async_trait::async_trait;
 use reqwest::Client;
 use schemars::JsonSchema;
@@ -283,7 +283,7 @@ impl Channel for ClawdTalkChannel {
         "ClawdTalk"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         // For ClawdTalk, "send" initiates a call with the message as TTS
         let session = self.initiate_call(&message.recipient, None).await?;
 
@@ -298,10 +298,14 @@ impl Channel for ClawdTalkChannel {
 
         self.hangup(&session.call_control_id).await?;
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        tx: mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         // ClawdTalk listens for incoming calls via webhooks
         // This would typically be handled by the gateway module
         // For now, we signal that this channel is ready and wait indefinitely
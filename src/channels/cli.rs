@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use tokio::io::{self, AsyncBufReadExt, BufReader};
 use uuid::Uuid;
@@ -18,12 +20,16 @@ impl Channel for CliChannel {
         "cli"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         println!("{}", message.content);
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         let stdin = io::stdin();
         let reader = BufReader::new(stdin);
         let mut lines = reader.lines();
@@ -48,9 +54,17 @@ impl Channel for CliChannel {
                     .unwrap_or_default()
                     .as_secs(),
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             };
 
-            if tx.send(msg).await.is_err() {
+            if tx.send(msg.into()).await.is_err() {
                 break;
             }
         }
@@ -76,6 +90,14 @@ mod tests {
                 recipient: "user".into(),
                 subject: None,
                 thread_ts: None,
+                reply_to_message_id: None,
+                card: None,
+                rich_text: None,
+                markdown: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                image_url: None,
+                idempotency_key: None,
             })
             .await;
         assert!(result.is_ok());
@@ -90,6 +112,14 @@ mod tests {
                 recipient: String::new(),
                 subject: None,
                 thread_ts: None,
+                reply_to_message_id: None,
+                card: None,
+                rich_text: None,
+                markdown: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                image_url: None,
+                idempotency_key: None,
             })
             .await;
         assert!(result.is_ok());
@@ -111,6 +141,14 @@ mod tests {
             channel: "cli".into(),
             timestamp: 1_234_567_890,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
         assert_eq!(msg.id, "test-id");
         assert_eq!(msg.sender, "user");
@@ -130,6 +168,14 @@ mod tests {
             channel: "ch".into(),
             timestamp: 0,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
         let cloned = msg.clone();
         assert_eq!(cloned.id, msg.id);
@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use std::collections::HashMap;
@@ -285,7 +287,7 @@ impl Channel for DingTalkChannel {
         "dingtalk"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let token = self.get_access_token().await?;
 
         let title = message.subject.as_deref().unwrap_or("ZeroClaw");
@@ -330,7 +332,7 @@ impl Channel for DingTalkChannel {
 
         if !status.is_success() {
             let sanitized = crate::providers::sanitize_api_error(&resp_text);
-            anyhow::bail!("DingTalk API send failed ({status}): {sanitized}");
+            return Err(anyhow::anyhow!("DingTalk API send failed ({status}): {sanitized}").into());
         }
 
         if let Ok(json) = serde_json::from_str::<serde_json::Value>(&resp_text) {
@@ -345,21 +347,30 @@ impl Channel for DingTalkChannel {
                     .and_then(|v| v.as_str())
                     .or_else(|| json.get("message").and_then(|v| v.as_str()))
                     .unwrap_or("unknown error");
-                anyhow::bail!("DingTalk API send rejected (code={app_code}): {app_msg}");
+                return Err(anyhow::anyhow!(
+                    "DingTalk API send rejected (code={app_code}): {app_msg}"
+                )
+                .into());
             }
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         tracing::info!("DingTalk: registering gateway connection...");
 
         let gw = self.register_connection().await?;
         let ws_url = format!("{}?ticket={}", gw.endpoint, gw.ticket);
 
         tracing::info!("DingTalk: connecting to stream WebSocket...");
-        let (ws_stream, _) = tokio_tungstenite::connect_async(&ws_url).await?;
+        let (ws_stream, _) = tokio_tungstenite::connect_async(&ws_url)
+            .await
+            .map_err(anyhow::Error::from)?;
         let (mut write, mut read) = ws_stream.split();
 
         tracing::info!("DingTalk: connected and listening for messages...");
@@ -485,9 +496,17 @@ impl Channel for DingTalkChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        reply_to_message_id: None,
+                        is_from_self: false,
+                        mentions: Vec::new(),
+                        attachments: Vec::new(),
+                        chat_type: ChatType::Group,
+                        triggered_by_mention: false,
+                        created_at: None,
+                        received_at: chrono::Utc::now(),
                     };
 
-                    if tx.send(channel_msg).await.is_err() {
+                    if tx.send(channel_msg.into()).await.is_err() {
                         tracing::warn!("DingTalk: message channel closed");
                         break;
                     }
@@ -496,7 +515,7 @@ impl Channel for DingTalkChannel {
             }
         }
 
-        anyhow::bail!("DingTalk WebSocket stream ended")
+        return Err(anyhow::anyhow!("DingTalk WebSocket stream ended").into());
     }
 
     async fn health_check(&self) -> bool {
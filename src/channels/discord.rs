@@ -1,5 +1,7 @@
 use super::ack_reaction::{select_ack_reaction, AckReactionContext, AckReactionContextChatType};
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use crate::config::AckReactionConfig;
 use crate::config::TranscriptionConfig;
 use anyhow::Context;
@@ -8,7 +10,7 @@ use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
 use reqwest::multipart::{Form, Part};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
@@ -17,6 +19,41 @@ use uuid::Uuid;
 const DISCORD_APPROVAL_APPROVE_PREFIX: &str = "zcapr:yes:";
 const DISCORD_APPROVAL_DENY_PREFIX: &str = "zcapr:no:";
 
+/// Received messages whose channel is remembered for [`Channel::add_reaction`].
+const DISCORD_RECENT_MESSAGE_CHANNELS: usize = 1_024;
+
+/// Channel IDs of recently received messages, oldest evicted first.
+///
+/// Discord's reaction endpoints are addressed by channel and message, while
+/// [`Channel::add_reaction`] only gets the message ID.
+#[derive(Default)]
+struct RecentMessageChannels {
+    order: VecDeque<String>,
+    channels: HashMap<String, String>,
+}
+
+impl RecentMessageChannels {
+    fn remember(&mut self, message_id: &str, channel_id: &str) {
+        if self
+            .channels
+            .insert(message_id.to_string(), channel_id.to_string())
+            .is_none()
+        {
+            self.order.push_back(message_id.to_string());
+        }
+        while self.order.len() > DISCORD_RECENT_MESSAGE_CHANNELS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.channels.remove(&oldest);
+            }
+        }
+    }
+
+    fn channel_of(&self, message_id: &str) -> Option<String> {
+        let raw_id = message_id.strip_prefix("discord_").unwrap_or(message_id);
+        self.channels.get(raw_id).cloned()
+    }
+}
+
 /// Discord channel — connects via Gateway WebSocket for real-time messages
 pub struct DiscordChannel {
     bot_token: String,
@@ -29,6 +66,9 @@ pub struct DiscordChannel {
     transcription: Option<TranscriptionConfig>,
     workspace_dir: Option<PathBuf>,
     typing_handles: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+    recent_message_channels: Mutex<RecentMessageChannels>,
+    /// Gateway intents bitmask sent with Identify.
+    intents: u64,
 }
 
 impl DiscordChannel {
@@ -50,9 +90,17 @@ impl DiscordChannel {
             transcription: None,
             workspace_dir: None,
             typing_handles: Mutex::new(HashMap::new()),
+            recent_message_channels: Mutex::new(RecentMessageChannels::default()),
+            intents: crate::config::schema::default_discord_intents(),
         }
     }
 
+    /// Override the Gateway intents bitmask sent with Identify.
+    pub fn with_intents(mut self, intents: u64) -> Self {
+        self.intents = intents;
+        self
+    }
+
     /// Configure sender IDs that bypass mention gating in guild channels.
     pub fn with_group_reply_allowed_senders(mut self, sender_ids: Vec<String>) -> Self {
         self.group_reply_allowed_sender_ids = normalize_group_reply_allowed_sender_ids(sender_ids);
@@ -141,6 +189,74 @@ impl DiscordChannel {
 
         Ok(resolved)
     }
+
+    fn reaction_channel_id(&self, message_id: &str) -> anyhow::Result<String> {
+        self.recent_message_channels
+            .lock()
+            .channel_of(message_id)
+            .ok_or_else(|| anyhow::anyhow!("Discord: no known channel for message {message_id}"))
+    }
+
+    async fn put_reaction(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> ChannelResult<()> {
+        let url = discord_reaction_url(channel_id, message_id, emoji);
+
+        let resp = self
+            .http_client()
+            .put(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .header("Content-Length", "0")
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            let sanitized = crate::providers::sanitize_api_error(&err);
+            return Err(
+                anyhow::anyhow!("Discord add reaction failed ({status}): {sanitized}").into(),
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn delete_reaction(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> ChannelResult<()> {
+        let url = discord_reaction_url(channel_id, message_id, emoji);
+
+        let resp = self
+            .http_client()
+            .delete(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            let sanitized = crate::providers::sanitize_api_error(&err);
+            return Err(
+                anyhow::anyhow!("Discord remove reaction failed ({status}): {sanitized}").into(),
+            );
+        }
+
+        Ok(())
+    }
 }
 
 fn normalize_group_reply_allowed_sender_ids(sender_ids: Vec<String>) -> Vec<String> {
@@ -802,6 +918,14 @@ fn try_parse_approval_interaction(
             .unwrap_or_default()
             .as_secs(),
         thread_ts: None,
+        reply_to_message_id: None,
+        is_from_self: false,
+        mentions: Vec::new(),
+        attachments: Vec::new(),
+        chat_type: ChatType::Group,
+        triggered_by_mention: false,
+        created_at: None,
+        received_at: chrono::Utc::now(),
     };
 
     Some((message, interaction_id, interaction_token))
@@ -876,7 +1000,7 @@ impl Channel for DiscordChannel {
         "discord"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let raw_content = super::strip_tool_call_tags(&message.content);
         let (cleaned_content, parsed_attachments) = parse_attachment_markers(&raw_content);
         let (local_attachment_targets, remote_urls, mut unresolved_markers) =
@@ -943,11 +1067,15 @@ impl Channel for DiscordChannel {
             }
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
     #[allow(clippy::too_many_lines)]
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         let bot_user_id = Self::bot_user_id_from_token(&self.bot_token).unwrap_or_default();
 
         // Get Gateway URL
@@ -968,12 +1096,19 @@ impl Channel for DiscordChannel {
         let ws_url = format!("{gw_url}/?v=10&encoding=json");
         tracing::info!("Discord: connecting to gateway...");
 
-        let (ws_stream, _) = tokio_tungstenite::connect_async(&ws_url).await?;
+        let (ws_stream, _) = tokio_tungstenite::connect_async(&ws_url)
+            .await
+            .map_err(anyhow::Error::from)?;
         let (mut write, mut read) = ws_stream.split();
 
         // Read Hello (opcode 10)
-        let hello = read.next().await.ok_or(anyhow::anyhow!("No hello"))??;
-        let hello_data: serde_json::Value = serde_json::from_str(&hello.to_string())?;
+        let hello = read
+            .next()
+            .await
+            .ok_or(anyhow::anyhow!("No hello"))?
+            .map_err(anyhow::Error::from)?;
+        let hello_data: serde_json::Value =
+            serde_json::from_str(&hello.to_string()).map_err(anyhow::Error::from)?;
         let heartbeat_interval = hello_data
             .get("d")
             .and_then(|d| d.get("heartbeat_interval"))
@@ -985,7 +1120,7 @@ impl Channel for DiscordChannel {
             "op": 2,
             "d": {
                 "token": self.bot_token,
-                "intents": 37377, // GUILDS | GUILD_MESSAGES | MESSAGE_CONTENT | DIRECT_MESSAGES
+                "intents": self.intents,
                 "properties": {
                     "os": "linux",
                     "browser": "zeroclaw",
@@ -995,7 +1130,8 @@ impl Channel for DiscordChannel {
         });
         write
             .send(Message::Text(identify.to_string().into()))
-            .await?;
+            .await
+            .map_err(anyhow::Error::from)?;
 
         tracing::info!("Discord: connected and identified");
 
@@ -1101,7 +1237,7 @@ impl Channel for DiscordChannel {
                                     approved,
                                 );
 
-                                if tx.send(channel_msg).await.is_err() {
+                                if tx.send(channel_msg.into()).await.is_err() {
                                     break;
                                 }
                             }
@@ -1180,6 +1316,9 @@ impl Channel for DiscordChannel {
                         .to_string();
 
                     if !message_id.is_empty() && !channel_id.is_empty() {
+                        self.recent_message_channels
+                            .lock()
+                            .remember(message_id, &channel_id);
                         let reaction_channel = DiscordChannel::new(
                             self.bot_token.clone(),
                             self.guild_id.clone(),
@@ -1207,7 +1346,7 @@ impl Channel for DiscordChannel {
                         ) {
                             tokio::spawn(async move {
                                 if let Err(err) = reaction_channel
-                                    .add_reaction(
+                                    .put_reaction(
                                         &reaction_channel_id,
                                         &reaction_message_id,
                                         &reaction_emoji,
@@ -1241,9 +1380,17 @@ impl Channel for DiscordChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        reply_to_message_id: None,
+                        is_from_self: false,
+                        mentions: Vec::new(),
+                        attachments: Vec::new(),
+                        chat_type: ChatType::Group,
+                        triggered_by_mention: false,
+                        created_at: None,
+                        received_at: chrono::Utc::now(),
                     };
 
-                    if tx.send(channel_msg).await.is_err() {
+                    if tx.send(channel_msg.into()).await.is_err() {
                         break;
                     }
                 }
@@ -1260,7 +1407,7 @@ impl Channel for DiscordChannel {
         tool_name: &str,
         arguments: &serde_json::Value,
         _thread_ts: Option<String>,
-    ) -> anyhow::Result<()> {
+    ) -> ChannelResult<()> {
         let raw_args = arguments.to_string();
         let args_preview = if raw_args.chars().count() > 260 {
             crate::util::truncate_with_ellipsis(&raw_args, 260)
@@ -1307,7 +1454,9 @@ impl Channel for DiscordChannel {
                 .await
                 .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
             let sanitized = crate::providers::sanitize_api_error(&err);
-            anyhow::bail!("Discord approval prompt failed ({status}): {sanitized}");
+            return Err(
+                anyhow::anyhow!("Discord approval prompt failed ({status}): {sanitized}").into(),
+            );
         }
 
         Ok(())
@@ -1323,7 +1472,7 @@ impl Channel for DiscordChannel {
             .unwrap_or(false)
     }
 
-    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+    async fn start_typing(&self, recipient: &str) -> ChannelResult<()> {
         self.stop_typing(recipient).await?;
 
         let client = self.http_client();
@@ -1348,7 +1497,7 @@ impl Channel for DiscordChannel {
         Ok(())
     }
 
-    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+    async fn stop_typing(&self, recipient: &str) -> ChannelResult<()> {
         let mut guard = self.typing_handles.lock();
         if let Some(handle) = guard.remove(recipient) {
             handle.abort();
@@ -1356,61 +1505,18 @@ impl Channel for DiscordChannel {
         Ok(())
     }
 
-    async fn add_reaction(
-        &self,
-        channel_id: &str,
-        message_id: &str,
-        emoji: &str,
-    ) -> anyhow::Result<()> {
-        let url = discord_reaction_url(channel_id, message_id, emoji);
-
-        let resp = self
-            .http_client()
-            .put(&url)
-            .header("Authorization", format!("Bot {}", self.bot_token))
-            .header("Content-Length", "0")
-            .send()
-            .await?;
-
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let err = resp
-                .text()
-                .await
-                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
-            let sanitized = crate::providers::sanitize_api_error(&err);
-            anyhow::bail!("Discord add reaction failed ({status}): {sanitized}");
-        }
-
-        Ok(())
+    /// Reacts with `emoji` as the bot; the reaction ID is the emoji itself,
+    /// since Discord removes the bot's own reaction by emoji.
+    async fn add_reaction(&self, message_id: &str, emoji: &str) -> ChannelResult<String> {
+        let channel_id = self.reaction_channel_id(message_id)?;
+        self.put_reaction(&channel_id, message_id, emoji).await?;
+        Ok(emoji.to_string())
     }
 
-    async fn remove_reaction(
-        &self,
-        channel_id: &str,
-        message_id: &str,
-        emoji: &str,
-    ) -> anyhow::Result<()> {
-        let url = discord_reaction_url(channel_id, message_id, emoji);
-
-        let resp = self
-            .http_client()
-            .delete(&url)
-            .header("Authorization", format!("Bot {}", self.bot_token))
-            .send()
-            .await?;
-
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let err = resp
-                .text()
-                .await
-                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
-            let sanitized = crate::providers::sanitize_api_error(&err);
-            anyhow::bail!("Discord remove reaction failed ({status}): {sanitized}");
-        }
-
-        Ok(())
+    async fn remove_reaction(&self, message_id: &str, reaction_id: &str) -> ChannelResult<()> {
+        let channel_id = self.reaction_channel_id(message_id)?;
+        self.delete_reaction(&channel_id, message_id, reaction_id)
+            .await
     }
 }
 
@@ -1802,6 +1908,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn recent_message_channels_resolve_prefixed_ids_and_evict_oldest() {
+        let mut recent = RecentMessageChannels::default();
+        recent.remember("1", "chan_a");
+        assert_eq!(recent.channel_of("discord_1").as_deref(), Some("chan_a"));
+        assert_eq!(recent.channel_of("1").as_deref(), Some("chan_a"));
+
+        for id in 2..=DISCORD_RECENT_MESSAGE_CHANNELS + 1 {
+            recent.remember(&id.to_string(), "chan_b");
+        }
+        assert_eq!(recent.channel_of("discord_1"), None);
+        assert_eq!(recent.channel_of("discord_2").as_deref(), Some("chan_b"));
+        assert_eq!(recent.channels.len(), DISCORD_RECENT_MESSAGE_CHANNELS);
+    }
+
+    #[tokio::test]
+    async fn reactions_on_unknown_messages_fail_without_network() {
+        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
+        assert!(ch.add_reaction("discord_42", "\u{1F440}").await.is_err());
+        assert!(ch.remove_reaction("discord_42", "\u{1F440}").await.is_err());
+    }
+
     #[test]
     fn discord_reaction_url_encodes_emoji_and_strips_prefix() {
         let url = discord_reaction_url("123", "discord_456", "👀");
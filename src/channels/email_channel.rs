@@ -33,7 +33,9 @@ use tokio_rustls::TlsConnector;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 
 /// Email channel configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -446,7 +448,7 @@ impl EmailChannel {
     }
 
     /// Main IDLE-based listen loop with automatic reconnection
-    async fn listen_with_idle(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn listen_with_idle(&self, tx: mpsc::Sender<ChannelEvent>) -> Result<()> {
         let mut backoff = Duration::from_secs(1);
         let max_backoff = Duration::from_secs(60);
 
@@ -470,7 +472,7 @@ impl EmailChannel {
     }
 
     /// Run a single IDLE session until error or clean shutdown
-    async fn run_idle_session(&self, tx: &mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn run_idle_session(&self, tx: &mpsc::Sender<ChannelEvent>) -> Result<()> {
         // Connect and authenticate
         let mut session = self.connect_imap().await?;
 
@@ -513,7 +515,7 @@ impl EmailChannel {
     async fn process_unseen(
         &self,
         session: &mut ImapSession,
-        tx: &mpsc::Sender<ChannelMessage>,
+        tx: &mpsc::Sender<ChannelEvent>,
     ) -> Result<()> {
         let messages = self.fetch_unseen(session).await?;
 
@@ -540,9 +542,17 @@ impl EmailChannel {
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             };
 
-            if tx.send(msg).await.is_err() {
+            if tx.send(msg.into()).await.is_err() {
                 // Channel closed, exit cleanly
                 return Ok(());
             }
@@ -590,7 +600,7 @@ impl Channel for EmailChannel {
         "email"
     }
 
-    async fn send(&self, message: &SendMessage) -> Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         // Use explicit subject if provided, otherwise fall back to legacy parsing or default
         let (subject, body) = if let Some(ref subj) = message.subject {
             (subj.as_str(), message.content.as_str())
@@ -605,23 +615,33 @@ impl Channel for EmailChannel {
         };
 
         let email = Message::builder()
-            .from(self.config.from_address.parse()?)
-            .to(message.recipient.parse()?)
+            .from(
+                self.config
+                    .from_address
+                    .parse()
+                    .map_err(anyhow::Error::from)?,
+            )
+            .to(message.recipient.parse().map_err(anyhow::Error::from)?)
             .subject(subject)
-            .singlepart(SinglePart::plain(body.to_string()))?;
+            .singlepart(SinglePart::plain(body.to_string()))
+            .map_err(anyhow::Error::from)?;
 
         let transport = self.create_smtp_transport()?;
-        transport.send(&email)?;
+        transport.send(&email).map_err(anyhow::Error::from)?;
         info!("Email sent to {}", message.recipient);
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn listen(
+        &self,
+        tx: mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         info!(
             "Starting email channel with IDLE support on {}",
             self.config.imap_folder
         );
-        self.listen_with_idle(tx).await
+        Ok(self.listen_with_idle(tx).await?)
     }
 
     async fn health_check(&self) -> bool {
@@ -0,0 +1,267 @@
+//! Emoji lookups between Unicode, `:shortcode:` and Feishu/Lark `emoji_type`.
+//!
+//! Feishu reactions only accept its own `emoji_type` keys (`THUMBSUP`,
+//! `OnIt`, ...), while users and templates tend to write `:thumbsup:` or 👍.
+//! [`feishu_emoji_type`] resolves all three forms and [`render_shortcodes`]
+//! turns known shortcodes in outbound text into the Unicode emoji Feishu
+//! displays natively.
+
+use std::borrow::Cow;
+
+/// Why an emoji could not be turned into a Feishu `emoji_type`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EmojiError {
+    /// Nothing to react with.
+    #[error("emoji is empty")]
+    Empty,
+    /// A `:shortcode:` that is not in the table.
+    #[error("unknown emoji shortcode {0:?}")]
+    UnknownShortcode(String),
+    /// A Unicode emoji or other text with no Feishu equivalent.
+    #[error("no Feishu emoji_type for {0:?}")]
+    Unmappable(String),
+}
+
+/// `(shortcode, unicode, feishu emoji_type)`. Shortcodes follow the
+/// GitHub/Slack names; when several rows share an emoji the first one wins
+/// for Unicode lookups. Unicode entries are stored without variation
+/// selectors.
+const EMOJI_TABLE: &[(&str, &str, &str)] = &[
+    ("+1", "\u{1F44D}", "THUMBSUP"),
+    ("thumbsup", "\u{1F44D}", "THUMBSUP"),
+    ("-1", "\u{1F44E}", "ThumbsDown"),
+    ("thumbsdown", "\u{1F44E}", "ThumbsDown"),
+    ("ok_hand", "\u{1F44C}", "OK"),
+    ("ok", "\u{1F197}", "OK"),
+    ("clap", "\u{1F44F}", "APPLAUSE"),
+    ("muscle", "\u{1F4AA}", "MUSCLE"),
+    ("pray", "\u{1F64F}", "THANKS"),
+    ("raised_hands", "\u{1F64C}", "PRAISE"),
+    ("wave", "\u{1F44B}", "WAVE"),
+    ("handshake", "\u{1F91D}", "SHAKE"),
+    ("facepunch", "\u{1F44A}", "FISTBUMP"),
+    ("fist_bump", "\u{1F44A}", "FISTBUMP"),
+    ("saluting_face", "\u{1FAE1}", "SALUTE"),
+    ("heart_hands", "\u{1FAF6}", "FINGERHEART"),
+    ("eyes", "\u{1F440}", "OnIt"),
+    ("zap", "\u{26A1}", "OnIt"),
+    ("white_check_mark", "\u{2705}", "DONE"),
+    ("heavy_check_mark", "\u{2714}", "CheckMark"),
+    ("x", "\u{274C}", "CrossMark"),
+    ("warning", "\u{26A0}", "CrossMark"),
+    ("heavy_minus_sign", "\u{2796}", "MinusOne"),
+    ("100", "\u{1F4AF}", "Hundred"),
+    ("blush", "\u{1F60A}", "SMILE"),
+    ("smiley", "\u{1F603}", "SMILE"),
+    ("smile", "\u{1F604}", "LAUGH"),
+    ("grinning", "\u{1F600}", "LAUGH"),
+    ("slightly_smiling_face", "\u{1F642}", "SLIGHT"),
+    ("joy", "\u{1F602}", "LOL"),
+    ("wink", "\u{1F609}", "WINK"),
+    ("heart_eyes", "\u{1F60D}", "LOVE"),
+    ("kissing_heart", "\u{1F618}", "KISS"),
+    ("sunglasses", "\u{1F60E}", "PROUD"),
+    ("smirk", "\u{1F60F}", "SMIRK"),
+    ("innocent", "\u{1F607}", "INNOCENTSMILE"),
+    ("star_struck", "\u{1F929}", "WOW"),
+    ("stuck_out_tongue", "\u{1F61B}", "TONGUE"),
+    ("drooling_face", "\u{1F924}", "DROOL"),
+    ("money_mouth_face", "\u{1F911}", "MONEY"),
+    ("hugs", "\u{1F917}", "HUG"),
+    ("thinking", "\u{1F914}", "THINKING"),
+    ("shushing_face", "\u{1F92B}", "SHHH"),
+    ("zipper_mouth_face", "\u{1F910}", "SILENT"),
+    ("facepalm", "\u{1F926}", "FACEPALM"),
+    ("flushed", "\u{1F633}", "EMBARRASSED"),
+    ("sweat", "\u{1F613}", "SWEAT"),
+    ("astonished", "\u{1F632}", "SHOCKED"),
+    ("scream", "\u{1F631}", "TERROR"),
+    ("dizzy_face", "\u{1F635}", "DIZZY"),
+    ("cry", "\u{1F622}", "CRY"),
+    ("sob", "\u{1F62D}", "SOB"),
+    ("angry", "\u{1F620}", "ANGRY"),
+    ("rage", "\u{1F621}", "ANGRY"),
+    ("sleeping", "\u{1F634}", "SLEEP"),
+    ("yawning_face", "\u{1F971}", "YAWN"),
+    ("mask", "\u{1F637}", "SICK"),
+    ("face_vomiting", "\u{1F92E}", "PUKE"),
+    ("skull", "\u{1F480}", "SKULL"),
+    ("poop", "\u{1F4A9}", "POOP"),
+    ("heart", "\u{2764}", "HEART"),
+    ("broken_heart", "\u{1F494}", "HEARTBROKEN"),
+    ("kiss", "\u{1F48B}", "LIPS"),
+    ("rose", "\u{1F339}", "ROSE"),
+    ("fire", "\u{1F525}", "Fire"),
+    ("tada", "\u{1F389}", "PARTY"),
+    ("partying_face", "\u{1F973}", "PARTY"),
+    ("fireworks", "\u{1F386}", "FIREWORKS"),
+    ("firecracker", "\u{1F9E8}", "FIRECRACKER"),
+    ("red_envelope", "\u{1F9E7}", "REDPACKET"),
+    ("gift", "\u{1F381}", "GIFT"),
+    ("birthday", "\u{1F382}", "CAKE"),
+    ("beer", "\u{1F37A}", "BEER"),
+    ("coffee", "\u{2615}", "Coffee"),
+    ("bubble_tea", "\u{1F9CB}", "BubbleTea"),
+    ("lemon", "\u{1F34B}", "Lemon"),
+    ("cucumber", "\u{1F952}", "CUCUMBER"),
+    ("hot_pepper", "\u{1F336}", "Pepper"),
+    ("trophy", "\u{1F3C6}", "Trophy"),
+    ("bomb", "\u{1F4A3}", "BOMB"),
+    ("pushpin", "\u{1F4CC}", "Pin"),
+    ("alarm_clock", "\u{23F0}", "Alarm"),
+    ("loudspeaker", "\u{1F4E2}", "Loudspeaker"),
+    ("musical_note", "\u{1F3B5}", "Music"),
+    ("headphones", "\u{1F3A7}", "HEADSET"),
+    ("keyboard", "\u{2328}", "Typing"),
+    ("bulb", "\u{1F4A1}", "StatusFlashOfInspiration"),
+    ("books", "\u{1F4DA}", "StatusReading"),
+    ("airplane", "\u{2708}", "StatusInFlight"),
+    ("bus", "\u{1F68C}", "StatusBus"),
+    ("car", "\u{1F697}", "GeneralTravellingCar"),
+    ("house", "\u{1F3E0}", "GeneralWorkFromHome"),
+    ("sunny", "\u{2600}", "GeneralSun"),
+    ("crescent_moon", "\u{1F319}", "GeneralMoonRest"),
+    ("christmas_tree", "\u{1F384}", "XmasTree"),
+    ("snowman", "\u{26C4}", "Snowman"),
+    ("soccer", "\u{26BD}", "Soccer"),
+    ("basketball", "\u{1F3C0}", "Basketball"),
+    ("ox", "\u{1F402}", "BULL"),
+    ("cow", "\u{1F42E}", "CALF"),
+    ("bear", "\u{1F43B}", "BEAR"),
+];
+
+/// Variation selector and skin-tone modifiers, which Feishu keys ignore.
+fn is_emoji_modifier(c: char) -> bool {
+    c == '\u{FE0F}' || ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+fn lookup_shortcode(name: &str) -> Option<&'static (&'static str, &'static str, &'static str)> {
+    EMOJI_TABLE
+        .iter()
+        .find(|(shortcode, _, _)| shortcode.eq_ignore_ascii_case(name))
+}
+
+/// Unicode emoji for a shortcode given without colons, e.g. `thumbsup` → 👍.
+pub fn shortcode_to_unicode(name: &str) -> Option<&'static str> {
+    lookup_shortcode(name).map(|(_, unicode, _)| *unicode)
+}
+
+/// Resolve a reaction to a Feishu `emoji_type`.
+///
+/// Bare keys such as `THUMBSUP` pass through verbatim, `:shortcode:` and
+/// Unicode emoji (with or without variation selectors and skin tones) are
+/// mapped through the static table, and anything else is an error instead of
+/// a request Feishu would reject.
+pub fn feishu_emoji_type(emoji: &str) -> Result<&str, EmojiError> {
+    let emoji = emoji.trim();
+    if emoji.is_empty() {
+        return Err(EmojiError::Empty);
+    }
+    if let Some(name) = emoji
+        .strip_prefix(':')
+        .and_then(|rest| rest.strip_suffix(':'))
+        .filter(|name| !name.is_empty())
+    {
+        return lookup_shortcode(name)
+            .map(|(_, _, emoji_type)| *emoji_type)
+            .ok_or_else(|| EmojiError::UnknownShortcode(emoji.to_string()));
+    }
+    if emoji.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Ok(emoji);
+    }
+    let bare: String = emoji.chars().filter(|&c| !is_emoji_modifier(c)).collect();
+    EMOJI_TABLE
+        .iter()
+        .find(|(_, unicode, _)| *unicode == bare)
+        .map(|(_, _, emoji_type)| *emoji_type)
+        .ok_or_else(|| EmojiError::Unmappable(emoji.to_string()))
+}
+
+/// Replace known `:shortcode:` sequences in `text` with Unicode emoji.
+///
+/// Unknown shortcodes and colon-separated text such as `12:30:45` are left
+/// untouched, so this is safe to run on any outbound message.
+pub fn render_shortcodes(text: &str) -> Cow<'_, str> {
+    if !text.contains(':') {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut changed = false;
+    while let Some(start) = rest.find(':') {
+        let after = &rest[start + 1..];
+        let replacement = after.find(':').and_then(|end| {
+            let name = &after[..end];
+            let is_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-'));
+            is_name
+                .then(|| shortcode_to_unicode(name))
+                .flatten()
+                .map(|unicode| (unicode, end))
+        });
+        match replacement {
+            Some((unicode, end)) => {
+                out.push_str(&rest[..start]);
+                out.push_str(unicode);
+                rest = &after[end + 1..];
+                changed = true;
+            }
+            None => {
+                out.push_str(&rest[..=start]);
+                rest = after;
+            }
+        }
+    }
+    if !changed {
+        return Cow::Borrowed(text);
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortcodes_and_unicode_map_to_feishu_keys() {
+        assert_eq!(feishu_emoji_type(":thumbsup:").unwrap(), "THUMBSUP");
+        assert_eq!(feishu_emoji_type(":+1:").unwrap(), "THUMBSUP");
+        assert_eq!(feishu_emoji_type(":Tada:").unwrap(), "PARTY");
+        assert_eq!(feishu_emoji_type(":eyes:").unwrap(), "OnIt");
+        assert_eq!(feishu_emoji_type("\u{1F44D}").unwrap(), "THUMBSUP");
+        assert_eq!(feishu_emoji_type("\u{1F44D}\u{1F3FD}").unwrap(), "THUMBSUP");
+        assert_eq!(feishu_emoji_type("\u{2764}\u{FE0F}").unwrap(), "HEART");
+        assert_eq!(feishu_emoji_type("\u{26A0}\u{FE0F}").unwrap(), "CrossMark");
+        assert_eq!(feishu_emoji_type("\u{2705}").unwrap(), "DONE");
+        assert_eq!(feishu_emoji_type(" OnIt ").unwrap(), "OnIt");
+    }
+
+    #[test]
+    fn unmappable_emoji_are_rejected() {
+        assert_eq!(feishu_emoji_type("  "), Err(EmojiError::Empty));
+        assert_eq!(
+            feishu_emoji_type(":not_an_emoji:"),
+            Err(EmojiError::UnknownShortcode(":not_an_emoji:".into()))
+        );
+        assert_eq!(
+            feishu_emoji_type("\u{1F996}"),
+            Err(EmojiError::Unmappable("\u{1F996}".into()))
+        );
+        assert!(feishu_emoji_type("THUMBS UP").is_err());
+    }
+
+    #[test]
+    fn render_shortcodes_replaces_known_names_only() {
+        assert_eq!(
+            render_shortcodes("Shipped :tada: at 12:30:45 :nope: :+1:"),
+            "Shipped \u{1F389} at 12:30:45 :nope: \u{1F44D}"
+        );
+        assert!(matches!(
+            render_shortcodes("no emoji: here"),
+            Cow::Borrowed(_)
+        ));
+    }
+}
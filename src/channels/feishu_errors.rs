@@ -0,0 +1,187 @@
+//! Named Feishu/Lark Open API business codes.
+//!
+//! A failed Feishu call answers with a numeric `code` next to a free-form
+//! `msg`. [`FeishuErrorCode`] names the documented codes so callers can match
+//! on [`ChannelError::Api`](super::traits::ChannelError::Api) without keeping
+//! their own table of magic numbers; anything else is kept as
+//! [`FeishuErrorCode::Other`].
+
+use std::fmt;
+
+/// A Feishu/Lark business error code, from [`From<i64>`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeishuErrorCode {
+    /// 9499: the interactive card JSON failed validation.
+    InvalidCard,
+    /// 10014: the app secret does not match the app ID.
+    InvalidAppSecret,
+    /// 230001: a request parameter is invalid.
+    InvalidParameter,
+    /// 230002: the bot is not a member of the chat.
+    BotNotInChat,
+    /// 230006: the app has not enabled its bot capability.
+    BotNotEnabled,
+    /// 230011: the message has been recalled.
+    MessageRecalled,
+    /// 230013: the user is outside the bot's availability scope.
+    UserNotAvailable,
+    /// 230020: the chat or user hit a message frequency limit.
+    ChatRateLimited,
+    /// 230022: the message was rejected for sensitive content.
+    SensitiveContent,
+    /// 230024: the message is past its recall window.
+    RecallWindowExpired,
+    /// 230025: the message can no longer be edited.
+    MessageNotEditable,
+    /// 230027: the bot lacks the permission the operation needs.
+    MissingPermission,
+    /// 230066: the message is already pinned.
+    AlreadyPinned,
+    /// 230067: the chat has reached its pinned-message limit.
+    TooManyPins,
+    /// 230099: Feishu could not build the card content.
+    CardContentRejected,
+    /// 99991400: the app hit an API frequency limit.
+    RateLimited,
+    /// 99991401: the caller's IP is not on the app's allowlist.
+    IpNotAllowed,
+    /// 99991661: the request carried no access token.
+    MissingAccessToken,
+    /// 99991663: the tenant access token is invalid or expired.
+    InvalidAccessToken,
+    /// 99991668: the access token was rejected for this request.
+    AccessTokenRejected,
+    /// 99991672: the app has not been granted a required scope.
+    AppScopeMissing,
+    /// 99991679: the user has not granted a required scope.
+    UserScopeMissing,
+    /// Any code without a named variant.
+    Other(i64),
+}
+
+/// `(code, variant)` for every named variant.
+const FEISHU_ERROR_CODES: &[(i64, FeishuErrorCode)] = &[
+    (9_499, FeishuErrorCode::InvalidCard),
+    (10_014, FeishuErrorCode::InvalidAppSecret),
+    (230_001, FeishuErrorCode::InvalidParameter),
+    (230_002, FeishuErrorCode::BotNotInChat),
+    (230_006, FeishuErrorCode::BotNotEnabled),
+    (230_011, FeishuErrorCode::MessageRecalled),
+    (230_013, FeishuErrorCode::UserNotAvailable),
+    (230_020, FeishuErrorCode::ChatRateLimited),
+    (230_022, FeishuErrorCode::SensitiveContent),
+    (230_024, FeishuErrorCode::RecallWindowExpired),
+    (230_025, FeishuErrorCode::MessageNotEditable),
+    (230_027, FeishuErrorCode::MissingPermission),
+    (230_066, FeishuErrorCode::AlreadyPinned),
+    (230_067, FeishuErrorCode::TooManyPins),
+    (230_099, FeishuErrorCode::CardContentRejected),
+    (99_991_400, FeishuErrorCode::RateLimited),
+    (99_991_401, FeishuErrorCode::IpNotAllowed),
+    (99_991_661, FeishuErrorCode::MissingAccessToken),
+    (99_991_663, FeishuErrorCode::InvalidAccessToken),
+    (99_991_668, FeishuErrorCode::AccessTokenRejected),
+    (99_991_672, FeishuErrorCode::AppScopeMissing),
+    (99_991_679, FeishuErrorCode::UserScopeMissing),
+];
+
+impl FeishuErrorCode {
+    /// The numeric code Feishu sent.
+    pub fn code(self) -> i64 {
+        match self {
+            Self::Other(code) => code,
+            known => FEISHU_ERROR_CODES
+                .iter()
+                .find(|(_, variant)| *variant == known)
+                .map(|(code, _)| *code)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether the code has a named variant.
+    pub fn is_known(self) -> bool {
+        !matches!(self, Self::Other(_))
+    }
+
+    /// Short English explanation of the condition.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::InvalidCard => "interactive card JSON is invalid",
+            Self::InvalidAppSecret => "app secret does not match the app ID",
+            Self::InvalidParameter => "a request parameter is invalid",
+            Self::BotNotInChat => "the bot is not in this chat",
+            Self::BotNotEnabled => "the app's bot capability is not enabled",
+            Self::MessageRecalled => "the message has been recalled",
+            Self::UserNotAvailable => "the user is outside the bot's availability scope",
+            Self::ChatRateLimited => "too many messages to this chat or user",
+            Self::SensitiveContent => "the message contains sensitive content",
+            Self::RecallWindowExpired => "the message is past its recall window",
+            Self::MessageNotEditable => "the message can no longer be edited",
+            Self::MissingPermission => "the bot lacks permission for this operation",
+            Self::AlreadyPinned => "the message is already pinned",
+            Self::TooManyPins => "the chat has reached its pinned-message limit",
+            Self::CardContentRejected => "Feishu could not build the card content",
+            Self::RateLimited => "the app hit an API frequency limit",
+            Self::IpNotAllowed => "the caller's IP is not on the app's allowlist",
+            Self::MissingAccessToken => "the request has no access token",
+            Self::InvalidAccessToken => "the access token is invalid or expired",
+            Self::AccessTokenRejected => "the access token was rejected",
+            Self::AppScopeMissing => "the app is missing a required permission scope",
+            Self::UserScopeMissing => "the user has not granted a required permission scope",
+            Self::Other(_) => "unrecognized error code",
+        }
+    }
+}
+
+impl From<i64> for FeishuErrorCode {
+    fn from(code: i64) -> Self {
+        FEISHU_ERROR_CODES
+            .iter()
+            .find(|(known, _)| *known == code)
+            .map_or(Self::Other(code), |(_, variant)| *variant)
+    }
+}
+
+impl fmt::Display for FeishuErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.description(), self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documented_codes_map_to_named_variants() {
+        assert_eq!(
+            FeishuErrorCode::from(99_991_663),
+            FeishuErrorCode::InvalidAccessToken
+        );
+        assert_eq!(
+            FeishuErrorCode::from(230_002),
+            FeishuErrorCode::BotNotInChat
+        );
+        assert_eq!(
+            FeishuErrorCode::from(230_013),
+            FeishuErrorCode::UserNotAvailable
+        );
+        assert_eq!(FeishuErrorCode::from(9_499), FeishuErrorCode::InvalidCard);
+        assert_eq!(FeishuErrorCode::from(42), FeishuErrorCode::Other(42));
+        assert!(!FeishuErrorCode::from(42).is_known());
+    }
+
+    #[test]
+    fn every_named_code_round_trips() {
+        for &(code, variant) in FEISHU_ERROR_CODES {
+            assert_eq!(FeishuErrorCode::from(code), variant);
+            assert_eq!(variant.code(), code);
+            assert!(variant.is_known());
+        }
+        assert_eq!(FeishuErrorCode::Other(7).code(), 7);
+        assert_eq!(
+            FeishuErrorCode::BotNotInChat.to_string(),
+            "the bot is not in this chat (230002)"
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use hmac::{Hmac, Mac};
 use reqwest::{header::HeaderMap, StatusCode};
@@ -305,6 +307,14 @@ author={sender}\nurl={comment_url}\n\n{comment_body}"
             channel: "github".to_string(),
             timestamp,
             thread_ts: comment_id,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         });
 
         out
@@ -418,6 +428,14 @@ author={sender}\nfile={file_path}\nurl={comment_url}\n\n{comment_body}"
             channel: "github".to_string(),
             timestamp,
             thread_ts: comment_id,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         });
 
         out
@@ -442,23 +460,31 @@ impl Channel for GitHubChannel {
         "github"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let Some((repo, issue_number)) = Self::parse_issue_recipient(&message.recipient) else {
-            anyhow::bail!(
+            return Err(anyhow::anyhow!(
                 "GitHub recipient must be in 'owner/repo#number' format, got '{}'",
                 message.recipient
-            );
+            )
+            .into());
         };
 
         if !self.repo_is_allowed(repo) {
-            anyhow::bail!("GitHub repository '{repo}' is not in allowed_repos");
+            return Err(
+                anyhow::anyhow!("GitHub repository '{repo}' is not in allowed_repos").into(),
+            );
         }
 
         self.post_issue_comment(repo, issue_number, &message.content)
-            .await
+            .await?;
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        _tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         tracing::info!(
             "GitHub channel active (webhook mode). \
             Configure GitHub webhook to POST to your gateway's /github endpoint."
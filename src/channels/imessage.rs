@@ -1,4 +1,6 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use directories::UserDirs;
 use rusqlite::{Connection, OpenFlags};
@@ -95,12 +97,12 @@ impl Channel for IMessageChannel {
         "imessage"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         // Defense-in-depth: validate target format before any interpolation
         if !is_valid_imessage_target(&message.recipient) {
-            anyhow::bail!(
+            return Err(anyhow::anyhow!(
                 "Invalid iMessage target: must be a phone number (+1234567890) or email (user@example.com)"
-            );
+            ).into());
         }
 
         // SECURITY: Escape both message AND target to prevent AppleScript injection
@@ -120,17 +122,22 @@ end tell"#
             .arg("-e")
             .arg(&script)
             .output()
-            .await?;
+            .await
+            .map_err(anyhow::Error::from)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("iMessage send failed: {stderr}");
+            return Err(anyhow::anyhow!("iMessage send failed: {stderr}").into());
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        tx: mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         tracing::info!("iMessage channel listening (AppleScript bridge)...");
 
         // Query the Messages SQLite database for new messages
@@ -140,10 +147,10 @@ end tell"#
             .ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
 
         if !db_path.exists() {
-            anyhow::bail!(
+            return Err(anyhow::anyhow!(
                 "Messages database not found at {}. Ensure Messages.app is set up and Full Disk Access is granted.",
                 db_path.display()
-            );
+            ).into());
         }
 
         // Open a persistent read-only connection instead of creating
@@ -155,7 +162,8 @@ end tell"#
                 OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
             )?)
         })
-        .await??;
+        .await
+        .map_err(anyhow::Error::from)??;
 
         // Track the last ROWID we've seen (shuttle conn in and out)
         let (mut conn, initial_rowid) =
@@ -168,7 +176,8 @@ end tell"#
                 };
                 Ok((conn, rowid))
             })
-            .await??;
+            .await
+            .map_err(anyhow::Error::from)??;
         let mut last_rowid = initial_rowid;
 
         loop {
@@ -232,9 +241,17 @@ end tell"#
                                 .unwrap_or_default()
                                 .as_secs(),
                             thread_ts: None,
+                            reply_to_message_id: None,
+                            is_from_self: false,
+                            mentions: Vec::new(),
+                            attachments: Vec::new(),
+                            chat_type: ChatType::Group,
+                            triggered_by_mention: false,
+                            created_at: None,
+                            received_at: chrono::Utc::now(),
                         };
 
-                        if tx.send(msg).await.is_err() {
+                        if tx.send(msg.into()).await.is_err() {
                             return Ok(());
                         }
                     }
@@ -1,4 +1,6 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -350,7 +352,7 @@ impl Channel for IrcChannel {
         "irc"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let mut guard = self.writer.lock().await;
         let writer = guard
             .as_mut()
@@ -366,10 +368,14 @@ impl Channel for IrcChannel {
             Self::send_raw(writer, &format!("PRIVMSG {} :{chunk}", message.recipient)).await?;
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        tx: mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         let mut current_nick = self.nickname.clone();
         tracing::info!(
             "IRC channel connecting to {}:{} as {}...",
@@ -414,11 +420,10 @@ impl Channel for IrcChannel {
             line.clear();
             let n = tokio::time::timeout(READ_TIMEOUT, buf_reader.read_line(&mut line))
                 .await
-                .map_err(|_| {
-                    anyhow::anyhow!("IRC read timed out (no data for {READ_TIMEOUT:?})")
-                })??;
+                .map_err(|_| anyhow::anyhow!("IRC read timed out (no data for {READ_TIMEOUT:?})"))?
+                .map_err(anyhow::Error::from)?;
             if n == 0 {
-                anyhow::bail!("IRC connection closed by server");
+                return Err(anyhow::anyhow!("IRC connection closed by server").into());
             }
 
             let Some(msg) = IrcMessage::parse(&line) else {
@@ -580,16 +585,24 @@ impl Channel for IrcChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        reply_to_message_id: None,
+                        is_from_self: false,
+                        mentions: Vec::new(),
+                        attachments: Vec::new(),
+                        chat_type: ChatType::Group,
+                        triggered_by_mention: false,
+                        created_at: None,
+                        received_at: chrono::Utc::now(),
                     };
 
-                    if tx.send(channel_msg).await.is_err() {
+                    if tx.send(channel_msg.into()).await.is_err() {
                         return Ok(());
                     }
                 }
 
                 // ERR_PASSWDMISMATCH (464) or other fatal errors
                 "464" => {
-                    anyhow::bail!("IRC password mismatch");
+                    return Err(anyhow::anyhow!("IRC password mismatch").into());
                 }
 
                 _ => {}
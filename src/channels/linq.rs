@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -250,6 +252,14 @@ impl LinqChannel {
             channel: "linq".to_string(),
             timestamp,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         });
 
         messages
@@ -262,7 +272,7 @@ impl Channel for LinqChannel {
         "linq"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         // If reply_target looks like a chat_id, send to existing chat.
         // Otherwise create a new chat with the recipient phone number.
         let recipient = &message.recipient;
@@ -289,7 +299,7 @@ impl Channel for LinqChannel {
             .await?;
 
         if resp.status().is_success() {
-            return Ok(());
+            return Ok(SendReceipt::default());
         }
 
         // If the chat_id-based send failed with 404, try creating a new chat
@@ -319,20 +329,24 @@ impl Channel for LinqChannel {
                 let error_body = create_resp.text().await.unwrap_or_default();
                 let sanitized = crate::providers::sanitize_api_error(&error_body);
                 tracing::error!("Linq create chat failed: {status} — {sanitized}");
-                anyhow::bail!("Linq API error: {status}");
+                return Err(anyhow::anyhow!("Linq API error: {status}").into());
             }
 
-            return Ok(());
+            return Ok(SendReceipt::default());
         }
 
         let status = resp.status();
         let error_body = resp.text().await.unwrap_or_default();
         let sanitized = crate::providers::sanitize_api_error(&error_body);
         tracing::error!("Linq send failed: {status} — {sanitized}");
-        anyhow::bail!("Linq API error: {status}");
+        return Err(anyhow::anyhow!("Linq API error: {status}").into());
     }
 
-    async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        _tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         // Linq uses webhooks (push-based), not polling.
         // Messages are received via the gateway's /linq endpoint.
         tracing::info!(
@@ -359,7 +373,7 @@ impl Channel for LinqChannel {
             .unwrap_or(false)
     }
 
-    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+    async fn start_typing(&self, recipient: &str) -> ChannelResult<()> {
         let url = format!("{LINQ_API_BASE}/chats/{recipient}/typing");
 
         let resp = self
@@ -376,7 +390,7 @@ impl Channel for LinqChannel {
         Ok(())
     }
 
-    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+    async fn stop_typing(&self, recipient: &str) -> ChannelResult<()> {
         let url = format!("{LINQ_API_BASE}/chats/{recipient}/typing");
 
         let resp = self
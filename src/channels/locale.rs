@@ -0,0 +1,133 @@
+//! Localized text for system messages channels show to end users.
+//!
+//! Covers the fixed strings the runtime and channels emit on their own
+//! (error replies, timeouts, fallbacks), not model output. Each channel
+//! reports its [`Locale`] through [`Channel::locale`](super::traits::Channel::locale)
+//! and callers look the strings up with [`messages`].
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Language for bot-generated system messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    /// Simplified Chinese.
+    ZhCn,
+}
+
+impl Locale {
+    /// BCP 47 tag, as accepted by [`Locale::from_str`].
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::ZhCn => "zh-CN",
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.tag())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = anyhow::Error;
+
+    /// Accepts `en`/`en-US`-style tags and `zh`, `zh-CN`, `zh_cn`, or
+    /// `zh-Hans`, case-insensitively.
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        let normalized = tag.trim().to_ascii_lowercase().replace('_', "-");
+        match normalized.as_str() {
+            "en" => Ok(Self::En),
+            "zh" | "zh-cn" | "zh-hans" | "zh-hans-cn" => Ok(Self::ZhCn),
+            other if other.starts_with("en-") => Ok(Self::En),
+            _ => anyhow::bail!("unsupported locale \"{tag}\" (expected \"en\" or \"zh-CN\")"),
+        }
+    }
+}
+
+/// System message strings for one [`Locale`].
+#[derive(Debug)]
+pub struct Messages {
+    /// Prefix for a failed request's error reply; the error follows it.
+    pub error_prefix: &'static str,
+    /// The model did not answer within the message timeout.
+    pub request_timed_out: &'static str,
+    /// The conversation overflowed the model's context window.
+    pub context_window_exceeded: &'static str,
+    /// As `context_window_exceeded`, after older history was compacted.
+    pub context_window_compacted: &'static str,
+    /// The turn hit `agent.max_tool_iterations`; `{limit}` is the limit.
+    pub tool_iteration_limit: &'static str,
+    /// Stands in for an image that could not be sent; the image name follows.
+    pub image_unavailable_prefix: &'static str,
+    /// Shown while a streamed reply is still being written.
+    pub stream_placeholder: &'static str,
+}
+
+impl Messages {
+    /// [`Self::tool_iteration_limit`] with the limit filled in.
+    pub fn tool_iteration_limit(&self, limit: usize) -> String {
+        self.tool_iteration_limit
+            .replace("{limit}", &limit.to_string())
+    }
+}
+
+const EN: Messages = Messages {
+    error_prefix: "⚠️ Error: ",
+    request_timed_out: "⚠️ Request timed out while waiting for the model. Please try again.",
+    context_window_exceeded:
+        "⚠️ Context window exceeded for this conversation. Please resend your last message.",
+    context_window_compacted: "⚠️ Context window exceeded for this conversation. I compacted recent history and kept the latest context. Please resend your last message.",
+    tool_iteration_limit: "⚠️ Reached tool-iteration limit ({limit}) for this turn. Context and progress were preserved. Reply \"continue\" to resume, or increase `agent.max_tool_iterations`.",
+    image_unavailable_prefix: "Image: ",
+    stream_placeholder: "Thinking…",
+};
+
+const ZH_CN: Messages = Messages {
+    error_prefix: "⚠️ 出错了：",
+    request_timed_out: "⚠️ 等待模型响应超时，请稍后重试。",
+    context_window_exceeded: "⚠️ 当前对话已超出上下文窗口，请重新发送你的上一条消息。",
+    context_window_compacted: "⚠️ 当前对话已超出上下文窗口。我已压缩较早的历史记录并保留了最新的上下文，请重新发送你的上一条消息。",
+    tool_iteration_limit: "⚠️ 本轮已达到工具调用次数上限（{limit}），上下文和进度均已保留。回复 \"continue\" 继续，或调大 `agent.max_tool_iterations`。",
+    image_unavailable_prefix: "图片：",
+    stream_placeholder: "思考中…",
+};
+
+/// System message strings for `locale`.
+pub fn messages(locale: Locale) -> &'static Messages {
+    match locale {
+        Locale::En => &EN,
+        Locale::ZhCn => &ZH_CN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_common_tags() {
+        for tag in ["en", "EN", "en-US", "en_GB"] {
+            assert_eq!(tag.parse::<Locale>().unwrap(), Locale::En, "{tag}");
+        }
+        for tag in ["zh", "zh-CN", "zh_cn", "zh-Hans"] {
+            assert_eq!(tag.parse::<Locale>().unwrap(), Locale::ZhCn, "{tag}");
+        }
+        assert!("ja".parse::<Locale>().is_err());
+        assert!("zh-TW".parse::<Locale>().is_err());
+        assert_eq!(Locale::ZhCn.tag().parse::<Locale>().unwrap(), Locale::ZhCn);
+    }
+
+    #[test]
+    fn every_locale_fills_the_tool_limit_placeholder() {
+        for locale in [Locale::En, Locale::ZhCn] {
+            let text = messages(locale).tool_iteration_limit(7);
+            assert!(text.contains('7'), "{locale}: {text}");
+            assert!(!text.contains("{limit}"), "{locale}: {text}");
+        }
+    }
+}
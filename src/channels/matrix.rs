@@ -1,4 +1,6 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use matrix_sdk::{
     authentication::matrix::MatrixSession,
@@ -706,7 +708,7 @@ impl Channel for MatrixChannel {
         "matrix"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         if self.otk_conflict_detected.load(Ordering::Relaxed) {
             anyhow::bail!("{}", self.otk_conflict_recovery_message());
         }
@@ -732,10 +734,14 @@ impl Channel for MatrixChannel {
         room.send(RoomMessageEventContent::text_markdown(&message.content))
             .await?;
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        tx: mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         if self.otk_conflict_detected.load(Ordering::Relaxed) {
             anyhow::bail!("{}", self.otk_conflict_recovery_message());
         }
@@ -872,9 +878,17 @@ impl Channel for MatrixChannel {
                         .unwrap_or_default()
                         .as_secs(),
                     thread_ts: None,
+                    reply_to_message_id: None,
+                    is_from_self: false,
+                    mentions: Vec::new(),
+                    attachments: Vec::new(),
+                    chat_type: ChatType::Group,
+                    triggered_by_mention: false,
+                    created_at: None,
+                    received_at: chrono::Utc::now(),
                 };
 
-                let _ = tx.send(msg).await;
+                let _ = tx.send(msg.into()).await;
             }
         });
 
@@ -1,5 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
-use anyhow::{bail, Result};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use parking_lot::Mutex;
 
@@ -108,7 +109,7 @@ impl Channel for MattermostChannel {
         "mattermost"
     }
 
-    async fn send(&self, message: &SendMessage) -> Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         // Mattermost supports threading via 'root_id'.
         // We pack 'channel_id:root_id' into recipient if it's a thread.
         let (channel_id, root_id) = if let Some((c, r)) = message.recipient.split_once(':') {
@@ -144,13 +145,17 @@ impl Channel for MattermostChannel {
                 .await
                 .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
             let sanitized = crate::providers::sanitize_api_error(&body);
-            bail!("Mattermost post failed ({status}): {sanitized}");
+            return Err(anyhow::anyhow!("Mattermost post failed ({status}): {sanitized}").into());
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         let channel_id = self
             .channel_id
             .clone()
@@ -214,7 +219,7 @@ impl Channel for MattermostChannel {
                     last_create_at = last_create_at.max(create_at);
 
                     if let Some(channel_msg) = msg {
-                        if tx.send(channel_msg).await.is_err() {
+                        if tx.send(channel_msg.into()).await.is_err() {
                             return Ok(());
                         }
                     }
@@ -233,7 +238,7 @@ impl Channel for MattermostChannel {
             .unwrap_or(false)
     }
 
-    async fn start_typing(&self, recipient: &str) -> Result<()> {
+    async fn start_typing(&self, recipient: &str) -> ChannelResult<()> {
         // Cancel any existing typing loop before starting a new one.
         self.stop_typing(recipient).await?;
 
@@ -280,7 +285,7 @@ impl Channel for MattermostChannel {
         Ok(())
     }
 
-    async fn stop_typing(&self, _recipient: &str) -> Result<()> {
+    async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
         let mut guard = self.typing_handle.lock();
         if let Some(handle) = guard.take() {
             handle.abort();
@@ -344,6 +349,14 @@ impl MattermostChannel {
             #[allow(clippy::cast_sign_loss)]
             timestamp: (create_at / 1000) as u64,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         })
     }
 }
@@ -0,0 +1,233 @@
+//! Per-channel message throughput and send error counters.
+//!
+//! Channels record into a process-wide Prometheus [`Registry`] rather than
+//! through an `Observer`, because they are constructed long before (and
+//! independently of) the runtime observer. Any exporter can gather from
+//! [`registry`]; with the `prometheus` feature the gateway `/metrics`
+//! endpoint appends [`encode`] to its output.
+
+use super::traits::{ChannelError, ChannelResult};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+struct ChannelMetrics {
+    registry: Registry,
+    sent: IntCounterVec,
+    received: IntCounterVec,
+    dropped: IntCounterVec,
+    stale: IntCounterVec,
+    send_errors: IntCounterVec,
+    send_latency: HistogramVec,
+}
+
+impl ChannelMetrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        let sent = IntCounterVec::new(
+            Opts::new(
+                "zeroclaw_messages_sent_total",
+                "Messages delivered by channel send",
+            ),
+            &["channel"],
+        )
+        .expect("valid zeroclaw_messages_sent_total counter");
+        let received = IntCounterVec::new(
+            Opts::new(
+                "zeroclaw_messages_received_total",
+                "Inbound messages forwarded by channel listeners",
+            ),
+            &["channel"],
+        )
+        .expect("valid zeroclaw_messages_received_total counter");
+        let dropped = IntCounterVec::new(
+            Opts::new(
+                "zeroclaw_messages_dropped_total",
+                "Inbound messages discarded because the dispatch queue was full",
+            ),
+            &["channel"],
+        )
+        .expect("valid zeroclaw_messages_dropped_total counter");
+        let stale = IntCounterVec::new(
+            Opts::new(
+                "zeroclaw_stale_messages_dropped_total",
+                "Inbound messages discarded for exceeding the maximum event age",
+            ),
+            &["channel"],
+        )
+        .expect("valid zeroclaw_stale_messages_dropped_total counter");
+        let send_errors = IntCounterVec::new(
+            Opts::new(
+                "zeroclaw_send_errors_total",
+                "Failed channel sends by reason",
+            ),
+            &["channel", "reason"],
+        )
+        .expect("valid zeroclaw_send_errors_total counter");
+        let send_latency = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_send_latency_seconds",
+                "Channel send latency in seconds, including retries",
+            )
+            .buckets(vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]),
+            &["channel"],
+        )
+        .expect("valid zeroclaw_send_latency_seconds histogram");
+
+        for collector in [
+            Box::new(sent.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(received.clone()),
+            Box::new(dropped.clone()),
+            Box::new(stale.clone()),
+            Box::new(send_errors.clone()),
+            Box::new(send_latency.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("channel metrics are registered once");
+        }
+
+        Self {
+            registry,
+            sent,
+            received,
+            dropped,
+            stale,
+            send_errors,
+            send_latency,
+        }
+    }
+}
+
+static METRICS: LazyLock<ChannelMetrics> = LazyLock::new(ChannelMetrics::new);
+
+/// Registry holding the channel metrics, for custom exporters.
+pub fn registry() -> &'static Registry {
+    &METRICS.registry
+}
+
+/// Render the channel metrics in the Prometheus text exposition format.
+pub fn encode() -> String {
+    let mut buf = Vec::new();
+    TextEncoder::new()
+        .encode(&METRICS.registry.gather(), &mut buf)
+        .unwrap_or_default();
+    String::from_utf8(buf).unwrap_or_default()
+}
+
+/// Count one inbound message handed to the runtime.
+pub fn record_received(channel: &str) {
+    METRICS.received.with_label_values(&[channel]).inc();
+}
+
+/// Count one inbound message discarded under backpressure.
+pub fn record_dropped(channel: &str) {
+    METRICS.dropped.with_label_values(&[channel]).inc();
+}
+
+/// Count one inbound message discarded as too old.
+pub fn record_stale(channel: &str) {
+    METRICS.stale.with_label_values(&[channel]).inc();
+}
+
+/// Record the outcome and latency of one `Channel::send` call.
+pub fn record_send<T>(channel: &str, elapsed: Duration, result: &ChannelResult<T>) {
+    METRICS
+        .send_latency
+        .with_label_values(&[channel])
+        .observe(elapsed.as_secs_f64());
+    match result {
+        Ok(_) => METRICS.sent.with_label_values(&[channel]).inc(),
+        Err(err) => METRICS
+            .send_errors
+            .with_label_values(&[channel, send_error_reason(err)])
+            .inc(),
+    }
+}
+
+/// Low-cardinality `reason` label for a failed send.
+pub fn send_error_reason(err: &ChannelError) -> &'static str {
+    if err.is_network() {
+        return "network";
+    }
+    match err {
+        ChannelError::Unauthorized(_) => "unauthorized",
+        ChannelError::RateLimited { .. } => "rate_limited",
+        ChannelError::UserNotAllowed(_) => "user_not_allowed",
+        ChannelError::NotSupported(_) => "not_supported",
+        ChannelError::Network(_) => "network",
+        ChannelError::Unavailable(_) => "unavailable",
+        ChannelError::Api { .. } => "api",
+        ChannelError::CircuitOpen { .. } => "circuit_open",
+        ChannelError::Other(_) => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Metrics are process-wide, so each test uses its own channel label.
+
+    #[test]
+    fn record_send_counts_successes_and_failures_by_reason() {
+        let ok: ChannelResult<()> = Ok(());
+        let limited: ChannelResult<()> = Err(ChannelError::RateLimited { retry_after: None });
+        record_send("metrics_test_send", Duration::from_millis(20), &ok);
+        record_send("metrics_test_send", Duration::from_millis(20), &ok);
+        record_send("metrics_test_send", Duration::from_millis(20), &limited);
+
+        assert_eq!(
+            METRICS.sent.with_label_values(&["metrics_test_send"]).get(),
+            2
+        );
+        assert_eq!(
+            METRICS
+                .send_errors
+                .with_label_values(&["metrics_test_send", "rate_limited"])
+                .get(),
+            1
+        );
+        assert_eq!(
+            METRICS
+                .send_latency
+                .with_label_values(&["metrics_test_send"])
+                .get_sample_count(),
+            3
+        );
+    }
+
+    #[test]
+    fn encode_exposes_requested_metric_names() {
+        record_received("metrics_test_encode");
+        record_dropped("metrics_test_encode");
+        record_stale("metrics_test_encode");
+        let ok: ChannelResult<()> = Ok(());
+        record_send("metrics_test_encode", Duration::from_millis(5), &ok);
+
+        let text = encode();
+        assert!(
+            text.contains("zeroclaw_messages_received_total{channel=\"metrics_test_encode\"} 1")
+        );
+        assert!(text.contains("zeroclaw_messages_sent_total{channel=\"metrics_test_encode\"} 1"));
+        assert!(text.contains("zeroclaw_messages_dropped_total{channel=\"metrics_test_encode\"} 1"));
+        assert!(text
+            .contains("zeroclaw_stale_messages_dropped_total{channel=\"metrics_test_encode\"} 1"));
+        assert!(text.contains("zeroclaw_send_latency_seconds_bucket"));
+    }
+
+    #[test]
+    fn send_error_reason_is_stable_per_variant() {
+        assert_eq!(
+            send_error_reason(&ChannelError::Unauthorized("x".into())),
+            "unauthorized"
+        );
+        assert_eq!(send_error_reason(&ChannelError::api(1, "x")), "api");
+        assert_eq!(
+            send_error_reason(&ChannelError::Other(anyhow::anyhow!("x"))),
+            "other"
+        );
+    }
+}
@@ -0,0 +1,558 @@
+//! Inbound/outbound message middleware for moderation and redaction.
+//!
+//! [`MiddlewareChannel`] wraps any [`Channel`] with an ordered list of
+//! [`MessageMiddleware`]. Outbound messages (`send`, drafts, edits) run
+//! through [`MessageMiddleware::on_outbound`] before reaching the platform;
+//! inbound messages forwarded by `listen` run through
+//! [`MessageMiddleware::on_inbound`] before reaching the runtime. Middleware
+//! runs in insertion order and the first [`Filter::Drop`] stops the chain.
+//!
+//! [`RegexRedactor`] is a built-in middleware that masks text matching a set
+//! of patterns, with a preset for credit card numbers and email addresses.
+
+use super::traits::{
+    Channel, ChannelCapabilities, ChannelEvent, ChannelMessage, ChannelResult, ConfigReload,
+    RichTextElement, SendMessage, SendReceipt,
+};
+use async_trait::async_trait;
+use regex::Regex;
+use std::sync::Arc;
+
+/// Verdict returned by a [`MessageMiddleware`] hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    /// Pass the message on unchanged.
+    Allow,
+    /// Discard the message; later middleware does not run.
+    Drop,
+    /// The hook rewrote the message in place; pass it on.
+    Modify,
+}
+
+/// Hook that inspects, rewrites, or discards messages crossing a channel.
+///
+/// Both hooks default to [`Filter::Allow`], so implementations only override
+/// the direction they care about.
+#[async_trait]
+pub trait MessageMiddleware: Send + Sync {
+    /// Called for every inbound message before it is forwarded to the runtime.
+    async fn on_inbound(&self, _msg: &mut ChannelMessage) -> Filter {
+        Filter::Allow
+    }
+
+    /// Called for every outbound message before it is sent to the platform.
+    async fn on_outbound(&self, _msg: &mut SendMessage) -> Filter {
+        Filter::Allow
+    }
+}
+
+/// Matches 13–19 digit card numbers, optionally grouped by spaces or dashes.
+const CREDIT_CARD_PATTERN: &str = r"\b(?:\d[ -]?){12,18}\d\b";
+const EMAIL_PATTERN: &str = r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b";
+const DEFAULT_MASK: &str = "[redacted]";
+
+/// Middleware that replaces every match of its patterns with a mask.
+///
+/// Applies to both directions. Outbound, it rewrites the text content, rich
+/// text runs, and every string value in an interactive card.
+#[derive(Debug, Clone)]
+pub struct RegexRedactor {
+    patterns: Vec<Regex>,
+    mask: String,
+}
+
+impl RegexRedactor {
+    /// Redact matches of `patterns` with `"[redacted]"`.
+    pub fn new<I, S>(patterns: I) -> Result<Self, regex::Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Ok(Self {
+            patterns: patterns
+                .into_iter()
+                .map(|pattern| Regex::new(pattern.as_ref()))
+                .collect::<Result<_, _>>()?,
+            mask: DEFAULT_MASK.to_string(),
+        })
+    }
+
+    /// Redact credit card numbers and email addresses.
+    pub fn credit_cards_and_emails() -> Self {
+        Self::new([CREDIT_CARD_PATTERN, EMAIL_PATTERN]).expect("built-in patterns are valid")
+    }
+
+    /// Replace matches with `mask` instead of `"[redacted]"`.
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    /// Redact `text` in place, returning whether anything matched.
+    pub fn redact(&self, text: &mut String) -> bool {
+        let mut changed = false;
+        for pattern in &self.patterns {
+            if let std::borrow::Cow::Owned(redacted) =
+                pattern.replace_all(text, regex::NoExpand(&self.mask))
+            {
+                *text = redacted;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    fn redact_json(&self, value: &mut serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::String(text) => self.redact(text),
+            serde_json::Value::Array(items) => items
+                .iter_mut()
+                .fold(false, |changed, item| self.redact_json(item) | changed),
+            serde_json::Value::Object(fields) => fields
+                .values_mut()
+                .fold(false, |changed, field| self.redact_json(field) | changed),
+            _ => false,
+        }
+    }
+
+    fn verdict(changed: bool) -> Filter {
+        if changed {
+            Filter::Modify
+        } else {
+            Filter::Allow
+        }
+    }
+}
+
+#[async_trait]
+impl MessageMiddleware for RegexRedactor {
+    async fn on_inbound(&self, msg: &mut ChannelMessage) -> Filter {
+        Self::verdict(self.redact(&mut msg.content))
+    }
+
+    async fn on_outbound(&self, msg: &mut SendMessage) -> Filter {
+        let mut changed = self.redact(&mut msg.content);
+        if let Some(subject) = msg.subject.as_mut() {
+            changed |= self.redact(subject);
+        }
+        if let Some(rich_text) = msg.rich_text.as_mut() {
+            if let Some(title) = rich_text.title.as_mut() {
+                changed |= self.redact(title);
+            }
+            for element in rich_text.paragraphs.iter_mut().flatten() {
+                if let RichTextElement::Text { text, .. } | RichTextElement::Link { text, .. } =
+                    element
+                {
+                    changed |= self.redact(text);
+                }
+            }
+        }
+        if let Some(card) = msg.card.as_mut() {
+            changed |= self.redact_json(card);
+        }
+        Self::verdict(changed)
+    }
+}
+
+/// Channel wrapper that runs [`MessageMiddleware`] around another channel.
+///
+/// `send`, drafts, and edits go through `on_outbound`; a dropped `send` or
+/// draft reports success with an empty receipt, a dropped draft update or
+/// edit leaves the platform message as it was, and a dropped final draft text
+/// cancels the draft. Inbound messages go through
+/// `on_inbound`; dropped ones are never forwarded. Approval prompts, typing
+/// indicators, reactions, downloads, and history are passed straight through.
+pub struct MiddlewareChannel {
+    inner: Arc<dyn Channel>,
+    middleware: Vec<Box<dyn MessageMiddleware>>,
+}
+
+impl MiddlewareChannel {
+    pub fn new(inner: Arc<dyn Channel>) -> Self {
+        Self {
+            inner,
+            middleware: Vec::new(),
+        }
+    }
+
+    /// Append `middleware`; it runs after every middleware added before it.
+    pub fn with_middleware(mut self, middleware: impl MessageMiddleware + 'static) -> Self {
+        self.middleware.push(Box::new(middleware));
+        self
+    }
+
+    async fn filter_outbound(&self, message: &mut SendMessage) -> Filter {
+        let mut verdict = Filter::Allow;
+        for middleware in &self.middleware {
+            match middleware.on_outbound(message).await {
+                Filter::Allow => {}
+                Filter::Modify => verdict = Filter::Modify,
+                Filter::Drop => {
+                    tracing::debug!(
+                        "Channel {} middleware dropped outbound message to {}",
+                        self.inner.name(),
+                        message.recipient
+                    );
+                    return Filter::Drop;
+                }
+            }
+        }
+        verdict
+    }
+
+    async fn filter_inbound(&self, message: &mut ChannelMessage) -> Filter {
+        let mut verdict = Filter::Allow;
+        for middleware in &self.middleware {
+            match middleware.on_inbound(message).await {
+                Filter::Allow => {}
+                Filter::Modify => verdict = Filter::Modify,
+                Filter::Drop => {
+                    tracing::debug!(
+                        "Channel {} middleware dropped inbound message {} from {}",
+                        self.inner.name(),
+                        message.id,
+                        message.sender
+                    );
+                    return Filter::Drop;
+                }
+            }
+        }
+        verdict
+    }
+
+    /// Run plain draft text through the outbound chain; `None` if dropped.
+    async fn filter_text(&self, recipient: &str, text: &str) -> Option<String> {
+        let mut message = SendMessage::new(text, recipient);
+        match self.filter_outbound(&mut message).await {
+            Filter::Drop => None,
+            Filter::Allow | Filter::Modify => Some(message.content),
+        }
+    }
+}
+
+#[async_trait]
+impl Channel for MiddlewareChannel {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
+        let mut message = message.clone();
+        match self.filter_outbound(&mut message).await {
+            Filter::Drop => Ok(SendReceipt::default()),
+            Filter::Allow | Filter::Modify => self.inner.send(&message).await,
+        }
+    }
+
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
+        let (inner_tx, mut inner_rx) = tokio::sync::mpsc::channel(tx.max_capacity());
+        let forward = async move {
+            while let Some(event) = inner_rx.recv().await {
+                let event = match event {
+                    ChannelEvent::Message(mut message) => {
+                        if self.filter_inbound(&mut message).await == Filter::Drop {
+                            continue;
+                        }
+                        ChannelEvent::Message(message)
+                    }
+                    other => other,
+                };
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        };
+        let (result, ()) = tokio::join!(self.inner.listen(inner_tx, shutdown), forward);
+        result
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+
+    async fn reload_config(&self, config: &crate::config::Config) -> ConfigReload {
+        self.inner.reload_config(config).await
+    }
+
+    fn locale(&self) -> super::locale::Locale {
+        self.inner.locale()
+    }
+
+    fn capabilities(&self) -> ChannelCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn start_typing(&self, recipient: &str) -> ChannelResult<()> {
+        self.inner.start_typing(recipient).await
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> ChannelResult<()> {
+        self.inner.stop_typing(recipient).await
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.inner.supports_draft_updates()
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> ChannelResult<Option<String>> {
+        let mut message = message.clone();
+        match self.filter_outbound(&mut message).await {
+            Filter::Drop => Ok(None),
+            Filter::Allow | Filter::Modify => self.inner.send_draft(&message).await,
+        }
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> ChannelResult<Option<String>> {
+        match self.filter_text(recipient, text).await {
+            Some(text) => self.inner.update_draft(recipient, message_id, &text).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> ChannelResult<()> {
+        match self.filter_text(recipient, text).await {
+            Some(text) => {
+                self.inner
+                    .finalize_draft(recipient, message_id, &text)
+                    .await
+            }
+            None => self.inner.cancel_draft(recipient, message_id).await,
+        }
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> ChannelResult<()> {
+        self.inner.cancel_draft(recipient, message_id).await
+    }
+
+    async fn edit_message(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        new_content: &SendMessage,
+    ) -> ChannelResult<()> {
+        let mut new_content = new_content.clone();
+        match self.filter_outbound(&mut new_content).await {
+            Filter::Drop => Ok(()),
+            Filter::Allow | Filter::Modify => {
+                self.inner
+                    .edit_message(recipient, message_id, &new_content)
+                    .await
+            }
+        }
+    }
+
+    async fn delete_message(&self, message_id: &str) -> ChannelResult<()> {
+        self.inner.delete_message(message_id).await
+    }
+
+    async fn download_attachment(&self, message_id: &str, key: &str) -> ChannelResult<Vec<u8>> {
+        self.inner.download_attachment(message_id, key).await
+    }
+
+    async fn fetch_history(
+        &self,
+        chat_id: &str,
+        limit: usize,
+    ) -> ChannelResult<Vec<ChannelMessage>> {
+        self.inner.fetch_history(chat_id, limit).await
+    }
+
+    async fn send_approval_prompt(
+        &self,
+        recipient: &str,
+        request_id: &str,
+        tool_name: &str,
+        arguments: &serde_json::Value,
+        thread_ts: Option<String>,
+    ) -> ChannelResult<()> {
+        self.inner
+            .send_approval_prompt(recipient, request_id, tool_name, arguments, thread_ts)
+            .await
+    }
+
+    async fn add_reaction(&self, message_id: &str, emoji: &str) -> ChannelResult<String> {
+        self.inner.add_reaction(message_id, emoji).await
+    }
+
+    async fn remove_reaction(&self, message_id: &str, reaction_id: &str) -> ChannelResult<()> {
+        self.inner.remove_reaction(message_id, reaction_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::ChatType;
+    use tokio::sync::{mpsc, watch};
+
+    /// Records sent messages and replays canned inbound messages on `listen`.
+    #[derive(Default)]
+    struct RecordingChannel {
+        sent: parking_lot::Mutex<Vec<SendMessage>>,
+        inbound: Vec<String>,
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
+            self.sent.lock().push(message.clone());
+            Ok(SendReceipt::new("om_1", "chat"))
+        }
+
+        async fn listen(
+            &self,
+            tx: mpsc::Sender<ChannelEvent>,
+            _shutdown: watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
+            for (index, content) in self.inbound.iter().enumerate() {
+                let message = ChannelMessage {
+                    id: format!("m{index}"),
+                    sender: "ou_user".into(),
+                    reply_target: "chat".into(),
+                    content: content.clone(),
+                    channel: "recording".into(),
+                    timestamp: 0,
+                    thread_ts: None,
+                    reply_to_message_id: None,
+                    is_from_self: false,
+                    mentions: Vec::new(),
+                    attachments: Vec::new(),
+                    chat_type: ChatType::Group,
+                    triggered_by_mention: false,
+                    created_at: None,
+                    received_at: chrono::Utc::now(),
+                };
+                tx.send(ChannelEvent::Message(message)).await.unwrap();
+            }
+            Ok(())
+        }
+    }
+
+    /// Drops any message whose content contains `"spam"`.
+    struct SpamFilter;
+
+    #[async_trait]
+    impl MessageMiddleware for SpamFilter {
+        async fn on_inbound(&self, msg: &mut ChannelMessage) -> Filter {
+            if msg.content.contains("spam") {
+                Filter::Drop
+            } else {
+                Filter::Allow
+            }
+        }
+
+        async fn on_outbound(&self, msg: &mut SendMessage) -> Filter {
+            if msg.content.contains("spam") {
+                Filter::Drop
+            } else {
+                Filter::Allow
+            }
+        }
+    }
+
+    #[test]
+    fn redactor_masks_cards_and_emails() {
+        let redactor = RegexRedactor::credit_cards_and_emails();
+        let mut text =
+            "card 4111 1111 1111 1111, mail Jane.Doe@example.com, order 12345".to_string();
+
+        assert!(redactor.redact(&mut text));
+        assert_eq!(text, "card [redacted], mail [redacted], order 12345");
+
+        let mut clean = "nothing to see".to_string();
+        assert!(!redactor.redact(&mut clean));
+    }
+
+    #[test]
+    fn redactor_uses_custom_patterns_and_mask() {
+        let redactor = RegexRedactor::new([r"sk-[A-Za-z0-9]+"])
+            .unwrap()
+            .with_mask("$1***");
+        let mut text = "key sk-abc123".to_string();
+
+        assert!(redactor.redact(&mut text));
+        assert_eq!(text, "key $1***");
+        assert!(RegexRedactor::new(["("]).is_err());
+    }
+
+    #[tokio::test]
+    async fn outbound_redaction_covers_rich_text_and_cards() {
+        let inner = Arc::new(RecordingChannel::default());
+        let channel = MiddlewareChannel::new(Arc::clone(&inner) as Arc<dyn Channel>)
+            .with_middleware(RegexRedactor::credit_cards_and_emails());
+        let message = SendMessage::new("reach me at a@b.io", "chat")
+            .with_rich_text(
+                crate::channels::traits::RichText::new().text("card 4111-1111-1111-1111"),
+            )
+            .with_card(serde_json::json!({"elements": [{"content": "c@d.io"}]}));
+
+        let receipt = channel.send(&message).await.unwrap();
+
+        assert_eq!(receipt.message_id.as_deref(), Some("om_1"));
+        let sent = inner.sent.lock();
+        assert_eq!(sent[0].content, "reach me at [redacted]");
+        assert!(matches!(
+            &sent[0].rich_text.as_ref().unwrap().paragraphs[0][0],
+            RichTextElement::Text { text, .. } if text == "card [redacted]"
+        ));
+        assert_eq!(
+            sent[0].card.as_ref().unwrap()["elements"][0]["content"],
+            "[redacted]"
+        );
+    }
+
+    #[tokio::test]
+    async fn dropped_outbound_message_is_never_sent() {
+        let inner = Arc::new(RecordingChannel::default());
+        let channel = MiddlewareChannel::new(Arc::clone(&inner) as Arc<dyn Channel>)
+            .with_middleware(SpamFilter)
+            .with_middleware(RegexRedactor::credit_cards_and_emails());
+
+        let receipt = channel
+            .send(&SendMessage::new("buy spam", "chat"))
+            .await
+            .unwrap();
+
+        assert_eq!(receipt, SendReceipt::default());
+        assert!(inner.sent.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn listen_filters_and_rewrites_inbound_messages() {
+        let inner = Arc::new(RecordingChannel {
+            inbound: vec!["hi".into(), "spam offer".into(), "mail x@y.org".into()],
+            ..RecordingChannel::default()
+        });
+        let channel = MiddlewareChannel::new(inner as Arc<dyn Channel>)
+            .with_middleware(SpamFilter)
+            .with_middleware(RegexRedactor::credit_cards_and_emails());
+        let (tx, mut rx) = mpsc::channel(8);
+        let (_shutdown_tx, shutdown) = watch::channel(false);
+
+        channel.listen(tx, shutdown).await.unwrap();
+
+        let mut received = Vec::new();
+        while let Some(event) = rx.recv().await {
+            received.push(event.into_message().unwrap().content);
+        }
+        assert_eq!(received, ["hi", "mail [redacted]"]);
+    }
+}
@@ -0,0 +1,193 @@
+//! In-memory channel for exercising routing and command handling offline.
+//!
+//! [`MockChannel`] records every outbound [`SendMessage`] and replays inbound
+//! [`ChannelMessage`]s pushed with [`MockChannel::inject`] into the `listen`
+//! sender. Only compiled for tests or with the `testing` feature.
+
+use super::traits::{
+    wait_for_shutdown, Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage,
+    SendReceipt,
+};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Channel double that never touches the network.
+pub struct MockChannel {
+    name: String,
+    sent: Arc<Mutex<Vec<SendMessage>>>,
+    inbound_tx: mpsc::UnboundedSender<ChannelMessage>,
+    inbound_rx: Mutex<Option<mpsc::UnboundedReceiver<ChannelMessage>>>,
+}
+
+impl MockChannel {
+    pub fn new(name: impl Into<String>) -> Self {
+        let (inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+        Self {
+            name: name.into(),
+            sent: Arc::new(Mutex::new(Vec::new())),
+            inbound_tx,
+            inbound_rx: Mutex::new(Some(inbound_rx)),
+        }
+    }
+
+    /// Queue an inbound message; delivered once `listen` is running.
+    pub fn inject(&self, msg: ChannelMessage) {
+        // The receiver lives in `self` until `listen` takes it, so this cannot fail
+        // while the channel is alive.
+        let _ = self.inbound_tx.send(msg);
+    }
+
+    /// Build an inbound message addressed to this channel.
+    pub fn message(&self, sender: &str, content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            sender: sender.to_string(),
+            reply_target: sender.to_string(),
+            content: content.to_string(),
+            channel: self.name.clone(),
+            timestamp: 0,
+            thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Snapshot of every message passed to `send`, in order.
+    pub fn sent_messages(&self) -> Vec<SendMessage> {
+        self.sent.lock().clone()
+    }
+}
+
+#[async_trait]
+impl Channel for MockChannel {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
+        let mut sent = self.sent.lock();
+        sent.push(message.clone());
+        Ok(SendReceipt::new(
+            format!("mock-{}", sent.len()),
+            message.recipient.clone(),
+        ))
+    }
+
+    async fn listen(
+        &self,
+        tx: mpsc::Sender<ChannelEvent>,
+        mut shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
+        let mut inbound = self
+            .inbound_rx
+            .lock()
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("MockChannel::listen may only run once"))?;
+        loop {
+            tokio::select! {
+                () = wait_for_shutdown(&mut shutdown) => break,
+                msg = inbound.recv() => {
+                    let Some(msg) = msg else { break };
+                    if tx.send(msg.into()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn send_records_messages_and_returns_receipts() {
+        let channel = MockChannel::new("mock");
+
+        let first = channel
+            .send(&SendMessage::new("hi", "alice"))
+            .await
+            .unwrap();
+        let second = channel.send(&SendMessage::new("bye", "bob")).await.unwrap();
+
+        assert_eq!(first, SendReceipt::new("mock-1", "alice"));
+        assert_eq!(second.message_id.as_deref(), Some("mock-2"));
+        let sent = channel.sent_messages();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].content, "hi");
+        assert_eq!(sent[1].recipient, "bob");
+    }
+
+    #[tokio::test]
+    async fn injected_messages_reach_listen_in_order() {
+        let channel = Arc::new(MockChannel::new("mock"));
+        channel.inject(channel.message("alice", "first"));
+
+        let (tx, mut rx) = mpsc::channel(4);
+        let listener = tokio::spawn({
+            let channel = Arc::clone(&channel);
+            async move {
+                channel
+                    .listen(tx, tokio::sync::watch::channel(false).1)
+                    .await
+            }
+        });
+        channel.inject(channel.message("alice", "second"));
+
+        let first = rx.recv().await.and_then(ChannelEvent::into_message);
+        assert_eq!(first.unwrap().content, "first");
+        let second = rx
+            .recv()
+            .await
+            .and_then(ChannelEvent::into_message)
+            .unwrap();
+        assert_eq!(second.content, "second");
+        assert_eq!(second.channel, "mock");
+
+        drop(rx);
+        channel.inject(channel.message("alice", "after close"));
+        listener.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn listen_runs_only_once() {
+        let channel = MockChannel::new("mock");
+        let (tx, rx) = mpsc::channel(1);
+        drop(rx);
+        channel.inject(channel.message("alice", "x"));
+        channel
+            .listen(tx.clone(), tokio::sync::watch::channel(false).1)
+            .await
+            .unwrap();
+
+        let err = channel
+            .listen(tx, tokio::sync::watch::channel(false).1)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("only run once"));
+    }
+
+    #[tokio::test]
+    async fn listen_returns_when_shutdown_is_signalled() {
+        let channel = MockChannel::new("mock");
+        let (tx, _rx) = mpsc::channel(1);
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+        let listener = channel.listen(tx, shutdown_rx);
+        shutdown_tx.send(true).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(1), listener)
+            .await
+            .expect("listen should stop on shutdown")
+            .unwrap();
+    }
+}
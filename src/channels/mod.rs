@@ -17,30 +17,43 @@
 pub(crate) mod ack_reaction;
 pub mod acp;
 pub mod bluebubbles;
+pub mod circuit_breaker;
 pub mod clawdtalk;
 pub mod cli;
 pub mod dingtalk;
 pub mod discord;
 pub mod email_channel;
+pub mod emoji;
+pub mod feishu_errors;
 pub mod github;
 pub mod imessage;
 pub mod irc;
 #[cfg(feature = "channel-lark")]
 pub mod lark;
 pub mod linq;
+pub mod locale;
 #[cfg(feature = "channel-matrix")]
 pub mod matrix;
 pub mod mattermost;
+pub mod metrics;
+pub mod middleware;
+#[cfg(any(test, feature = "testing"))]
+pub mod mock;
 pub mod napcat;
 pub mod nextcloud_talk;
 pub mod nostr;
+pub mod outbound_queue;
+pub(crate) mod proxy;
 pub mod qq;
+pub mod registry;
 pub mod signal;
 pub mod slack;
 pub mod telegram;
+pub mod template;
 pub mod traits;
 pub mod transcription;
 pub mod wati;
+pub mod webhook;
 pub mod whatsapp;
 #[cfg(feature = "whatsapp-web")]
 pub mod whatsapp_storage;
@@ -49,6 +62,7 @@ pub mod whatsapp_web;
 
 pub use acp::AcpChannel;
 pub use bluebubbles::BlueBubblesChannel;
+pub use circuit_breaker::CircuitBreakerChannel;
 pub use clawdtalk::ClawdTalkChannel;
 pub use cli::CliChannel;
 pub use dingtalk::DingTalkChannel;
@@ -70,7 +84,7 @@ pub use qq::QQChannel;
 pub use signal::SignalChannel;
 pub use slack::SlackChannel;
 pub use telegram::TelegramChannel;
-pub use traits::{Channel, SendMessage};
+pub use traits::{Channel, SendMessage, TypingGuard};
 pub use wati::WatiChannel;
 pub use whatsapp::WhatsAppChannel;
 #[cfg(feature = "whatsapp-web")]
@@ -83,7 +97,7 @@ use crate::agent::loop_::{
 };
 use crate::agent::session::{resolve_session_id, shared_session_manager, Session, SessionManager};
 use crate::approval::{ApprovalManager, ApprovalResponse, PendingApprovalError};
-use crate::config::{Config, NonCliNaturalLanguageApprovalMode, ProgressMode};
+use crate::config::{Config, NonCliNaturalLanguageApprovalMode, ProgressMode, TelegramReceiveMode};
 use crate::identity;
 use crate::memory::{self, Memory};
 use crate::observability::{self, runtime_trace, Observer};
@@ -127,8 +141,11 @@ const CHANNEL_MESSAGE_TIMEOUT_SCALE_CAP: u64 = 4;
 const CHANNEL_PARALLELISM_PER_CHANNEL: usize = 4;
 const CHANNEL_MIN_IN_FLIGHT_MESSAGES: usize = 8;
 const CHANNEL_MAX_IN_FLIGHT_MESSAGES: usize = 64;
-const CHANNEL_TYPING_REFRESH_INTERVAL_SECS: u64 = 4;
 const CHANNEL_HEALTH_HEARTBEAT_SECS: u64 = 30;
+/// How long a listener gets to close cleanly after shutdown is signalled.
+const CHANNEL_LISTENER_SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+/// How often the channel server checks the config file for edits.
+const CONFIG_WATCH_POLL_INTERVAL: Duration = Duration::from_secs(5);
 const MODEL_CACHE_FILE: &str = "models_cache.json";
 const MODEL_CACHE_PREVIEW_LIMIT: usize = 10;
 const MEMORY_CONTEXT_MAX_ENTRIES: usize = 4;
@@ -357,6 +374,8 @@ struct ChannelRuntimeContext {
     approval_manager: Arc<ApprovalManager>,
     safety_heartbeat: Option<SafetyHeartbeatConfig>,
     startup_perplexity_filter: crate::config::PerplexityFilterConfig,
+    /// Durable queue for final replies (`[channels_config.outbound_queue]`).
+    outbound_queue: Option<Arc<outbound_queue::OutboundQueue>>,
 }
 
 #[derive(Clone)]
@@ -573,7 +592,9 @@ fn strip_tool_call_tags(message: &str) -> String {
 }
 
 fn channel_delivery_instructions(channel_name: &str) -> Option<&'static str> {
-    match channel_name {
+    // Named instances such as `feishu:prod` share their platform's instructions.
+    let base_name = channel_name.split(':').next().unwrap_or(channel_name);
+    match base_name {
         "telegram" => Some(
             "When responding on Telegram:\n\
              - Include media markers for files or URLs that should be sent as attachments\n\
@@ -3407,13 +3428,15 @@ fn strip_isolated_tool_json_artifacts(message: &str, known_tool_names: &HashSet<
 
 fn spawn_supervised_listener(
     ch: Arc<dyn Channel>,
-    tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+    tx: tokio::sync::mpsc::Sender<traits::ChannelEvent>,
+    shutdown: tokio::sync::watch::Receiver<bool>,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
 ) -> tokio::task::JoinHandle<()> {
     spawn_supervised_listener_with_health_interval(
         ch,
         tx,
+        shutdown,
         initial_backoff_secs,
         max_backoff_secs,
         Duration::from_secs(CHANNEL_HEALTH_HEARTBEAT_SECS),
@@ -3422,7 +3445,8 @@ fn spawn_supervised_listener(
 
 fn spawn_supervised_listener_with_health_interval(
     ch: Arc<dyn Channel>,
-    tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+    tx: tokio::sync::mpsc::Sender<traits::ChannelEvent>,
+    shutdown: tokio::sync::watch::Receiver<bool>,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
     health_interval: Duration,
@@ -3439,24 +3463,49 @@ fn spawn_supervised_listener_with_health_interval(
         let max_backoff = max_backoff_secs.max(backoff);
 
         loop {
+            if *shutdown.borrow() {
+                break;
+            }
             crate::health::mark_component_ok(&component);
             let mut health = tokio::time::interval(health_interval);
             health.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
             let result = {
-                let listen_future = ch.listen(tx.clone());
+                let listen_future = ch.listen(tx.clone(), shutdown.clone());
                 tokio::pin!(listen_future);
+                let mut shutdown_signal = shutdown.clone();
 
                 loop {
                     tokio::select! {
                         _ = health.tick() => {
                             crate::health::mark_component_ok(&component);
                         }
+                        () = traits::wait_for_shutdown(&mut shutdown_signal) => {
+                            match tokio::time::timeout(
+                                CHANNEL_LISTENER_SHUTDOWN_GRACE,
+                                &mut listen_future,
+                            )
+                            .await
+                            {
+                                Ok(result) => break result,
+                                Err(_) => {
+                                    tracing::warn!(
+                                        "Channel {} did not stop within {:?}; dropping listener",
+                                        ch.name(),
+                                        CHANNEL_LISTENER_SHUTDOWN_GRACE
+                                    );
+                                    break Ok(());
+                                }
+                            }
+                        }
                         result = &mut listen_future => break result,
                     }
                 }
             };
 
-            if tx.is_closed() {
+            if tx.is_closed() || *shutdown.borrow() {
+                if let Err(e) = result {
+                    tracing::warn!("Channel {} stopped with error: {e}", ch.name());
+                }
                 break;
             }
 
@@ -3496,36 +3545,6 @@ fn log_worker_join_result(result: Result<(), tokio::task::JoinError>) {
     }
 }
 
-fn spawn_scoped_typing_task(
-    channel: Arc<dyn Channel>,
-    recipient: String,
-    cancellation_token: CancellationToken,
-) -> tokio::task::JoinHandle<()> {
-    let stop_signal = cancellation_token;
-    let refresh_interval = Duration::from_secs(CHANNEL_TYPING_REFRESH_INTERVAL_SECS);
-    let handle = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(refresh_interval);
-        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-
-        loop {
-            tokio::select! {
-                () = stop_signal.cancelled() => break,
-                _ = interval.tick() => {
-                    if let Err(e) = channel.start_typing(&recipient).await {
-                        tracing::debug!("Failed to start typing on {}: {e}", channel.name());
-                    }
-                }
-            }
-        }
-
-        if let Err(e) = channel.stop_typing(&recipient).await {
-            tracing::debug!("Failed to stop typing on {}: {e}", channel.name());
-        }
-    });
-
-    handle
-}
-
 async fn process_channel_message(
     ctx: Arc<ChannelRuntimeContext>,
     msg: traits::ChannelMessage,
@@ -4007,24 +4026,18 @@ If this input is legitimate, rephrase the request and avoid instruction-override
     };
 
     // React with 👀 to acknowledge the incoming message
+    let mut ack_reaction_id = None;
     if let Some(channel) = target_channel.as_ref() {
-        if let Err(e) = channel
-            .add_reaction(&msg.reply_target, &msg.id, "\u{1F440}")
-            .await
-        {
-            tracing::debug!("Failed to add reaction: {e}");
+        match channel.add_reaction(&msg.id, "\u{1F440}").await {
+            Ok(reaction_id) => ack_reaction_id = Some(reaction_id),
+            Err(traits::ChannelError::NotSupported(_)) => {}
+            Err(e) => tracing::debug!("Failed to add reaction: {e}"),
         }
     }
 
-    let typing_cancellation = target_channel.as_ref().map(|_| CancellationToken::new());
-    let typing_task = match (target_channel.as_ref(), typing_cancellation.as_ref()) {
-        (Some(channel), Some(token)) => Some(spawn_scoped_typing_task(
-            Arc::clone(channel),
-            msg.reply_target.clone(),
-            token.clone(),
-        )),
-        _ => None,
-    };
+    let typing_guard = target_channel
+        .as_ref()
+        .map(|channel| TypingGuard::start(Arc::clone(channel), msg.reply_target.clone()));
 
     // Record history length before tool loop so we can extract tool context after.
     let history_len_before_tools = history.len();
@@ -4122,13 +4135,16 @@ If this input is legitimate, rephrase the request and avoid instruction-override
         let _ = handle.await;
     }
 
-    if let Some(token) = typing_cancellation.as_ref() {
-        token.cancel();
-    }
-    if let Some(handle) = typing_task {
-        log_worker_join_result(handle.await);
+    if let Some(guard) = typing_guard {
+        guard.stop().await;
     }
 
+    let system_messages = locale::messages(
+        target_channel
+            .as_ref()
+            .map_or(locale::Locale::En, |channel| channel.locale()),
+    );
+
     let reaction_done_emoji = match &llm_result {
         LlmExecutionResult::Completed(Ok(Ok(_))) => "\u{2705}", // ✅
         _ => "\u{26A0}\u{FE0F}",                                // ⚠️
@@ -4308,19 +4324,21 @@ If this input is legitimate, rephrase the request and avoid instruction-override
                         .await
                     {
                         tracing::warn!("Failed to finalize draft: {e}; sending as new message");
-                        let _ = channel
-                            .send(
-                                &SendMessage::new(&delivered_response, &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
-                            )
-                            .await;
+                        let _ = send_final_reply(
+                            ctx.as_ref(),
+                            channel,
+                            SendMessage::new(&delivered_response, &msg.reply_target)
+                                .in_thread(msg.thread_ts.clone()),
+                        )
+                        .await;
                     }
-                } else if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await
+                } else if let Err(e) = send_final_reply(
+                    ctx.as_ref(),
+                    channel,
+                    SendMessage::new(delivered_response, &msg.reply_target)
+                        .in_thread(msg.thread_ts.clone()),
+                )
+                .await
                 {
                     eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
                 }
@@ -4357,9 +4375,9 @@ If this input is legitimate, rephrase the request and avoid instruction-override
             } else if is_context_window_overflow_error(&e) {
                 let compacted = compact_sender_history(ctx.as_ref(), &history_key);
                 let error_text = if compacted {
-                    "⚠️ Context window exceeded for this conversation. I compacted recent history and kept the latest context. Please resend your last message."
+                    system_messages.context_window_compacted
                 } else {
-                    "⚠️ Context window exceeded for this conversation. Please resend your last message."
+                    system_messages.context_window_exceeded
                 };
                 eprintln!(
                     "  ⚠️ Context window exceeded after {}ms; sender history compacted={}",
@@ -4396,9 +4414,7 @@ If this input is legitimate, rephrase the request and avoid instruction-override
                 }
             } else if is_tool_iteration_limit_error(&e) {
                 let limit = runtime_defaults.max_tool_iterations.max(1);
-                let pause_text = format!(
-                    "⚠️ Reached tool-iteration limit ({limit}) for this turn. Context and progress were preserved. Reply \"continue\" to resume, or increase `agent.max_tool_iterations`."
-                );
+                let pause_text = system_messages.tool_iteration_limit(limit);
                 runtime_trace::record_event(
                     "channel_message_error",
                     Some(msg.channel.as_str()),
@@ -4475,13 +4491,20 @@ If this input is legitimate, rephrase the request and avoid instruction-override
                 if let Some(channel) = target_channel.as_ref() {
                     if let Some(ref draft_id) = draft_message_id {
                         let _ = channel
-                            .finalize_draft(&msg.reply_target, draft_id, &format!("⚠️ Error: {e}"))
+                            .finalize_draft(
+                                &msg.reply_target,
+                                draft_id,
+                                &format!("{}{e}", system_messages.error_prefix),
+                            )
                             .await;
                     } else {
                         let _ = channel
                             .send(
-                                &SendMessage::new(format!("⚠️ Error: {e}"), &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
+                                &SendMessage::new(
+                                    format!("{}{e}", system_messages.error_prefix),
+                                    &msg.reply_target,
+                                )
+                                .in_thread(msg.thread_ts.clone()),
                             )
                             .await;
                     }
@@ -4521,8 +4544,7 @@ If this input is legitimate, rephrase the request and avoid instruction-override
                 ChatMessage::assistant("[Task timed out — not continuing this request]"),
             );
             if let Some(channel) = target_channel.as_ref() {
-                let error_text =
-                    "⚠️ Request timed out while waiting for the model. Please try again.";
+                let error_text = system_messages.request_timed_out;
                 if let Some(ref draft_id) = draft_message_id {
                     let _ = channel
                         .finalize_draft(&msg.reply_target, draft_id, error_text)
@@ -4541,17 +4563,50 @@ If this input is legitimate, rephrase the request and avoid instruction-override
 
     // Swap 👀 → ✅ (or ⚠️ on error) to signal processing is complete
     if let Some(channel) = target_channel.as_ref() {
-        let _ = channel
-            .remove_reaction(&msg.reply_target, &msg.id, "\u{1F440}")
-            .await;
-        let _ = channel
-            .add_reaction(&msg.reply_target, &msg.id, reaction_done_emoji)
-            .await;
+        if let Some(reaction_id) = ack_reaction_id {
+            let _ = channel.remove_reaction(&msg.id, &reaction_id).await;
+        }
+        let _ = channel.add_reaction(&msg.id, reaction_done_emoji).await;
+    }
+}
+
+/// Send the final reply to a message, through the outbound queue when one is
+/// configured so the reply survives a restart.
+async fn send_final_reply(
+    ctx: &ChannelRuntimeContext,
+    channel: &Arc<dyn Channel>,
+    message: SendMessage,
+) -> anyhow::Result<()> {
+    match ctx.outbound_queue.as_ref() {
+        Some(queue) => {
+            queue.enqueue(channel.name(), message).await?;
+        }
+        None => {
+            channel.send(&message).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Reflect a channel connection lifecycle event in the health registry.
+fn record_channel_lifecycle(event: &traits::ChannelEvent) {
+    match event {
+        traits::ChannelEvent::Message(_)
+        | traits::ChannelEvent::CardAction { .. }
+        | traits::ChannelEvent::FormSubmit { .. } => {}
+        traits::ChannelEvent::Connected { channel } => {
+            tracing::info!("Channel {channel} connected");
+            crate::health::mark_component_ok(&format!("channel:{channel}"));
+        }
+        traits::ChannelEvent::Disconnected { channel, reason } => {
+            tracing::warn!("Channel {channel} disconnected: {reason}");
+            crate::health::mark_component_error(&format!("channel:{channel}"), reason);
+        }
     }
 }
 
 async fn run_message_dispatch_loop(
-    mut rx: tokio::sync::mpsc::Receiver<traits::ChannelMessage>,
+    mut rx: tokio::sync::mpsc::Receiver<traits::ChannelEvent>,
     ctx: Arc<ChannelRuntimeContext>,
     max_in_flight_messages: usize,
 ) {
@@ -4563,7 +4618,30 @@ async fn run_message_dispatch_loop(
     >::new()));
     let task_sequence = Arc::new(AtomicU64::new(1));
 
-    while let Some(msg) = rx.recv().await {
+    while let Some(event) = rx.recv().await {
+        let msg = match event {
+            traits::ChannelEvent::Message(msg) => msg,
+            traits::ChannelEvent::CardAction {
+                channel,
+                message_id,
+                ..
+            } => {
+                tracing::debug!("Channel {channel}: ignoring card action on {message_id}");
+                continue;
+            }
+            traits::ChannelEvent::FormSubmit {
+                channel,
+                message_id,
+                ..
+            } => {
+                tracing::debug!("Channel {channel}: ignoring form submission on {message_id}");
+                continue;
+            }
+            lifecycle => {
+                record_channel_lifecycle(&lifecycle);
+                continue;
+            }
+        };
         let permit = match Arc::clone(&semaphore).acquire_owned().await {
             Ok(permit) => permit,
             Err(_) => break,
@@ -5232,6 +5310,101 @@ fn classify_health_result(
     }
 }
 
+/// SIGHUP stream for config reloads; never fires off Unix.
+struct HangupSignal {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl HangupSignal {
+    fn new() -> Self {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            let signal = signal(SignalKind::hangup())
+                .inspect_err(|err| tracing::warn!("SIGHUP config reload unavailable: {err}"))
+                .ok();
+            Self { signal }
+        }
+        #[cfg(not(unix))]
+        {
+            Self {}
+        }
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = self.signal.as_mut() {
+            if signal.recv().await.is_some() {
+                return;
+            }
+        }
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Reload the config on SIGHUP or when the file changes on disk, and hand it
+/// to every running channel so access lists and rate limits change without
+/// dropping connections.
+fn spawn_config_watcher(
+    config_path: PathBuf,
+    registry: Arc<registry::ChannelRegistry>,
+    mut shutdown: tokio::sync::watch::Receiver<bool>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut hangup = HangupSignal::new();
+        let mut last_stamp = config_file_stamp(&config_path).await;
+        let mut poll = tokio::time::interval(CONFIG_WATCH_POLL_INTERVAL);
+        poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                () = traits::wait_for_shutdown(&mut shutdown) => break,
+                () = hangup.recv() => {
+                    tracing::info!("SIGHUP received; reloading {}", config_path.display());
+                    last_stamp = config_file_stamp(&config_path).await;
+                }
+                _ = poll.tick() => {
+                    let stamp = config_file_stamp(&config_path).await;
+                    if stamp.is_none() || stamp == last_stamp {
+                        continue;
+                    }
+                    tracing::info!("{} changed; reloading", config_path.display());
+                    last_stamp = stamp;
+                }
+            }
+            reload_channel_configs(&config_path, &registry).await;
+        }
+    })
+}
+
+async fn reload_channel_configs(config_path: &Path, registry: &registry::ChannelRegistry) {
+    let mut config = match crate::config::load_from_path(config_path) {
+        Ok(config) => config,
+        Err(err) => {
+            tracing::warn!("Config reload rejected; channels keep their settings: {err:#}");
+            return;
+        }
+    };
+    config.apply_env_overrides();
+
+    for channel in registry.iter() {
+        match channel.reload_config(&config).await {
+            traits::ConfigReload::Unchanged => {}
+            traits::ConfigReload::Applied => {
+                tracing::info!("Channel {} updated from reloaded config", channel.name());
+            }
+            traits::ConfigReload::Reconnecting => {
+                tracing::info!(
+                    "Channel {} reconnecting with reloaded config",
+                    channel.name()
+                );
+            }
+        }
+    }
+}
+
 struct ConfiguredChannel {
     display_name: &'static str,
     channel: Arc<dyn Channel>,
@@ -5262,6 +5435,13 @@ fn collect_configured_channels(
         if let Some(ref base_url) = tg.base_url {
             telegram = telegram.with_api_base(base_url.clone());
         }
+        if tg.receive_mode == TelegramReceiveMode::Webhook {
+            telegram = telegram.with_webhook(
+                tg.webhook_port,
+                tg.webhook_url.clone(),
+                tg.webhook_secret.clone(),
+            );
+        }
 
         channels.push(ConfiguredChannel {
             display_name: "Telegram",
@@ -5281,6 +5461,7 @@ fn collect_configured_channels(
                     dc.effective_group_reply_mode().requires_mention(),
                 )
                 .with_group_reply_allowed_senders(dc.group_reply_allowed_sender_ids())
+                .with_intents(dc.intents)
                 .with_ack_reaction(config.channels_config.ack_reaction.discord.clone())
                 .with_transcription(config.transcription.clone())
                 .with_workspace_dir(config.workspace_dir.clone()),
@@ -5496,6 +5677,21 @@ fn collect_configured_channels(
         });
     }
 
+    #[cfg(feature = "channel-lark")]
+    let webhook_server = config
+        .channels_config
+        .webhook_server
+        .as_ref()
+        .map(webhook::WebhookServer::from_config);
+
+    // One client per platform, so every Lark/Feishu channel shares a
+    // connection pool.
+    #[cfg(feature = "channel-lark")]
+    let (lark_http_client, feishu_http_client) = (
+        LarkChannel::default_http_client(false),
+        LarkChannel::default_http_client(true),
+    );
+
     #[cfg(feature = "channel-lark")]
     if let Some(ref lk) = config.channels_config.lark {
         if lk.use_feishu {
@@ -5511,7 +5707,9 @@ fn collect_configured_channels(
                     display_name: "Feishu",
                     channel: Arc::new(
                         LarkChannel::from_config(lk)
-                            .with_ack_reaction(config.channels_config.ack_reaction.feishu.clone()),
+                            .with_http_client(feishu_http_client.clone())
+                            .with_ack_reaction(config.channels_config.ack_reaction.feishu.clone())
+                            .with_webhook_server(webhook_server.clone()),
                     ),
                 });
             }
@@ -5520,7 +5718,9 @@ fn collect_configured_channels(
                 display_name: "Lark",
                 channel: Arc::new(
                     LarkChannel::from_lark_config(lk)
-                        .with_ack_reaction(config.channels_config.ack_reaction.lark.clone()),
+                        .with_http_client(lark_http_client.clone())
+                        .with_ack_reaction(config.channels_config.ack_reaction.lark.clone())
+                        .with_webhook_server(webhook_server.clone()),
                 ),
             });
         }
@@ -5532,13 +5732,32 @@ fn collect_configured_channels(
             display_name: "Feishu",
             channel: Arc::new(
                 LarkChannel::from_feishu_config(fs)
-                    .with_ack_reaction(config.channels_config.ack_reaction.feishu.clone()),
+                    .with_http_client(feishu_http_client.clone())
+                    .with_ack_reaction(config.channels_config.ack_reaction.feishu.clone())
+                    .with_webhook_server(webhook_server.clone()),
+            ),
+        });
+    }
+
+    #[cfg(feature = "channel-lark")]
+    for (name, fs) in &config.channels_config.feishu_apps {
+        channels.push(ConfiguredChannel {
+            display_name: "Feishu",
+            channel: Arc::new(
+                LarkChannel::from_feishu_config(fs)
+                    .with_http_client(feishu_http_client.clone())
+                    .with_name(format!("feishu:{name}"))
+                    .with_ack_reaction(config.channels_config.ack_reaction.feishu.clone())
+                    .with_webhook_server(webhook_server.clone()),
             ),
         });
     }
 
     #[cfg(not(feature = "channel-lark"))]
-    if config.channels_config.lark.is_some() || config.channels_config.feishu.is_some() {
+    if config.channels_config.lark.is_some()
+        || config.channels_config.feishu.is_some()
+        || !config.channels_config.feishu_apps.is_empty()
+    {
         let executable = std::env::current_exe()
             .map(|path| path.display().to_string())
             .unwrap_or_else(|_| "<unknown>".to_string());
@@ -5605,6 +5824,78 @@ fn collect_configured_channels(
     channels
 }
 
+/// Put `channel` behind a [`CircuitBreakerChannel`] when one is configured.
+fn wrap_with_circuit_breaker(
+    channel: Arc<dyn Channel>,
+    config: Option<&crate::config::schema::ChannelCircuitBreakerConfig>,
+) -> Arc<dyn Channel> {
+    match config {
+        Some(cb) => Arc::new(CircuitBreakerChannel::new(
+            channel,
+            cb.failure_threshold,
+            Duration::from_secs(cb.cooldown_secs),
+        )),
+        None => channel,
+    }
+}
+
+/// Build every channel configured under `[channels_config]` into one
+/// registry, wrapped in the circuit breaker and downgrade policy the config
+/// asks for. Fails naming the first configured section this build cannot
+/// construct, instead of silently leaving that channel out.
+///
+/// Nostr needs an async relay handshake and is not included; the channel
+/// server adds it separately.
+pub fn build_all(config: &Config) -> Result<registry::ChannelRegistry> {
+    if let Some(section) = unsupported_channel_sections(config).into_iter().next() {
+        anyhow::bail!(
+            "[channels_config.{section}] is configured but this build cannot construct that channel type"
+        );
+    }
+    Ok(into_registry(
+        collect_configured_channels(config, "registry build"),
+        config,
+    ))
+}
+
+/// Configured sections whose channel type is compiled out of this build.
+fn unsupported_channel_sections(config: &Config) -> Vec<String> {
+    // Nothing is pushed (or read from `config`) when every feature is on.
+    #[allow(unused_mut)]
+    let mut sections = Vec::new();
+    #[cfg(not(feature = "channel-lark"))]
+    {
+        if config.channels_config.lark.is_some() {
+            sections.push("lark".to_string());
+        }
+        if config.channels_config.feishu.is_some() {
+            sections.push("feishu".to_string());
+        }
+        sections.extend(
+            config
+                .channels_config
+                .feishu_apps
+                .keys()
+                .map(|name| format!("feishu_apps.{name}")),
+        );
+    }
+    #[cfg(not(feature = "channel-matrix"))]
+    if config.channels_config.matrix.is_some() {
+        sections.push("matrix".to_string());
+    }
+    let _ = config;
+    sections
+}
+
+fn into_registry(channels: Vec<ConfiguredChannel>, config: &Config) -> registry::ChannelRegistry {
+    let circuit_breaker = config.channels_config.circuit_breaker.as_ref();
+    channels
+        .into_iter()
+        .map(|configured| wrap_with_circuit_breaker(configured.channel, circuit_breaker))
+        .collect::<registry::ChannelRegistry>()
+        .with_downgrade_policy(config.channels_config.capability_downgrade)
+}
+
 async fn append_nostr_channel_if_available(
     config: &Config,
     channels: &mut Vec<ConfiguredChannel>,
@@ -5694,8 +5985,18 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
 }
 
 /// Start all configured channels and route messages to the agent
-#[allow(clippy::too_many_lines)]
 pub async fn start_channels(config: Config) -> Result<()> {
+    let (_shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    start_channels_with_shutdown(config, shutdown_rx).await
+}
+
+/// Like [`start_channels`], but stops listeners once `shutdown` is set to
+/// `true`, drains in-flight messages and returns.
+#[allow(clippy::too_many_lines)]
+pub async fn start_channels_with_shutdown(
+    config: Config,
+    shutdown: tokio::sync::watch::Receiver<bool>,
+) -> Result<()> {
     // Ensure stale channel handles are never reused across restarts.
     clear_live_channels();
 
@@ -5990,10 +6291,8 @@ pub async fn start_channels(config: Config) -> Result<()> {
         println!();
     }
 
-    let channels: Vec<Arc<dyn Channel>> = configured_channels
-        .into_iter()
-        .map(|configured| configured.channel)
-        .collect();
+    let registry: Arc<registry::ChannelRegistry> =
+        Arc::new(into_registry(configured_channels, &config));
 
     println!("🦀 ZeroClaw Channel Server");
     println!("  🤖 Model:    {model}");
@@ -6008,7 +6307,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     );
     println!(
         "  📡 Channels: {}",
-        channels
+        registry
             .iter()
             .map(|c| c.name())
             .collect::<Vec<_>>()
@@ -6030,28 +6329,62 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .max(DEFAULT_CHANNEL_MAX_BACKOFF_SECS);
 
     // Single message bus — all channels send messages here
-    let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(100);
+    let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelEvent>(100);
 
     // Spawn a listener for each channel
     let mut handles = Vec::new();
-    for ch in &channels {
+    for ch in registry.iter() {
         handles.push(spawn_supervised_listener(
             ch.clone(),
             tx.clone(),
+            shutdown.clone(),
             initial_backoff_secs,
             max_backoff_secs,
         ));
     }
     drop(tx); // Drop our copy so rx closes when all channels stop
 
+    let mut outbound_worker = None;
+    let outbound_queue = match &config.channels_config.outbound_queue {
+        Some(queue_config) => {
+            let dir = queue_config.resolved_dir(&config.workspace_dir);
+            let store = outbound_queue::JsonFileQueue::open(&dir)
+                .await
+                .with_context(|| format!("open outbound queue {}", dir.display()))?;
+            let queue = Arc::new(
+                outbound_queue::OutboundQueue::new(Arc::new(store), Arc::clone(&registry))
+                    .with_backoff(
+                        Duration::from_secs(initial_backoff_secs),
+                        Duration::from_secs(max_backoff_secs),
+                    )
+                    .with_max_attempts(queue_config.max_attempts),
+            );
+            println!("  📮 Outbound queue: {}", dir.display());
+            outbound_worker = Some(tokio::spawn({
+                let queue = Arc::clone(&queue);
+                let shutdown = shutdown.clone();
+                async move { queue.run(shutdown).await }
+            }));
+            Some(queue)
+        }
+        None => None,
+    };
+    let config_watcher = spawn_config_watcher(
+        config.config_path.clone(),
+        Arc::clone(&registry),
+        shutdown.clone(),
+    );
+
+    // Reversed so the first channel with a name wins, as in `ChannelRegistry::get`.
     let channels_by_name = Arc::new(
-        channels
+        registry
             .iter()
+            .rev()
             .map(|ch| (ch.name().to_string(), Arc::clone(ch)))
             .collect::<HashMap<_, _>>(),
     );
     register_live_channels(channels_by_name.as_ref());
-    let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
+    let max_in_flight_messages = compute_max_in_flight_messages(registry.len());
 
     println!("  🚦 In-flight message limit: {max_in_flight_messages}");
 
@@ -6111,6 +6444,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         // Preserve startup perplexity filter config to ensure policy is not weakened
         // when runtime store lookup misses.
         startup_perplexity_filter: config.security.perplexity_filter.clone(),
+        outbound_queue,
         approval_manager: {
             // Keep approval policy provenance-bound to static config. Do not
             // auto-approve tool names from untrusted manifest files.
@@ -6127,11 +6461,16 @@ pub async fn start_channels(config: Config) -> Result<()> {
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
+    config_watcher.abort();
 
     // Wait for all channel tasks
     for h in handles {
         let _ = h.await;
     }
+    // Undelivered replies stay on disk for the next start.
+    if let Some(worker) = outbound_worker {
+        worker.abort();
+    }
 
     clear_live_channels();
 
@@ -6142,6 +6481,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
 #[allow(clippy::large_futures)]
 mod tests {
     use super::*;
+    use crate::channels::traits::{ChannelResult, SendReceipt};
     use crate::memory::{Memory, MemoryCategory, SqliteMemory};
     use crate::observability::NoopObserver;
     use crate::providers::{ChatMessage, Provider};
@@ -6469,6 +6809,7 @@ mod tests {
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -6526,6 +6867,7 @@ mod tests {
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -6586,6 +6928,7 @@ mod tests {
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -6622,8 +6965,9 @@ mod tests {
         sent_messages: tokio::sync::Mutex<Vec<String>>,
         start_typing_calls: AtomicUsize,
         stop_typing_calls: AtomicUsize,
-        reactions_added: tokio::sync::Mutex<Vec<(String, String, String)>>,
-        reactions_removed: tokio::sync::Mutex<Vec<(String, String, String)>>,
+        reactions_added: tokio::sync::Mutex<Vec<(String, String)>>,
+        reactions_removed: tokio::sync::Mutex<Vec<(String, String)>>,
+        locale: locale::Locale,
     }
 
     #[derive(Default)]
@@ -6649,26 +6993,27 @@ mod tests {
             "telegram"
         }
 
-        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
             self.sent_messages
                 .lock()
                 .await
                 .push(format!("{}:{}", message.recipient, message.content));
-            Ok(())
+            Ok(SendReceipt::default())
         }
 
         async fn listen(
             &self,
-            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
-        ) -> anyhow::Result<()> {
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelEvent>,
+            _shutdown: tokio::sync::watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
             Ok(())
         }
 
-        async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+        async fn start_typing(&self, _recipient: &str) -> ChannelResult<()> {
             Ok(())
         }
 
-        async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+        async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
             Ok(())
         }
     }
@@ -6679,18 +7024,19 @@ mod tests {
             "draft-streaming-channel"
         }
 
-        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
             self.sent_messages
                 .lock()
                 .await
                 .push(format!("{}:{}", message.recipient, message.content));
-            Ok(())
+            Ok(SendReceipt::default())
         }
 
         async fn listen(
             &self,
-            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
-        ) -> anyhow::Result<()> {
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelEvent>,
+            _shutdown: tokio::sync::watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
             Ok(())
         }
 
@@ -6698,7 +7044,7 @@ mod tests {
             true
         }
 
-        async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        async fn send_draft(&self, message: &SendMessage) -> ChannelResult<Option<String>> {
             self.sent_messages
                 .lock()
                 .await
@@ -6711,7 +7057,7 @@ mod tests {
             _recipient: &str,
             _message_id: &str,
             text: &str,
-        ) -> anyhow::Result<Option<String>> {
+        ) -> ChannelResult<Option<String>> {
             self.draft_updates.lock().await.push(text.to_string());
             Ok(None)
         }
@@ -6721,7 +7067,7 @@ mod tests {
             _recipient: &str,
             _message_id: &str,
             text: &str,
-        ) -> anyhow::Result<()> {
+        ) -> ChannelResult<()> {
             self.finalized_drafts.lock().await.push(text.to_string());
             Ok(())
         }
@@ -6733,56 +7079,47 @@ mod tests {
             "test-channel"
         }
 
-        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        fn locale(&self) -> locale::Locale {
+            self.locale
+        }
+
+        async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
             self.sent_messages
                 .lock()
                 .await
                 .push(format!("{}:{}", message.recipient, message.content));
-            Ok(())
+            Ok(SendReceipt::default())
         }
 
         async fn listen(
             &self,
-            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
-        ) -> anyhow::Result<()> {
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelEvent>,
+            _shutdown: tokio::sync::watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
             Ok(())
         }
 
-        async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+        async fn start_typing(&self, _recipient: &str) -> ChannelResult<()> {
             self.start_typing_calls.fetch_add(1, Ordering::SeqCst);
             Ok(())
         }
 
-        async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+        async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
             self.stop_typing_calls.fetch_add(1, Ordering::SeqCst);
             Ok(())
         }
 
-        async fn add_reaction(
-            &self,
-            channel_id: &str,
-            message_id: &str,
-            emoji: &str,
-        ) -> anyhow::Result<()> {
-            self.reactions_added.lock().await.push((
-                channel_id.to_string(),
-                message_id.to_string(),
-                emoji.to_string(),
-            ));
-            Ok(())
+        async fn add_reaction(&self, message_id: &str, emoji: &str) -> ChannelResult<String> {
+            let mut added = self.reactions_added.lock().await;
+            added.push((message_id.to_string(), emoji.to_string()));
+            Ok(format!("reaction-{}", added.len()))
         }
 
-        async fn remove_reaction(
-            &self,
-            channel_id: &str,
-            message_id: &str,
-            emoji: &str,
-        ) -> anyhow::Result<()> {
-            self.reactions_removed.lock().await.push((
-                channel_id.to_string(),
-                message_id.to_string(),
-                emoji.to_string(),
-            ));
+        async fn remove_reaction(&self, message_id: &str, reaction_id: &str) -> ChannelResult<()> {
+            self.reactions_removed
+                .lock()
+                .await
+                .push((message_id.to_string(), reaction_id.to_string()));
             Ok(())
         }
     }
@@ -6793,26 +7130,27 @@ mod tests {
             "qq"
         }
 
-        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
             self.sent_messages
                 .lock()
                 .await
                 .push(format!("{}:{}", message.recipient, message.content));
-            Ok(())
+            Ok(SendReceipt::default())
         }
 
         async fn listen(
             &self,
-            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
-        ) -> anyhow::Result<()> {
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelEvent>,
+            _shutdown: tokio::sync::watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
             Ok(())
         }
 
-        async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+        async fn start_typing(&self, _recipient: &str) -> ChannelResult<()> {
             Ok(())
         }
 
-        async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+        async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
             Ok(())
         }
     }
@@ -7269,6 +7607,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -7281,6 +7620,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -7307,6 +7654,103 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(sent[0].contains("response-1"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_persists_final_reply_in_outbound_queue() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+        let registry: Arc<registry::ChannelRegistry> =
+            Arc::new(std::iter::once(Arc::clone(&channel)).collect());
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let queue_dir = TempDir::new().unwrap();
+        let store = Arc::new(
+            outbound_queue::JsonFileQueue::open(queue_dir.path())
+                .await
+                .unwrap(),
+        );
+        let queue = Arc::new(outbound_queue::OutboundQueue::new(store.clone(), registry));
+
+        let provider: Arc<dyn Provider> = Arc::new(HistoryCaptureProvider::default());
+        let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+        provider_cache_seed.insert("test-provider".to_string(), Arc::clone(&provider));
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_locks: Default::default(),
+            session_config: crate::config::AgentSessionConfig::default(),
+            session_manager: None,
+            provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            safety_heartbeat: None,
+            startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: Some(Arc::clone(&queue)),
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-queued-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-queued".to_string(),
+                content: "hello".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        use outbound_queue::Queue as _;
+        assert!(channel_impl.sent_messages.lock().await.is_empty());
+        let pending = store.pending().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].channel, "test-channel");
+        assert_eq!(pending[0].message.recipient, "chat-queued");
+
+        queue.drain_due().await.unwrap();
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains("response-1"));
+        assert!(store.pending().await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn process_channel_message_executes_tool_calls_instead_of_sending_raw_json() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -7356,6 +7800,7 @@ BTC is currently around $65,000 based on latest tool output."#
             hooks: None,
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -7368,6 +7813,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -7430,6 +7883,7 @@ BTC is currently around $65,000 based on latest tool output."#
             hooks: None,
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -7442,6 +7896,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -7518,6 +7980,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model_routes: Vec::new(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -7530,6 +7993,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "draft-streaming-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -7605,6 +8076,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model_routes: Vec::new(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -7617,6 +8089,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "draft-streaming-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -7677,6 +8157,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -7689,6 +8170,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -7751,6 +8240,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -7763,6 +8253,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -7827,6 +8325,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -7839,6 +8338,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -7934,6 +8441,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
         assert_eq!(
             runtime_ctx
@@ -7958,6 +8466,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8072,6 +8588,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::clone(&approval_manager),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -8084,6 +8601,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8161,6 +8686,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::clone(&approval_manager),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -8173,6 +8699,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8239,6 +8773,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         let runtime_ctx_for_first_turn = runtime_ctx.clone();
@@ -8253,6 +8788,14 @@ BTC is currently around $65,000 based on latest tool output."#
                     channel: "telegram".to_string(),
                     timestamp: 1,
                     thread_ts: None,
+                    reply_to_message_id: None,
+                    is_from_self: false,
+                    mentions: Vec::new(),
+                    attachments: Vec::new(),
+                    chat_type: traits::ChatType::Group,
+                    triggered_by_mention: false,
+                    created_at: None,
+                    received_at: chrono::Utc::now(),
                 },
                 CancellationToken::new(),
             )
@@ -8285,6 +8828,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8403,6 +8954,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
         assert_eq!(
             runtime_ctx
@@ -8421,6 +8973,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8518,6 +9078,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager,
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -8530,6 +9091,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8628,6 +9197,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager,
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -8640,6 +9210,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8720,6 +9298,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::clone(&approval_manager),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -8732,6 +9311,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8822,6 +9409,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::clone(&approval_manager),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -8834,6 +9422,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8925,6 +9521,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -8937,6 +9534,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -8971,6 +9576,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9076,6 +9689,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
         maybe_apply_runtime_config_update(runtime_ctx.as_ref())
             .await
@@ -9093,6 +9707,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9173,6 +9795,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -9185,6 +9808,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9219,6 +9850,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9323,6 +9962,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -9335,6 +9975,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9443,6 +10091,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -9455,6 +10104,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9543,6 +10200,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -9555,6 +10213,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9584,6 +10250,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9662,6 +10336,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -9674,6 +10349,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9702,6 +10385,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9782,6 +10473,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -9794,6 +10486,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9861,6 +10561,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -9873,6 +10574,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -9971,6 +10680,7 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -9983,6 +10693,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 4,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -10169,6 +10887,7 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         maybe_apply_runtime_config_update(runtime_ctx.as_ref())
@@ -10374,6 +11093,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -10386,6 +11106,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -10442,6 +11170,7 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: mock_price_approved_manager(),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -10454,6 +11183,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -10466,6 +11203,86 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(sent_messages[0].contains("Context and progress were preserved"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_sends_system_replies_in_channel_locale() {
+        let channel_impl = Arc::new(RecordingChannel {
+            locale: locale::Locale::ZhCn,
+            ..RecordingChannel::default()
+        });
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(IterativeToolProvider {
+                required_tool_iterations: 20,
+            }),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 3,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            conversation_locks: Default::default(),
+            session_config: crate::config::AgentSessionConfig::default(),
+            session_manager: None,
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            approval_manager: mock_price_approved_manager(),
+            safety_heartbeat: None,
+            startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-iter-fail".to_string(),
+                sender: "bob".to_string(),
+                reply_target: "chat-iter-fail".to_string(),
+                content: "Loop forever".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 2,
+                thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sent_messages = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent_messages.len(), 1);
+        assert!(sent_messages[0].starts_with("chat-iter-fail:"));
+        assert!(sent_messages[0].contains("工具调用次数上限（3）"));
+        assert!(!sent_messages[0].contains("Reached tool-iteration limit"));
+    }
+
     struct NoopMemory;
 
     #[async_trait::async_trait]
@@ -10624,29 +11441,52 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
-        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
-        tx.send(traits::ChannelMessage {
-            id: "1".to_string(),
-            sender: "alice".to_string(),
-            reply_target: "alice".to_string(),
-            content: "hello".to_string(),
-            channel: "test-channel".to_string(),
-            timestamp: 1,
-            thread_ts: None,
-        })
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelEvent>(4);
+        tx.send(
+            traits::ChannelMessage {
+                id: "1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "alice".to_string(),
+                content: "hello".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
+            }
+            .into(),
+        )
         .await
         .unwrap();
-        tx.send(traits::ChannelMessage {
-            id: "2".to_string(),
-            sender: "bob".to_string(),
-            reply_target: "bob".to_string(),
-            content: "world".to_string(),
-            channel: "test-channel".to_string(),
-            timestamp: 2,
-            thread_ts: None,
-        })
+        tx.send(
+            traits::ChannelMessage {
+                id: "2".to_string(),
+                sender: "bob".to_string(),
+                reply_target: "bob".to_string(),
+                content: "world".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 2,
+                thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
+            }
+            .into(),
+        )
         .await
         .unwrap();
         drop(tx);
@@ -10714,31 +11554,54 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
-        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelEvent>(8);
         let send_task = tokio::spawn(async move {
-            tx.send(traits::ChannelMessage {
-                id: "msg-1".to_string(),
-                sender: "alice".to_string(),
-                reply_target: "chat-1".to_string(),
-                content: "forwarded content".to_string(),
-                channel: "telegram".to_string(),
-                timestamp: 1,
-                thread_ts: None,
-            })
+            tx.send(
+                traits::ChannelMessage {
+                    id: "msg-1".to_string(),
+                    sender: "alice".to_string(),
+                    reply_target: "chat-1".to_string(),
+                    content: "forwarded content".to_string(),
+                    channel: "telegram".to_string(),
+                    timestamp: 1,
+                    thread_ts: None,
+                    reply_to_message_id: None,
+                    is_from_self: false,
+                    mentions: Vec::new(),
+                    attachments: Vec::new(),
+                    chat_type: traits::ChatType::Group,
+                    triggered_by_mention: false,
+                    created_at: None,
+                    received_at: chrono::Utc::now(),
+                }
+                .into(),
+            )
             .await
             .unwrap();
             tokio::time::sleep(Duration::from_millis(40)).await;
-            tx.send(traits::ChannelMessage {
-                id: "msg-2".to_string(),
-                sender: "alice".to_string(),
-                reply_target: "chat-1".to_string(),
-                content: "summarize this".to_string(),
-                channel: "telegram".to_string(),
-                timestamp: 2,
-                thread_ts: None,
-            })
+            tx.send(
+                traits::ChannelMessage {
+                    id: "msg-2".to_string(),
+                    sender: "alice".to_string(),
+                    reply_target: "chat-1".to_string(),
+                    content: "summarize this".to_string(),
+                    channel: "telegram".to_string(),
+                    timestamp: 2,
+                    thread_ts: None,
+                    reply_to_message_id: None,
+                    is_from_self: false,
+                    mentions: Vec::new(),
+                    attachments: Vec::new(),
+                    chat_type: traits::ChatType::Group,
+                    triggered_by_mention: false,
+                    created_at: None,
+                    received_at: chrono::Utc::now(),
+                }
+                .into(),
+            )
             .await
             .unwrap();
         });
@@ -10816,31 +11679,54 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
-        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelEvent>(8);
         let send_task = tokio::spawn(async move {
-            tx.send(traits::ChannelMessage {
-                id: "msg-a".to_string(),
-                sender: "alice".to_string(),
-                reply_target: "chat-1".to_string(),
-                content: "first chat".to_string(),
-                channel: "telegram".to_string(),
-                timestamp: 1,
-                thread_ts: None,
-            })
+            tx.send(
+                traits::ChannelMessage {
+                    id: "msg-a".to_string(),
+                    sender: "alice".to_string(),
+                    reply_target: "chat-1".to_string(),
+                    content: "first chat".to_string(),
+                    channel: "telegram".to_string(),
+                    timestamp: 1,
+                    thread_ts: None,
+                    reply_to_message_id: None,
+                    is_from_self: false,
+                    mentions: Vec::new(),
+                    attachments: Vec::new(),
+                    chat_type: traits::ChatType::Group,
+                    triggered_by_mention: false,
+                    created_at: None,
+                    received_at: chrono::Utc::now(),
+                }
+                .into(),
+            )
             .await
             .unwrap();
             tokio::time::sleep(Duration::from_millis(30)).await;
-            tx.send(traits::ChannelMessage {
-                id: "msg-b".to_string(),
-                sender: "alice".to_string(),
-                reply_target: "chat-2".to_string(),
-                content: "second chat".to_string(),
-                channel: "telegram".to_string(),
-                timestamp: 2,
-                thread_ts: None,
-            })
+            tx.send(
+                traits::ChannelMessage {
+                    id: "msg-b".to_string(),
+                    sender: "alice".to_string(),
+                    reply_target: "chat-2".to_string(),
+                    content: "second chat".to_string(),
+                    channel: "telegram".to_string(),
+                    timestamp: 2,
+                    thread_ts: None,
+                    reply_to_message_id: None,
+                    is_from_self: false,
+                    mentions: Vec::new(),
+                    attachments: Vec::new(),
+                    chat_type: traits::ChatType::Group,
+                    triggered_by_mention: false,
+                    created_at: None,
+                    received_at: chrono::Utc::now(),
+                }
+                .into(),
+            )
             .await
             .unwrap();
         });
@@ -10900,6 +11786,7 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -10912,6 +11799,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -10969,6 +11864,7 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -10981,6 +11877,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -10992,16 +11896,20 @@ BTC is currently around $65,000 based on latest tool output."#
             "expected at least 2 reactions added (\u{1F440} then \u{2705}), got {}",
             added.len()
         );
-        assert_eq!(added[0].2, "\u{1F440}", "first reaction should be eyes");
+        assert_eq!(added[0].1, "\u{1F440}", "first reaction should be eyes");
         assert_eq!(
-            added.last().unwrap().2,
+            added.last().unwrap().1,
             "\u{2705}",
             "last reaction should be checkmark"
         );
 
         let removed = channel_impl.reactions_removed.lock().await;
         assert_eq!(removed.len(), 1, "eyes reaction should be removed once");
-        assert_eq!(removed[0].2, "\u{1F440}");
+        assert_eq!(
+            removed[0],
+            ("react-msg".to_string(), "reaction-1".to_string()),
+            "eyes reaction should be removed by the ID it was added with"
+        );
     }
 
     #[test]
@@ -11360,6 +12268,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         assert_eq!(conversation_memory_key(&msg), "slack_U123_msg_abc123");
@@ -11375,6 +12291,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -11384,6 +12308,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         assert_ne!(
@@ -11402,6 +12334,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         let user_key = conversation_memory_key(&msg);
@@ -11422,6 +12362,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "qq".into(),
             timestamp: 1,
             thread_ts: Some("msg-a".into()),
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -11431,6 +12379,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "qq".into(),
             timestamp: 2,
             thread_ts: Some("msg-b".into()),
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         assert_eq!(conversation_history_key(&msg1), "qq_user_open_1");
@@ -11450,6 +12406,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "napcat".into(),
             timestamp: 1,
             thread_ts: Some("msg-a".into()),
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -11459,6 +12423,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "napcat".into(),
             timestamp: 2,
             thread_ts: Some("msg-b".into()),
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         assert_eq!(conversation_history_key(&msg1), "napcat_user_1001");
@@ -11478,6 +12450,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "telegram".into(),
             timestamp: 1,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         let enriched = llm_user_content_with_sender_identity(&msg, &msg.content);
@@ -11494,6 +12474,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "telegram".into(),
             timestamp: 1,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         let enriched = llm_user_content_with_sender_identity(&msg, &msg.content);
@@ -11510,6 +12498,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "telegram".into(),
             timestamp: 1,
             thread_ts: Some("789".into()),
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         let enriched = llm_user_content_with_sender_identity(&msg, &msg.content);
@@ -11529,6 +12525,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -11538,6 +12542,14 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         mem.store(
@@ -11648,6 +12660,7 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -11660,6 +12673,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -11675,6 +12696,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -11744,6 +12773,7 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -11756,6 +12786,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "qq".to_string(),
                 timestamp: 1,
                 thread_ts: Some("msg-1".to_string()),
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -11771,6 +12809,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "qq".to_string(),
                 timestamp: 2,
                 thread_ts: Some("msg-2".to_string()),
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -11839,6 +12885,7 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -11851,6 +12898,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -11938,6 +12993,7 @@ BTC is currently around $65,000 based on latest tool output."#
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -11950,6 +13006,14 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -12515,6 +13579,87 @@ BTC is currently around $65,000 based on latest tool output."#;
             .any(|entry| entry.channel.name() == "mattermost"));
     }
 
+    #[test]
+    fn wrap_with_circuit_breaker_only_when_configured() {
+        let channel: Arc<dyn Channel> = Arc::new(crate::channels::mock::MockChannel::new("feishu"));
+
+        let unwrapped = wrap_with_circuit_breaker(Arc::clone(&channel), None);
+        assert!(Arc::ptr_eq(&unwrapped, &channel));
+
+        let config = crate::config::schema::ChannelCircuitBreakerConfig::default();
+        let wrapped = wrap_with_circuit_breaker(Arc::clone(&channel), Some(&config));
+        assert!(!Arc::ptr_eq(&wrapped, &channel));
+        assert_eq!(wrapped.name(), "feishu");
+    }
+
+    #[cfg(feature = "channel-lark")]
+    #[test]
+    fn collect_configured_channels_includes_named_feishu_apps() {
+        let mut config = Config::default();
+        for name in ["prod", "staging"] {
+            config.channels_config.feishu_apps.insert(
+                name.to_string(),
+                serde_json::from_value(serde_json::json!({
+                    "app_id": format!("cli_{name}"),
+                    "app_secret": "secret",
+                }))
+                .unwrap(),
+            );
+        }
+
+        let channels = collect_configured_channels(&config, "test");
+        let names: Vec<&str> = channels.iter().map(|entry| entry.channel.name()).collect();
+
+        assert!(names.contains(&"feishu:prod"));
+        assert!(names.contains(&"feishu:staging"));
+        assert!(!names.contains(&"feishu"));
+        assert!(channel_delivery_instructions("feishu:prod").is_some());
+    }
+
+    #[cfg(feature = "channel-lark")]
+    #[test]
+    fn build_all_registers_feishu_and_lark_sections() {
+        let mut config = Config::default();
+        config.channels_config.feishu = Some(
+            serde_json::from_value(serde_json::json!({
+                "app_id": "cli_feishu",
+                "app_secret": "secret",
+            }))
+            .unwrap(),
+        );
+        config.channels_config.lark = Some(
+            serde_json::from_value(serde_json::json!({
+                "app_id": "cli_lark",
+                "app_secret": "secret",
+            }))
+            .unwrap(),
+        );
+
+        let registry = build_all(&config).unwrap();
+
+        assert!(registry.get("feishu").is_some());
+        assert!(registry.get("lark").is_some());
+        assert!(unsupported_channel_sections(&config).is_empty());
+    }
+
+    #[cfg(not(feature = "channel-matrix"))]
+    #[test]
+    fn build_all_names_section_compiled_out_of_the_build() {
+        let mut config = Config::default();
+        config.channels_config.matrix = Some(
+            serde_json::from_value(serde_json::json!({
+                "homeserver": "https://matrix.example.com",
+                "access_token": "token",
+                "room_id": "!room:example.com",
+                "allowed_users": [],
+            }))
+            .unwrap(),
+        );
+
+        let err = build_all(&config).err().expect("matrix is compiled out");
+        assert!(err.to_string().contains("[channels_config.matrix]"));
+    }
+
     #[test]
     fn collect_configured_channels_includes_dingtalk_when_configured() {
         let mut config = Config::default();
@@ -12550,16 +13695,17 @@ BTC is currently around $65,000 based on latest tool output."#;
             self.name
         }
 
-        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
-            Ok(())
+        async fn send(&self, _message: &SendMessage) -> ChannelResult<SendReceipt> {
+            Ok(SendReceipt::default())
         }
 
         async fn listen(
             &self,
-            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
-        ) -> anyhow::Result<()> {
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelEvent>,
+            _shutdown: tokio::sync::watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
             self.calls.fetch_add(1, Ordering::SeqCst);
-            anyhow::bail!("listen boom")
+            Err(anyhow::anyhow!("listen boom").into())
         }
     }
 
@@ -12569,14 +13715,15 @@ BTC is currently around $65,000 based on latest tool output."#;
             &self.name
         }
 
-        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
-            Ok(())
+        async fn send(&self, _message: &SendMessage) -> ChannelResult<SendReceipt> {
+            Ok(SendReceipt::default())
         }
 
         async fn listen(
             &self,
-            tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
-        ) -> anyhow::Result<()> {
+            tx: tokio::sync::mpsc::Sender<traits::ChannelEvent>,
+            _shutdown: tokio::sync::watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
             self.calls.fetch_add(1, Ordering::SeqCst);
             tx.closed().await;
             Ok(())
@@ -12591,8 +13738,9 @@ BTC is currently around $65,000 based on latest tool output."#;
             calls: Arc::clone(&calls),
         });
 
-        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
-        let handle = spawn_supervised_listener(channel, tx, 1, 1);
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelEvent>(1);
+        let (_shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let handle = spawn_supervised_listener(channel, tx, shutdown_rx, 1, 1);
 
         tokio::time::sleep(Duration::from_millis(80)).await;
         drop(rx);
@@ -12610,6 +13758,84 @@ BTC is currently around $65,000 based on latest tool output."#;
         assert!(calls.load(Ordering::SeqCst) >= 1);
     }
 
+    struct ReloadRecordingChannel {
+        reloaded_ports: Arc<Mutex<Vec<u16>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Channel for ReloadRecordingChannel {
+        fn name(&self) -> &str {
+            "reload-recording"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> ChannelResult<SendReceipt> {
+            Ok(SendReceipt::default())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelEvent>,
+            _shutdown: tokio::sync::watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
+            Ok(())
+        }
+
+        async fn reload_config(&self, config: &Config) -> traits::ConfigReload {
+            self.reloaded_ports
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(config.gateway.port);
+            traits::ConfigReload::Applied
+        }
+    }
+
+    #[tokio::test]
+    async fn config_reload_skips_channels_when_new_config_is_invalid() {
+        let tmp = TempDir::new().unwrap();
+        let config_path = tmp.path().join("config.toml");
+        let reloaded_ports = Arc::new(Mutex::new(Vec::new()));
+        let channels: registry::ChannelRegistry =
+            std::iter::once(Arc::new(ReloadRecordingChannel {
+                reloaded_ports: Arc::clone(&reloaded_ports),
+            }) as Arc<dyn Channel>)
+            .collect();
+
+        let mut config = Config::default();
+        config.gateway.port = 4321;
+        std::fs::write(&config_path, toml::to_string(&config).unwrap()).unwrap();
+        reload_channel_configs(&config_path, &channels).await;
+
+        config.gateway.host = String::new();
+        std::fs::write(&config_path, toml::to_string(&config).unwrap()).unwrap();
+        reload_channel_configs(&config_path, &channels).await;
+
+        assert_eq!(
+            *reloaded_ports.lock().unwrap_or_else(|e| e.into_inner()),
+            vec![4321]
+        );
+    }
+
+    #[test]
+    fn channel_lifecycle_events_update_health() {
+        let channel_name = format!("test-lifecycle-{}", uuid::Uuid::new_v4());
+        let component_name = format!("channel:{channel_name}");
+
+        record_channel_lifecycle(&traits::ChannelEvent::Disconnected {
+            channel: channel_name.clone(),
+            reason: "socket reset".into(),
+        });
+        let snapshot = crate::health::snapshot_json();
+        let component = &snapshot["components"][&component_name];
+        assert_eq!(component["status"], "error");
+        assert_eq!(component["last_error"], "socket reset");
+
+        record_channel_lifecycle(&traits::ChannelEvent::Connected {
+            channel: channel_name,
+        });
+        let snapshot = crate::health::snapshot_json();
+        assert_eq!(snapshot["components"][&component_name]["status"], "ok");
+    }
+
     #[tokio::test]
     async fn supervised_listener_refreshes_health_while_running() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -12620,10 +13846,12 @@ BTC is currently around $65,000 based on latest tool output."#;
             calls: Arc::clone(&calls),
         });
 
-        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelEvent>(1);
+        let (_shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
         let handle = spawn_supervised_listener_with_health_interval(
             channel,
             tx,
+            shutdown_rx,
             1,
             1,
             Duration::from_millis(20),
@@ -12655,6 +13883,32 @@ BTC is currently around $65,000 based on latest tool output."#;
         assert!(calls.load(Ordering::SeqCst) >= 1);
     }
 
+    #[tokio::test]
+    async fn supervised_listener_stops_on_shutdown_signal() {
+        let channel = Arc::new(mock::MockChannel::new("test-supervised-shutdown"));
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<traits::ChannelEvent>(4);
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let handle = spawn_supervised_listener(
+            Arc::clone(&channel) as Arc<dyn Channel>,
+            tx,
+            shutdown_rx,
+            1,
+            1,
+        );
+
+        channel.inject(channel.message("alice", "before shutdown"));
+        let received = rx.recv().await.and_then(traits::ChannelEvent::into_message);
+        assert_eq!(received.unwrap().content, "before shutdown");
+
+        shutdown_tx.send(true).unwrap();
+        let join = tokio::time::timeout(Duration::from_secs(1), handle).await;
+        assert!(
+            join.is_ok(),
+            "supervisor should exit once shutdown is signalled"
+        );
+        assert!(rx.recv().await.is_none(), "listener should drop its sender");
+    }
+
     #[test]
     fn maybe_restart_daemon_systemd_args_regression() {
         assert_eq!(
@@ -12766,6 +14020,7 @@ BTC is currently around $65,000 based on latest tool output."#;
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -12779,6 +14034,14 @@ BTC is currently around $65,000 based on latest tool output."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -12842,6 +14105,7 @@ BTC is currently around $65,000 based on latest tool output."#;
             )),
             safety_heartbeat: None,
             startup_perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+            outbound_queue: None,
         });
 
         process_channel_message(
@@ -12854,6 +14118,14 @@ BTC is currently around $65,000 based on latest tool output."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
@@ -12869,6 +14141,14 @@ BTC is currently around $65,000 based on latest tool output."#;
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: traits::ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
             },
             CancellationToken::new(),
         )
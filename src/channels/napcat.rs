@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use crate::config::schema::NapcatConfig;
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
@@ -334,10 +336,18 @@ impl NapcatChannel {
             timestamp: extract_timestamp(event),
             // This is a message id for passive reply, not a thread id.
             thread_ts: Some(message_id),
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         })
     }
 
-    async fn listen_once(&self, tx: &tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn listen_once(&self, tx: &tokio::sync::mpsc::Sender<ChannelEvent>) -> Result<()> {
         let request = self.build_ws_request()?;
         let (mut socket, _) = connect_async(request).await?;
         tracing::info!("Napcat: connected to {}", self.websocket_url);
@@ -353,7 +363,7 @@ impl NapcatChannel {
                         }
                     };
                     if let Some(msg) = self.parse_message_event(&event).await {
-                        if tx.send(msg).await.is_err() {
+                        if tx.send(msg.into()).await.is_err() {
                             return Ok(());
                         }
                     }
@@ -383,10 +393,10 @@ impl Channel for NapcatChannel {
         "napcat"
     }
 
-    async fn send(&self, message: &SendMessage) -> Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let payload = compose_onebot_content(&message.content, message.thread_ts.as_deref());
         if payload.trim().is_empty() {
-            return Ok(());
+            return Ok(SendReceipt::default());
         }
 
         if let Some(group_id) = message.recipient.strip_prefix("group:") {
@@ -395,7 +405,7 @@ impl Channel for NapcatChannel {
                 "message": payload,
             });
             self.post_onebot(NAPCAT_SEND_GROUP, &body).await?;
-            return Ok(());
+            return Ok(SendReceipt::default());
         }
 
         let user_id = message
@@ -404,17 +414,22 @@ impl Channel for NapcatChannel {
             .unwrap_or(&message.recipient)
             .trim();
         if user_id.is_empty() {
-            anyhow::bail!("Napcat recipient is empty");
+            return Err(anyhow::anyhow!("Napcat recipient is empty").into());
         }
 
         let body = json!({
             "user_id": user_id,
             "message": payload,
         });
-        self.post_onebot(NAPCAT_SEND_PRIVATE, &body).await
+        self.post_onebot(NAPCAT_SEND_PRIVATE, &body).await?;
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         let mut backoff = Duration::from_secs(1);
         loop {
             match self.listen_once(&tx).await {
@@ -1,4 +1,6 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use nostr_sdk::prelude::*;
@@ -94,7 +96,7 @@ impl Channel for NostrChannel {
         "nostr"
     }
 
-    async fn send(&self, message: &SendMessage) -> Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let recipient =
             PublicKey::parse(&message.recipient).context("Invalid recipient Nostr public key")?;
 
@@ -136,10 +138,14 @@ impl Channel for NostrChannel {
             }
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         let listen_start = Timestamp::now();
 
         // Subscribe to both NIP-04 (kind 4) and NIP-17/gift-wrap (kind 1059).
@@ -253,8 +259,16 @@ impl Channel for NostrChannel {
                             channel: "nostr".to_string(),
                             timestamp,
                             thread_ts: None,
+                            reply_to_message_id: None,
+                            is_from_self: false,
+                            mentions: Vec::new(),
+                            attachments: Vec::new(),
+                            chat_type: ChatType::Group,
+                            triggered_by_mention: false,
+                            created_at: None,
+                            received_at: chrono::Utc::now(),
                         };
-                        if tx.send(msg).await.is_err() {
+                        if tx.send(msg.into()).await.is_err() {
                             tracing::info!("Nostr listener: message bus closed, stopping");
                             break;
                         }
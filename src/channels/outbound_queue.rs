@@ -0,0 +1,570 @@
+//! Persistent outbound queue for at-least-once delivery across restarts.
+//!
+//! [`OutboundQueue::enqueue`] records a [`SendMessage`] and its target channel
+//! in a [`Queue`] store before anything is sent. [`OutboundQueue::run`] drains
+//! the store through a [`ChannelRegistry`], removing items once the channel
+//! accepts them and rescheduling retryable failures with the same doubling
+//! backoff the channel supervisor uses. Because items are only removed after a
+//! successful send, a restart re-sends whatever was still pending — including a
+//! message that was delivered just before the process died, so recipients may
+//! occasionally see a duplicate.
+//!
+//! [`JsonFileQueue`] is the bundled store; implement [`Queue`] to keep items
+//! elsewhere (e.g. Redis). The channel runtime routes final replies through
+//! the queue when `[channels_config.outbound_queue]` is set.
+
+use super::registry::ChannelRegistry;
+use super::traits::{ChannelError, SendMessage};
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Longest an item is ever postponed, whatever the backoff or a platform
+/// `Retry-After` asks for.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A message waiting to be delivered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedMessage {
+    pub id: String,
+    /// Registry name of the target channel (see [`Channel::name`](super::Channel::name)).
+    pub channel: String,
+    pub message: SendMessage,
+    /// Failed delivery attempts so far.
+    pub attempts: u32,
+    pub enqueued_at: DateTime<Utc>,
+    /// Earliest time the next attempt may run.
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+/// Storage backend for [`OutboundQueue`].
+///
+/// Implementations must persist every change before returning so a crash
+/// never loses an acknowledged [`push`](Queue::push).
+#[async_trait]
+pub trait Queue: Send + Sync {
+    /// Store a new item.
+    async fn push(&self, item: QueuedMessage) -> anyhow::Result<()>;
+
+    /// All stored items, oldest first.
+    async fn pending(&self) -> anyhow::Result<Vec<QueuedMessage>>;
+
+    /// Replace the stored item with the same `id` (after a failed attempt).
+    async fn update(&self, item: &QueuedMessage) -> anyhow::Result<()>;
+
+    /// Drop an item after delivery or a permanent failure.
+    async fn remove(&self, id: &str) -> anyhow::Result<()>;
+}
+
+/// [`Queue`] keeping one JSON file per item in a directory.
+///
+/// Each change writes or deletes only that item's file; writes go through a
+/// temporary file and a rename, so a crash never leaves a half-written item.
+/// Items are cached in memory, so [`pending`](Queue::pending) does not touch
+/// the disk.
+pub struct JsonFileQueue {
+    dir: PathBuf,
+    items: tokio::sync::Mutex<Vec<QueuedMessage>>,
+}
+
+impl JsonFileQueue {
+    /// Open the queue in `dir`, creating it if needed and loading any items
+    /// left by a previous run. Unreadable item files are logged and skipped.
+    pub async fn open(dir: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let dir = dir.into();
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("create outbound queue {}", dir.display()))?;
+
+        let mut items = Vec::new();
+        let mut entries = tokio::fs::read_dir(&dir)
+            .await
+            .with_context(|| format!("read outbound queue {}", dir.display()))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let parsed = tokio::fs::read(&path)
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|bytes| Ok(serde_json::from_slice::<QueuedMessage>(&bytes)?));
+            match parsed {
+                Ok(item) => items.push(item),
+                Err(err) => tracing::warn!(
+                    "Outbound queue: skipping unreadable item {}: {err}",
+                    path.display()
+                ),
+            }
+        }
+        items.sort_by_key(|item: &QueuedMessage| item.enqueued_at);
+
+        Ok(Self {
+            dir,
+            items: tokio::sync::Mutex::new(items),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn item_path(&self, id: &str) -> anyhow::Result<PathBuf> {
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            anyhow::bail!("invalid outbound queue item id {id:?}");
+        }
+        Ok(self.dir.join(format!("{id}.json")))
+    }
+
+    /// Atomic save: write to .tmp then rename.
+    async fn save(&self, item: &QueuedMessage) -> anyhow::Result<()> {
+        let path = self.item_path(&item.id)?;
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec(item)?).await?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .with_context(|| format!("write outbound queue item {}", path.display()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Queue for JsonFileQueue {
+    async fn push(&self, item: QueuedMessage) -> anyhow::Result<()> {
+        let mut items = self.items.lock().await;
+        self.save(&item).await?;
+        items.push(item);
+        Ok(())
+    }
+
+    async fn pending(&self) -> anyhow::Result<Vec<QueuedMessage>> {
+        Ok(self.items.lock().await.clone())
+    }
+
+    async fn update(&self, item: &QueuedMessage) -> anyhow::Result<()> {
+        let mut items = self.items.lock().await;
+        if let Some(slot) = items.iter_mut().find(|existing| existing.id == item.id) {
+            self.save(item).await?;
+            *slot = item.clone();
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, id: &str) -> anyhow::Result<()> {
+        let mut items = self.items.lock().await;
+        let Some(index) = items.iter().position(|item| item.id == id) else {
+            return Ok(());
+        };
+        let path = self.item_path(id)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("remove outbound queue item {}", path.display()))
+            }
+        }
+        items.remove(index);
+        Ok(())
+    }
+}
+
+/// Delivers stored messages through a [`ChannelRegistry`].
+pub struct OutboundQueue {
+    store: Arc<dyn Queue>,
+    registry: Arc<ChannelRegistry>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    max_attempts: u32,
+    wake: tokio::sync::Notify,
+}
+
+impl OutboundQueue {
+    pub fn new(store: Arc<dyn Queue>, registry: Arc<ChannelRegistry>) -> Self {
+        Self {
+            store,
+            registry,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(60),
+            max_attempts: 10,
+            wake: tokio::sync::Notify::new(),
+        }
+    }
+
+    /// Retry delays start at `initial` and double up to `max`, matching the
+    /// channel supervisor's `channel_initial_backoff_secs` /
+    /// `channel_max_backoff_secs`.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Give up on an item after this many failed attempts (at least 1).
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Persist `message` for delivery on `channel` and return its queue ID.
+    ///
+    /// The message is stored before this returns; [`run`](Self::run) sends it.
+    /// Without its own `idempotency_key` it gets the queue ID, so a resend
+    /// after a crash between sending and removing the item is deduplicated.
+    pub async fn enqueue(
+        &self,
+        channel: impl Into<String>,
+        mut message: SendMessage,
+    ) -> anyhow::Result<String> {
+        let now = Utc::now();
+        let id = Uuid::new_v4().to_string();
+        message.idempotency_key.get_or_insert_with(|| id.clone());
+        let item = QueuedMessage {
+            id,
+            channel: channel.into(),
+            message,
+            attempts: 0,
+            enqueued_at: now,
+            next_attempt_at: now,
+        };
+        let id = item.id.clone();
+        self.store.push(item).await?;
+        self.wake.notify_one();
+        Ok(id)
+    }
+
+    /// Drain the store until `shutdown` flips to `true`.
+    ///
+    /// Items left by a previous run are picked up on the first pass.
+    pub async fn run(&self, mut shutdown: tokio::sync::watch::Receiver<bool>) {
+        loop {
+            let next_due = match self.drain_due().await {
+                Ok(next_due) => next_due,
+                Err(err) => {
+                    tracing::warn!("Outbound queue: store error: {err:#}");
+                    Some(after(self.initial_backoff))
+                }
+            };
+            let wait_for_due = async {
+                match next_due {
+                    Some(due) => {
+                        tokio::time::sleep((due - Utc::now()).to_std().unwrap_or_default()).await;
+                    }
+                    None => std::future::pending().await,
+                }
+            };
+            tokio::select! {
+                () = wait_for_due => {}
+                () = self.wake.notified() => {}
+                () = super::traits::wait_for_shutdown(&mut shutdown) => return,
+            }
+        }
+    }
+
+    /// Attempt every due item once; returns when the next pending item is due.
+    pub async fn drain_due(&self) -> anyhow::Result<Option<DateTime<Utc>>> {
+        let mut next_due: Option<DateTime<Utc>> = None;
+        for mut item in self.store.pending().await? {
+            if item.next_attempt_at > Utc::now() {
+                next_due = earliest(next_due, item.next_attempt_at);
+                continue;
+            }
+            if self.registry.get(&item.channel).is_none() {
+                tracing::warn!(
+                    "Outbound queue: dropping {} for unknown channel {}",
+                    item.id,
+                    item.channel
+                );
+                self.store.remove(&item.id).await?;
+                continue;
+            }
+            match self.registry.send(&item.channel, &item.message).await {
+                Ok(_) => self.store.remove(&item.id).await?,
+                Err(err) => {
+                    item.attempts = item.attempts.saturating_add(1);
+                    if !err.is_retryable() || item.attempts >= self.max_attempts {
+                        tracing::warn!(
+                            "Outbound queue: giving up on {} to {} after {} attempt(s): {err}",
+                            item.id,
+                            item.channel,
+                            item.attempts
+                        );
+                        self.store.remove(&item.id).await?;
+                        continue;
+                    }
+                    let delay = self.retry_delay(item.attempts, &err);
+                    item.next_attempt_at = after(delay);
+                    tracing::debug!(
+                        "Outbound queue: {} to {} failed ({err}), retrying in {delay:?}",
+                        item.id,
+                        item.channel
+                    );
+                    self.store.update(&item).await?;
+                    next_due = earliest(next_due, item.next_attempt_at);
+                }
+            }
+        }
+        Ok(next_due)
+    }
+
+    /// Doubling backoff after `attempts` failures, stretched to honour a
+    /// platform `Retry-After`.
+    fn retry_delay(&self, attempts: u32, err: &ChannelError) -> Duration {
+        let doublings = attempts.saturating_sub(1).min(16);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << doublings)
+            .min(self.max_backoff);
+        match err {
+            ChannelError::RateLimited {
+                retry_after: Some(retry_after),
+            }
+            | ChannelError::CircuitOpen { retry_after, .. } => backoff.max(*retry_after),
+            _ => backoff,
+        }
+    }
+}
+
+/// `delay` from now, capped at [`MAX_RETRY_DELAY`].
+fn after(delay: Duration) -> DateTime<Utc> {
+    let now = Utc::now();
+    chrono::Duration::from_std(delay.min(MAX_RETRY_DELAY))
+        .ok()
+        .and_then(|delay| now.checked_add_signed(delay))
+        .unwrap_or(now)
+}
+
+fn earliest(current: Option<DateTime<Utc>>, candidate: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    Some(current.map_or(candidate, |due| due.min(candidate)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::{Channel, ChannelEvent, ChannelResult, SendReceipt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::{mpsc, watch};
+
+    /// Fails the first `failures` sends with a retryable error.
+    struct FlakyChannel {
+        failures: usize,
+        calls: Arc<AtomicUsize>,
+        sent: Arc<parking_lot::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Channel for FlakyChannel {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(ChannelError::RateLimited { retry_after: None });
+            }
+            self.sent.lock().push(message.content.clone());
+            Ok(SendReceipt::default())
+        }
+
+        async fn listen(
+            &self,
+            _tx: mpsc::Sender<ChannelEvent>,
+            _shutdown: watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
+            Ok(())
+        }
+    }
+
+    fn flaky_registry(
+        failures: usize,
+    ) -> (
+        Arc<ChannelRegistry>,
+        Arc<AtomicUsize>,
+        Arc<parking_lot::Mutex<Vec<String>>>,
+    ) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let sent = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let registry = ChannelRegistry::new(vec![Box::new(FlakyChannel {
+            failures,
+            calls: Arc::clone(&calls),
+            sent: Arc::clone(&sent),
+        })]);
+        (Arc::new(registry), calls, sent)
+    }
+
+    #[tokio::test]
+    async fn pending_items_survive_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbound");
+        let (registry, _calls, sent) = flaky_registry(0);
+
+        let store = Arc::new(JsonFileQueue::open(&path).await.unwrap());
+        let queue = OutboundQueue::new(store, Arc::clone(&registry));
+        queue
+            .enqueue("flaky", SendMessage::new("after restart", "chat"))
+            .await
+            .unwrap();
+        drop(queue);
+
+        let reopened = Arc::new(JsonFileQueue::open(&path).await.unwrap());
+        assert_eq!(reopened.pending().await.unwrap().len(), 1);
+        let queue = OutboundQueue::new(reopened.clone(), registry);
+        assert_eq!(queue.drain_due().await.unwrap(), None);
+
+        assert_eq!(*sent.lock(), vec!["after restart".to_string()]);
+        assert!(reopened.pending().await.unwrap().is_empty());
+        let on_disk = JsonFileQueue::open(&path).await.unwrap();
+        assert!(on_disk.pending().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn retryable_failures_are_rescheduled_with_backoff() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(JsonFileQueue::open(dir.path().join("q")).await.unwrap());
+        let (registry, calls, sent) = flaky_registry(1);
+        let queue = OutboundQueue::new(store.clone(), registry)
+            .with_backoff(Duration::from_secs(2), Duration::from_secs(60));
+        queue
+            .enqueue("flaky", SendMessage::new("retry me", "chat"))
+            .await
+            .unwrap();
+
+        let next_due = queue.drain_due().await.unwrap().expect("rescheduled");
+        assert!(next_due > Utc::now() + chrono::Duration::milliseconds(1500));
+        let pending = store.pending().await.unwrap();
+        assert_eq!(pending[0].attempts, 1);
+
+        // Not yet due: nothing is attempted.
+        queue.drain_due().await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let mut due_now = pending[0].clone();
+        due_now.next_attempt_at = Utc::now();
+        store.update(&due_now).await.unwrap();
+        assert_eq!(queue.drain_due().await.unwrap(), None);
+        assert_eq!(*sent.lock(), vec!["retry me".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn run_delivers_enqueued_messages_until_shutdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(JsonFileQueue::open(dir.path().join("q")).await.unwrap());
+        let (registry, _calls, sent) = flaky_registry(0);
+        let queue = Arc::new(OutboundQueue::new(store, registry));
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let worker = tokio::spawn({
+            let queue = Arc::clone(&queue);
+            async move { queue.run(shutdown_rx).await }
+        });
+
+        queue
+            .enqueue("flaky", SendMessage::new("queued", "chat"))
+            .await
+            .unwrap();
+        for _ in 0..100 {
+            if !sent.lock().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(*sent.lock(), vec!["queued".to_string()]);
+
+        shutdown_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), worker)
+            .await
+            .expect("worker stops on shutdown")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn exhausted_and_unknown_items_are_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(JsonFileQueue::open(dir.path().join("q")).await.unwrap());
+        let (registry, calls, _sent) = flaky_registry(usize::MAX);
+        let queue = OutboundQueue::new(store.clone(), registry).with_max_attempts(1);
+
+        queue
+            .enqueue("flaky", SendMessage::new("doomed", "chat"))
+            .await
+            .unwrap();
+        queue
+            .enqueue("missing", SendMessage::new("nowhere", "chat"))
+            .await
+            .unwrap();
+
+        assert_eq!(queue.drain_due().await.unwrap(), None);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(store.pending().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn items_are_stored_one_file_each_with_base64_attachments() {
+        let dir = tempfile::tempdir().unwrap();
+        let (registry, _calls, _sent) = flaky_registry(usize::MAX);
+        let store = Arc::new(JsonFileQueue::open(dir.path()).await.unwrap());
+        let queue = OutboundQueue::new(store.clone(), registry);
+        let message = SendMessage::new("report", "chat").with_attachment(
+            crate::channels::traits::Attachment::File {
+                bytes: vec![0xFF; 3],
+                filename: "r.bin".into(),
+                mime: "application/octet-stream".into(),
+            },
+        );
+        let first = queue.enqueue("flaky", message.clone()).await.unwrap();
+        let second = queue.enqueue("flaky", message).await.unwrap();
+
+        let stored = std::fs::read_to_string(dir.path().join(format!("{first}.json"))).unwrap();
+        assert!(stored.contains("\"////\""), "{stored}");
+        assert!(dir.path().join(format!("{second}.json")).exists());
+
+        store.remove(&first).await.unwrap();
+        assert!(!dir.path().join(format!("{first}.json")).exists());
+        let reopened = JsonFileQueue::open(dir.path()).await.unwrap();
+        let pending = reopened.pending().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, second);
+        assert_eq!(pending[0].message.attachments[0].bytes(), [0xFF; 3]);
+    }
+
+    #[test]
+    fn huge_delays_are_capped_instead_of_overflowing() {
+        let capped = after(Duration::MAX);
+        let limit = Utc::now() + chrono::Duration::from_std(MAX_RETRY_DELAY).unwrap();
+        assert!(capped <= limit);
+        assert!(capped > Utc::now() + chrono::Duration::hours(23));
+    }
+
+    #[test]
+    fn retry_delay_doubles_and_honours_retry_after() {
+        let (registry, _, _) = flaky_registry(0);
+        let store: Arc<dyn Queue> = Arc::new(JsonFileQueue {
+            dir: PathBuf::from("unused"),
+            items: tokio::sync::Mutex::new(Vec::new()),
+        });
+        let queue = OutboundQueue::new(store, registry)
+            .with_backoff(Duration::from_secs(2), Duration::from_secs(10));
+        let limited = ChannelError::RateLimited { retry_after: None };
+
+        assert_eq!(queue.retry_delay(1, &limited), Duration::from_secs(2));
+        assert_eq!(queue.retry_delay(2, &limited), Duration::from_secs(4));
+        assert_eq!(queue.retry_delay(5, &limited), Duration::from_secs(10));
+        assert_eq!(
+            queue.retry_delay(
+                1,
+                &ChannelError::RateLimited {
+                    retry_after: Some(Duration::from_secs(30))
+                }
+            ),
+            Duration::from_secs(30)
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use crate::config::schema::QQEnvironment;
 use async_trait::async_trait;
 use base64::Engine;
@@ -250,6 +252,14 @@ fn build_channel_message(
         channel: "qq".to_string(),
         timestamp: current_unix_timestamp_secs(),
         thread_ts: (!msg_id.is_empty()).then(|| msg_id.to_string()),
+        reply_to_message_id: None,
+        is_from_self: false,
+        mentions: Vec::new(),
+        attachments: Vec::new(),
+        chat_type: ChatType::Group,
+        triggered_by_mention: false,
+        created_at: None,
+        received_at: chrono::Utc::now(),
     }
 }
 
@@ -758,7 +768,7 @@ impl Channel for QQChannel {
         "qq"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let token = self.get_token().await?;
         let (message_url, files_url) = resolve_send_endpoints(self.api_base(), &message.recipient);
 
@@ -844,11 +854,15 @@ impl Channel for QQChannel {
             }
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
     #[allow(clippy::too_many_lines)]
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         tracing::info!("QQ: authenticating...");
         let token = self.get_token().await?;
 
@@ -856,15 +870,19 @@ impl Channel for QQChannel {
         let gw_url = self.get_gateway_url(&token).await?;
 
         tracing::info!("QQ: connecting to gateway WebSocket...");
-        let (ws_stream, _) = tokio_tungstenite::connect_async(&gw_url).await?;
+        let (ws_stream, _) = tokio_tungstenite::connect_async(&gw_url)
+            .await
+            .map_err(anyhow::Error::from)?;
         let (mut write, mut read) = ws_stream.split();
 
         // Read Hello (opcode 10)
         let hello = read
             .next()
             .await
-            .ok_or(anyhow::anyhow!("QQ: no hello frame"))??;
-        let hello_data: serde_json::Value = serde_json::from_str(&hello.to_string())?;
+            .ok_or(anyhow::anyhow!("QQ: no hello frame"))?
+            .map_err(anyhow::Error::from)?;
+        let hello_data: serde_json::Value =
+            serde_json::from_str(&hello.to_string()).map_err(anyhow::Error::from)?;
         let heartbeat_interval = hello_data
             .get("d")
             .and_then(|d| d.get("heartbeat_interval"))
@@ -888,7 +906,8 @@ impl Channel for QQChannel {
         });
         write
             .send(Message::Text(identify.to_string().into()))
-            .await?;
+            .await
+            .map_err(anyhow::Error::from)?;
 
         tracing::info!("QQ: connected and identified");
 
@@ -980,7 +999,7 @@ impl Channel for QQChannel {
                     if let Some(channel_msg) =
                         self.parse_dispatch_message_event(event_type, d).await
                     {
-                        if tx.send(channel_msg).await.is_err() {
+                        if tx.send(channel_msg.into()).await.is_err() {
                             tracing::warn!("QQ: message channel closed");
                             break;
                         }
@@ -989,7 +1008,7 @@ impl Channel for QQChannel {
             }
         }
 
-        anyhow::bail!("QQ WebSocket connection closed")
+        return Err(anyhow::anyhow!("QQ WebSocket connection closed").into());
     }
 
     async fn health_check(&self) -> bool {
@@ -0,0 +1,423 @@
+//! Name-indexed collection of channels with concurrent fan-out.
+//!
+//! [`ChannelRegistry`] holds the configured [`Channel`]s: the channel server
+//! builds one at startup and runs listeners, config reloads, and reply
+//! routing from it, and a single announcement can be delivered to every
+//! platform at once via [`ChannelRegistry::broadcast`].
+//!
+//! Sends through the registry check [`Channel::capabilities`] first: a card
+//! or rich text going to a channel that cannot render it is downgraded to
+//! plain text, or rejected, per [`CapabilityDowngradePolicy`].
+
+use super::traits::{
+    Channel, ChannelError, ChannelResult, RichText, RichTextElement, SendMessage, SendReceipt,
+};
+use crate::config::CapabilityDowngradePolicy;
+use futures_util::future::join_all;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Owns a list of channels and dispatches to them by name or all at once.
+#[derive(Default)]
+pub struct ChannelRegistry {
+    channels: Vec<Arc<dyn Channel>>,
+    downgrade: CapabilityDowngradePolicy,
+}
+
+impl ChannelRegistry {
+    pub fn new(channels: Vec<Box<dyn Channel>>) -> Self {
+        channels.into_iter().map(Arc::from).collect()
+    }
+
+    /// How sends handle cards and rich text a channel cannot render.
+    /// Default: [`CapabilityDowngradePolicy::Downgrade`].
+    pub fn with_downgrade_policy(mut self, policy: CapabilityDowngradePolicy) -> Self {
+        self.downgrade = policy;
+        self
+    }
+
+    /// Append a channel. Later registrations with a duplicate name are kept but
+    /// shadowed by the first one in [`get`](Self::get).
+    pub fn register(&mut self, channel: Box<dyn Channel>) {
+        self.channels.push(Arc::from(channel));
+    }
+
+    /// As [`register`](Self::register), for a channel that is also held
+    /// elsewhere (e.g. by its listener task).
+    pub fn register_shared(&mut self, channel: Arc<dyn Channel>) {
+        self.channels.push(channel);
+    }
+
+    /// Look up a channel by the name it reports from [`Channel::name`].
+    pub fn get(&self, name: &str) -> Option<&dyn Channel> {
+        self.channels
+            .iter()
+            .find(|channel| channel.name() == name)
+            .map(|channel| channel.as_ref())
+    }
+
+    /// Shared handle to the channel named `name`, as for [`get`](Self::get).
+    pub fn get_shared(&self, name: &str) -> Option<Arc<dyn Channel>> {
+        self.channels
+            .iter()
+            .find(|channel| channel.name() == name)
+            .cloned()
+    }
+
+    /// Registered channels in registration order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Arc<dyn Channel>> {
+        self.channels.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// Send `message` through the channel named `name`, adapted to what the
+    /// channel can render.
+    pub async fn send(&self, name: &str, message: &SendMessage) -> ChannelResult<SendReceipt> {
+        let channel = self
+            .get(name)
+            .ok_or_else(|| ChannelError::Other(anyhow::anyhow!("unknown channel {name}")))?;
+        self.send_adapted(channel, message).await
+    }
+
+    /// Send `message` to every registered channel concurrently.
+    ///
+    /// Results are returned in registration order; one channel failing does
+    /// not prevent delivery to the others.
+    pub async fn broadcast(&self, message: &SendMessage) -> Vec<Result<(), ChannelError>> {
+        join_all(self.channels.iter().map(|channel| async move {
+            self.send_adapted(channel.as_ref(), message)
+                .await
+                .map(|_receipt| ())
+        }))
+        .await
+    }
+
+    async fn send_adapted(
+        &self,
+        channel: &dyn Channel,
+        message: &SendMessage,
+    ) -> ChannelResult<SendReceipt> {
+        let message = adapt_to_capabilities(channel, message, self.downgrade)?;
+        channel.send(&message).await
+    }
+}
+
+/// Strip the card or rich text from `message` when `channel` cannot render
+/// it, keeping the caller's `content` or, when that is empty, the text
+/// extracted from the structured body.
+fn adapt_to_capabilities<'a>(
+    channel: &dyn Channel,
+    message: &'a SendMessage,
+    policy: CapabilityDowngradePolicy,
+) -> ChannelResult<Cow<'a, SendMessage>> {
+    let capabilities = channel.capabilities();
+    let drop_card = message.card.is_some() && !capabilities.supports_cards;
+    let drop_rich_text = message.rich_text.is_some() && !capabilities.supports_rich_text;
+    if !drop_card && !drop_rich_text {
+        return Ok(Cow::Borrowed(message));
+    }
+    if policy == CapabilityDowngradePolicy::Error {
+        return Err(ChannelError::NotSupported(if drop_card {
+            "cards"
+        } else {
+            "rich text"
+        }));
+    }
+
+    let mut downgraded = message.clone();
+    let mut fallback = String::new();
+    if drop_card {
+        if let Some(card) = downgraded.card.take() {
+            fallback = card_plain_text(&card);
+        }
+    }
+    if drop_rich_text {
+        if let Some(rich_text) = downgraded.rich_text.take() {
+            if fallback.is_empty() {
+                fallback = rich_text_plain_text(&rich_text);
+            }
+        }
+    }
+    if downgraded.content.trim().is_empty() {
+        downgraded.content = fallback;
+        downgraded.markdown = false;
+    }
+    tracing::debug!(
+        channel = channel.name(),
+        card = drop_card,
+        rich_text = drop_rich_text,
+        "Downgrading message to plain text for channel without the capability"
+    );
+    Ok(Cow::Owned(downgraded))
+}
+
+/// Text content of a card: the header title, then every `content` string
+/// in the body, one per line.
+fn card_plain_text(card: &serde_json::Value) -> String {
+    let mut lines = Vec::new();
+    if let Some(header) = card.get("header") {
+        collect_card_text(header, &mut lines);
+    }
+    match card {
+        serde_json::Value::Object(fields) => {
+            for (key, value) in fields {
+                if key != "header" {
+                    collect_card_text(value, &mut lines);
+                }
+            }
+        }
+        other => collect_card_text(other, &mut lines),
+    }
+    lines.join("\n")
+}
+
+fn collect_card_text(value: &serde_json::Value, lines: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            for (key, value) in fields {
+                match value {
+                    serde_json::Value::String(text) if key == "content" => {
+                        let text = text.trim();
+                        if !text.is_empty() {
+                            lines.push(text.to_string());
+                        }
+                    }
+                    _ => collect_card_text(value, lines),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_card_text(item, lines);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rich_text_plain_text(rich_text: &RichText) -> String {
+    let mut lines: Vec<String> = rich_text.title.iter().cloned().collect();
+    for paragraph in &rich_text.paragraphs {
+        let line: String = paragraph
+            .iter()
+            .map(|element| match element {
+                RichTextElement::Text { text, .. } => text.clone(),
+                RichTextElement::Link { text, href } if text == href => href.clone(),
+                RichTextElement::Link { text, href } => format!("{text} ({href})"),
+                RichTextElement::At { user_id } => format!("@{user_id}"),
+                RichTextElement::Image { .. } => String::new(),
+            })
+            .collect();
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+impl<'a> IntoIterator for &'a ChannelRegistry {
+    type Item = &'a Arc<dyn Channel>;
+    type IntoIter = std::slice::Iter<'a, Arc<dyn Channel>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Arc<dyn Channel>> for ChannelRegistry {
+    fn from_iter<I: IntoIterator<Item = Arc<dyn Channel>>>(channels: I) -> Self {
+        Self {
+            channels: channels.into_iter().collect(),
+            downgrade: CapabilityDowngradePolicy::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::mock::MockChannel;
+    use crate::channels::traits::ChannelEvent;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use tokio::sync::{mpsc, watch};
+
+    struct FailingChannel;
+
+    #[async_trait]
+    impl Channel for FailingChannel {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> ChannelResult<SendReceipt> {
+            Err(ChannelError::NotSupported("send"))
+        }
+
+        async fn listen(
+            &self,
+            _tx: mpsc::Sender<ChannelEvent>,
+            _shutdown: watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn get_finds_channel_by_reported_name() {
+        let registry = ChannelRegistry::new(vec![
+            Box::new(MockChannel::new("feishu")),
+            Box::new(MockChannel::new("slack")),
+        ]);
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.get("slack").map(Channel::name), Some("slack"));
+        assert!(registry.get("discord").is_none());
+    }
+
+    #[test]
+    fn shared_channels_are_the_registered_instances() {
+        let feishu: Arc<dyn Channel> = Arc::new(MockChannel::new("feishu"));
+        let mut registry: ChannelRegistry = std::iter::once(Arc::clone(&feishu)).collect();
+        registry.register(Box::new(MockChannel::new("slack")));
+
+        assert!(Arc::ptr_eq(
+            &registry.get_shared("feishu").unwrap(),
+            &feishu
+        ));
+        assert_eq!(
+            registry
+                .iter()
+                .map(|channel| channel.name())
+                .collect::<Vec<_>>(),
+            ["feishu", "slack"]
+        );
+        assert!(registry.get_shared("discord").is_none());
+    }
+
+    #[tokio::test]
+    async fn broadcast_reports_per_channel_results_in_order() {
+        let mut registry = ChannelRegistry::default();
+        registry.register(Box::new(MockChannel::new("feishu")));
+        registry.register(Box::new(FailingChannel));
+        registry.register(Box::new(MockChannel::new("slack")));
+
+        let results = registry
+            .broadcast(&SendMessage::new("maintenance at 5pm", "ops"))
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(ChannelError::NotSupported("send"))
+        ));
+        assert!(results[2].is_ok());
+    }
+
+    #[tokio::test]
+    async fn broadcast_on_empty_registry_is_noop() {
+        let registry = ChannelRegistry::default();
+        assert!(registry.is_empty());
+        assert!(registry
+            .broadcast(&SendMessage::new("hi", "x"))
+            .await
+            .is_empty());
+    }
+
+    fn deploy_card() -> serde_json::Value {
+        json!({
+            "header": { "title": { "tag": "plain_text", "content": "Deploy finished" } },
+            "elements": [
+                { "tag": "div", "text": { "tag": "lark_md", "content": "**api** is live" } },
+                { "tag": "hr" },
+                { "tag": "markdown", "content": "Rollback with `make rollback`" }
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn card_is_sent_as_its_text_to_channel_without_card_support() {
+        let slack = Arc::new(MockChannel::new("slack"));
+        let registry: ChannelRegistry =
+            std::iter::once(Arc::clone(&slack) as Arc<dyn Channel>).collect();
+
+        let message = SendMessage::builder().to("ops").card(deploy_card()).build();
+        registry.send("slack", &message).await.unwrap();
+
+        let sent = slack.sent_messages();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].card.is_none());
+        assert_eq!(
+            sent[0].content,
+            "Deploy finished\n**api** is live\nRollback with `make rollback`"
+        );
+    }
+
+    #[tokio::test]
+    async fn rich_text_is_flattened_and_caller_content_is_kept() {
+        let slack = Arc::new(MockChannel::new("slack"));
+        let registry: ChannelRegistry =
+            std::iter::once(Arc::clone(&slack) as Arc<dyn Channel>).collect();
+
+        let rich = RichText::new()
+            .with_title("Release")
+            .bold("v2 shipped")
+            .paragraph()
+            .text("ping ")
+            .at("ou_alice")
+            .text(" see ")
+            .link("notes", "https://example.com/notes");
+        let message = SendMessage::builder()
+            .to("ops")
+            .rich_text(rich.clone())
+            .build();
+        let with_content = SendMessage::builder()
+            .to("ops")
+            .text("v2 shipped, see notes")
+            .rich_text(rich)
+            .build();
+
+        assert!(registry.broadcast(&message).await[0].is_ok());
+        registry.send("slack", &with_content).await.unwrap();
+
+        let sent = slack.sent_messages();
+        assert!(sent.iter().all(|sent| sent.rich_text.is_none()));
+        assert_eq!(
+            sent[0].content,
+            "Release\nv2 shipped\nping @ou_alice see notes (https://example.com/notes)"
+        );
+        assert_eq!(sent[1].content, "v2 shipped, see notes");
+    }
+
+    #[tokio::test]
+    async fn error_policy_rejects_card_for_channel_without_card_support() {
+        let slack = Arc::new(MockChannel::new("slack"));
+        let registry = std::iter::once(Arc::clone(&slack) as Arc<dyn Channel>)
+            .collect::<ChannelRegistry>()
+            .with_downgrade_policy(CapabilityDowngradePolicy::Error);
+
+        let message = SendMessage::builder().to("ops").card(deploy_card()).build();
+
+        assert!(matches!(
+            registry.send("slack", &message).await,
+            Err(ChannelError::NotSupported("cards"))
+        ));
+        assert!(matches!(
+            registry.broadcast(&message).await[..],
+            [Err(ChannelError::NotSupported("cards"))]
+        ));
+        assert!(slack.sent_messages().is_empty());
+
+        registry
+            .send("slack", &SendMessage::new("plain text still goes", "ops"))
+            .await
+            .unwrap();
+        assert_eq!(slack.sent_messages().len(), 1);
+    }
+}
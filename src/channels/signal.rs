@@ -1,4 +1,6 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use futures_util::StreamExt;
 use reqwest::Client;
@@ -266,6 +268,14 @@ impl SignalChannel {
             channel: "signal".to_string(),
             timestamp: timestamp / 1000, // millis → secs
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         })
     }
 }
@@ -276,7 +286,7 @@ impl Channel for SignalChannel {
         "signal"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let params = match Self::parse_recipient_target(&message.recipient) {
             RecipientTarget::Direct(number) => serde_json::json!({
                 "recipient": [number],
@@ -291,11 +301,16 @@ impl Channel for SignalChannel {
         };
 
         self.rpc_request("send", params).await?;
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
-        let mut url = reqwest::Url::parse(&format!("{}/api/v1/events", self.http_url))?;
+    async fn listen(
+        &self,
+        tx: mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
+        let mut url = reqwest::Url::parse(&format!("{}/api/v1/events", self.http_url))
+            .map_err(anyhow::Error::from)?;
         url.query_pairs_mut().append_pair("account", &self.account);
 
         tracing::info!("Signal channel listening via SSE on {}...", self.http_url);
@@ -371,7 +386,7 @@ impl Channel for SignalChannel {
                                 Ok(sse) => {
                                     if let Some(ref envelope) = sse.envelope {
                                         if let Some(msg) = self.process_envelope(envelope) {
-                                            if tx.send(msg).await.is_err() {
+                                            if tx.send(msg.into()).await.is_err() {
                                                 return Ok(());
                                             }
                                         }
@@ -398,7 +413,7 @@ impl Channel for SignalChannel {
                     Ok(sse) => {
                         if let Some(ref envelope) = sse.envelope {
                             if let Some(msg) = self.process_envelope(envelope) {
-                                let _ = tx.send(msg).await;
+                                let _ = tx.send(msg.into()).await;
                             }
                         }
                     }
@@ -427,7 +442,7 @@ impl Channel for SignalChannel {
         resp.status().is_success()
     }
 
-    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+    async fn start_typing(&self, recipient: &str) -> ChannelResult<()> {
         let params = match Self::parse_recipient_target(recipient) {
             RecipientTarget::Direct(number) => serde_json::json!({
                 "recipient": [number],
@@ -442,7 +457,7 @@ impl Channel for SignalChannel {
         Ok(())
     }
 
-    async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+    async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
         // signal-cli doesn't have a stop-typing RPC; typing indicators
         // auto-expire after ~15s on the client side.
         Ok(())
@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use chrono::Utc;
 use futures_util::{SinkExt, StreamExt};
@@ -86,6 +88,21 @@ impl SlackChannel {
             .any(|entry| entry == "*" || entry == user_id)
     }
 
+    /// Build a receipt from a `chat.postMessage` response (`ts` is the message ID).
+    fn send_receipt_from_post_message(body: &serde_json::Value) -> SendReceipt {
+        let field = |key: &str| {
+            body.get(key)
+                .and_then(|value| value.as_str())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToOwned::to_owned)
+        };
+        SendReceipt {
+            message_id: field("ts"),
+            chat_id: field("channel"),
+        }
+    }
+
     /// Get the bot's own user ID so we can ignore our own messages
     async fn get_bot_user_id(&self) -> Option<String> {
         let resp: serde_json::Value = self
@@ -485,7 +502,7 @@ impl SlackChannel {
 
     async fn listen_socket_mode(
         &self,
-        tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
         bot_user_id: &str,
         scoped_channels: Option<Vec<String>>,
     ) -> anyhow::Result<()> {
@@ -649,9 +666,17 @@ impl SlackChannel {
                         .unwrap_or_default()
                         .as_secs(),
                     thread_ts: Self::inbound_thread_ts(event, ts),
+                    reply_to_message_id: None,
+                    is_from_self: false,
+                    mentions: Vec::new(),
+                    attachments: Vec::new(),
+                    chat_type: ChatType::Group,
+                    triggered_by_mention: false,
+                    created_at: None,
+                    received_at: chrono::Utc::now(),
                 };
 
-                if tx.send(channel_msg).await.is_err() {
+                if tx.send(channel_msg.into()).await.is_err() {
                     return Ok(());
                 }
             }
@@ -812,7 +837,7 @@ impl Channel for SlackChannel {
         "slack"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let mut body = serde_json::json!({
             "channel": message.recipient,
             "text": message.content
@@ -838,7 +863,9 @@ impl Channel for SlackChannel {
 
         if !status.is_success() {
             let sanitized = crate::providers::sanitize_api_error(&body);
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {sanitized}");
+            return Err(
+                anyhow::anyhow!("Slack chat.postMessage failed ({status}): {sanitized}").into(),
+            );
         }
 
         // Slack returns 200 for most app-level errors; check JSON "ok" field
@@ -848,20 +875,24 @@ impl Channel for SlackChannel {
                 .get("error")
                 .and_then(|e| e.as_str())
                 .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+            return Err(anyhow::anyhow!("Slack chat.postMessage failed: {err}").into());
         }
 
-        Ok(())
+        Ok(Self::send_receipt_from_post_message(&parsed))
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
         let scoped_channels = self.scoped_channel_ids();
         if self.configured_app_token().is_some() {
             tracing::info!("Slack channel listening in Socket Mode");
-            return self
+            return Ok(self
                 .listen_socket_mode(tx, &bot_user_id, scoped_channels)
-                .await;
+                .await?);
         }
 
         let mut discovered_channels: Vec<String> = Vec::new();
@@ -998,9 +1029,17 @@ impl Channel for SlackChannel {
                                 .unwrap_or_default()
                                 .as_secs(),
                             thread_ts: Self::inbound_thread_ts(msg, ts),
+                            reply_to_message_id: None,
+                            is_from_self: false,
+                            mentions: Vec::new(),
+                            attachments: Vec::new(),
+                            chat_type: ChatType::Group,
+                            triggered_by_mention: false,
+                            created_at: None,
+                            received_at: chrono::Utc::now(),
                         };
 
-                        if tx.send(channel_msg).await.is_err() {
+                        if tx.send(channel_msg.into()).await.is_err() {
                             return Ok(());
                         }
                     }
@@ -1010,12 +1049,23 @@ impl Channel for SlackChannel {
     }
 
     async fn health_check(&self) -> bool {
-        self.http_client()
+        let Ok(resp) = self
+            .http_client()
             .get("https://slack.com/api/auth.test")
             .bearer_auth(&self.bot_token)
             .send()
             .await
-            .map(|r| r.status().is_success())
+        else {
+            return false;
+        };
+        if !resp.status().is_success() {
+            return false;
+        }
+        // auth.test answers 200 even for revoked tokens; trust the "ok" flag.
+        resp.json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|body| body.get("ok").and_then(serde_json::Value::as_bool))
             .unwrap_or(false)
     }
 }
@@ -1024,6 +1074,21 @@ impl Channel for SlackChannel {
 mod tests {
     use super::*;
 
+    #[test]
+    fn slack_send_receipt_uses_post_message_ts() {
+        let receipt = SlackChannel::send_receipt_from_post_message(&serde_json::json!({
+            "ok": true,
+            "channel": "C12345",
+            "ts": "1700000000.000100"
+        }));
+        assert_eq!(receipt, SendReceipt::new("1700000000.000100", "C12345"));
+
+        let empty = SlackChannel::send_receipt_from_post_message(&serde_json::json!({
+            "ok": true
+        }));
+        assert_eq!(empty, SendReceipt::default());
+    }
+
     #[test]
     fn slack_channel_name() {
         let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec![], vec![]);
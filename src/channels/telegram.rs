@@ -1,6 +1,8 @@
 use super::ack_reaction::{select_ack_reaction, AckReactionContext, AckReactionContextChatType};
-use super::traits::{Channel, ChannelMessage, SendMessage};
-use crate::config::{AckReactionConfig, Config, StreamMode};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
+use crate::config::{AckReactionConfig, Config, StreamMode, TelegramReceiveMode};
 use crate::security::pairing::PairingGuard;
 use anyhow::Context;
 use async_trait::async_trait;
@@ -451,10 +453,13 @@ fn parse_attachment_markers(message: &str) -> (String, Vec<TelegramAttachment>)
     (cleaned.trim().to_string(), attachments)
 }
 
+/// Header carrying the `secret_token` registered via `setWebhook`.
+const TELEGRAM_WEBHOOK_SECRET_HEADER: &str = "X-Telegram-Bot-Api-Secret-Token";
+
 /// Telegram Bot API maximum file download size (20 MB).
 const TELEGRAM_MAX_FILE_DOWNLOAD_BYTES: u64 = 20 * 1024 * 1024;
 
-/// Telegram channel — long-polls the Bot API for updates
+/// Telegram channel — long-polls the Bot API or serves a webhook for updates
 pub struct TelegramChannel {
     bot_token: String,
     allowed_users: Arc<RwLock<Vec<String>>>,
@@ -477,6 +482,10 @@ pub struct TelegramChannel {
     /// Whether to send emoji reaction acknowledgments to incoming messages.
     ack_enabled: bool,
     ack_reaction: Option<AckReactionConfig>,
+    receive_mode: TelegramReceiveMode,
+    webhook_port: Option<u16>,
+    webhook_url: Option<String>,
+    webhook_secret: Option<String>,
 }
 
 impl TelegramChannel {
@@ -517,6 +526,10 @@ impl TelegramChannel {
             workspace_dir: None,
             ack_reaction: None,
             ack_enabled,
+            receive_mode: TelegramReceiveMode::Polling,
+            webhook_port: None,
+            webhook_url: None,
+            webhook_secret: None,
         }
     }
 
@@ -565,6 +578,23 @@ impl TelegramChannel {
         self
     }
 
+    /// Receive updates through a webhook server on `port` instead of long polling.
+    ///
+    /// When `url` is set it is registered with `setWebhook` on startup; `secret`
+    /// is required on every callback when configured.
+    pub fn with_webhook(
+        mut self,
+        port: Option<u16>,
+        url: Option<String>,
+        secret: Option<String>,
+    ) -> Self {
+        self.receive_mode = TelegramReceiveMode::Webhook;
+        self.webhook_port = port;
+        self.webhook_url = url;
+        self.webhook_secret = secret;
+        self
+    }
+
     /// Enable or disable emoji reaction acknowledgments to incoming messages.
     pub fn with_ack_enabled(mut self, enabled: bool) -> Self {
         self.ack_enabled = enabled;
@@ -883,6 +913,14 @@ impl TelegramChannel {
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         })
     }
 
@@ -1783,6 +1821,14 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         })
     }
 
@@ -1931,6 +1977,14 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         })
     }
 
@@ -2090,6 +2144,14 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         })
     }
 
@@ -2910,6 +2972,355 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         self.send_media_by_url("sendVoice", "voice", chat_id, thread_id, url, caption)
             .await
     }
+
+    /// Long-poll `getUpdates` and forward each update.
+    async fn listen_polling(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+    ) -> anyhow::Result<()> {
+        let mut offset: i64 = 0;
+        let mut consecutive_poll_transport_failures = 0u32;
+
+        if self.mention_only {
+            let _ = self.get_bot_username().await;
+        }
+
+        if let Err(e) = self.register_commands().await {
+            tracing::warn!("Failed to register Telegram bot commands: {e}");
+        }
+
+        tracing::info!("Telegram channel listening for messages...");
+
+        // Startup probe: claim the getUpdates slot before entering the long-poll loop.
+        // A previous daemon's 30-second poll may still be active on Telegram's server.
+        // We retry with timeout=0 until we receive a successful (non-409) response,
+        // confirming the slot is ours. This prevents the long-poll loop from entering
+        // a self-sustaining 409 cycle where each rejected request is immediately retried.
+        loop {
+            let url = self.api_url("getUpdates");
+            let probe = serde_json::json!({
+                "offset": offset,
+                "timeout": 0,
+                "allowed_updates": ["message", "callback_query"]
+            });
+            match self.http_client().post(&url).json(&probe).send().await {
+                Err(e) => {
+                    let sanitized = Self::sanitize_telegram_error(&e.to_string());
+                    tracing::warn!("Telegram startup probe error: {sanitized}; retrying in 5s");
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                }
+                Ok(resp) => {
+                    match resp.json::<serde_json::Value>().await {
+                        Err(e) => {
+                            let sanitized = Self::sanitize_telegram_error(&e.to_string());
+                            tracing::warn!(
+                                "Telegram startup probe parse error: {sanitized}; retrying in 5s"
+                            );
+                            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                        }
+                        Ok(data) => {
+                            let ok = data
+                                .get("ok")
+                                .and_then(serde_json::Value::as_bool)
+                                .unwrap_or(false);
+                            if ok {
+                                // Slot claimed — advance offset past any queued updates.
+                                if let Some(results) =
+                                    data.get("result").and_then(serde_json::Value::as_array)
+                                {
+                                    for update in results {
+                                        if let Some(uid) = update
+                                            .get("update_id")
+                                            .and_then(serde_json::Value::as_i64)
+                                        {
+                                            offset = uid + 1;
+                                        }
+                                    }
+                                }
+                                break; // Probe succeeded; enter the long-poll loop.
+                            }
+
+                            let error_code = data
+                                .get("error_code")
+                                .and_then(serde_json::Value::as_i64)
+                                .unwrap_or_default();
+                            if error_code == 409 {
+                                tracing::debug!("Startup probe: slot busy (409), retrying in 5s");
+                            } else {
+                                let desc = data
+                                    .get("description")
+                                    .and_then(serde_json::Value::as_str)
+                                    .unwrap_or("unknown");
+                                tracing::warn!(
+                                    "Startup probe: API error {error_code}: {desc}; retrying in 5s"
+                                );
+                            }
+                            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                        }
+                    }
+                }
+            }
+        }
+
+        tracing::debug!("Startup probe succeeded; entering main long-poll loop.");
+
+        loop {
+            if self.mention_only {
+                let missing_username = self.bot_username.lock().is_none();
+                if missing_username {
+                    let _ = self.get_bot_username().await;
+                }
+            }
+
+            let url = self.api_url("getUpdates");
+            let body = serde_json::json!({
+                "offset": offset,
+                "timeout": 30,
+                "allowed_updates": ["message", "callback_query"]
+            });
+
+            let resp = match self.http_client().post(&url).json(&body).send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    let sanitized = Self::sanitize_telegram_error(&e.to_string());
+                    consecutive_poll_transport_failures =
+                        consecutive_poll_transport_failures.saturating_add(1);
+                    Self::log_poll_transport_error(&sanitized, consecutive_poll_transport_failures);
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+
+            consecutive_poll_transport_failures = 0;
+
+            let data: serde_json::Value = match resp.json().await {
+                Ok(d) => d,
+                Err(e) => {
+                    let sanitized = Self::sanitize_telegram_error(&e.to_string());
+                    tracing::warn!("Telegram parse error: {sanitized}");
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+
+            let ok = data
+                .get("ok")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(true);
+            if !ok {
+                let error_code = data
+                    .get("error_code")
+                    .and_then(serde_json::Value::as_i64)
+                    .unwrap_or_default();
+                let description = data
+                    .get("description")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("unknown Telegram API error");
+
+                if error_code == 409 {
+                    tracing::warn!(
+                        "Telegram polling conflict (409): {description}. \
+Ensure only one `zeroclaw` process is using this bot token."
+                    );
+                    // Back off for 35 seconds — longer than Telegram's 30-second poll
+                    // timeout — so any competing session (e.g. a stale connection from
+                    // a previous daemon) has time to expire before we retry.
+                    tokio::time::sleep(std::time::Duration::from_secs(35)).await;
+                } else {
+                    tracing::warn!(
+                        "Telegram getUpdates API error (code={}): {description}",
+                        error_code
+                    );
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                }
+                continue;
+            }
+
+            if let Some(results) = data.get("result").and_then(serde_json::Value::as_array) {
+                for update in results {
+                    // Advance offset past this update
+                    if let Some(uid) = update.get("update_id").and_then(serde_json::Value::as_i64) {
+                        offset = uid + 1;
+                    }
+
+                    if !self.dispatch_update(update, &tx).await {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Register the webhook URL (and secret) with Telegram.
+    async fn set_webhook(&self, url: &str) -> anyhow::Result<()> {
+        let mut body = serde_json::json!({
+            "url": url,
+            "allowed_updates": ["message", "callback_query"]
+        });
+        if let Some(secret) = &self.webhook_secret {
+            body["secret_token"] = serde_json::Value::String(secret.clone());
+        }
+
+        let resp = self
+            .http_client()
+            .post(self.api_url("setWebhook"))
+            .json(&body)
+            .send()
+            .await?;
+        let status = resp.status();
+        let data: serde_json::Value = resp.json().await.unwrap_or_default();
+        if !status.is_success() || data.get("ok") != Some(&serde_json::Value::Bool(true)) {
+            let description = data
+                .get("description")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown Telegram API error");
+            anyhow::bail!("Telegram setWebhook failed ({status}): {description}");
+        }
+        Ok(())
+    }
+
+    /// Serve `POST /telegram` for Bot API webhook updates and forward each update.
+    /// Every request must echo `webhook_secret`, so the secret is required.
+    async fn listen_webhook(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+    ) -> anyhow::Result<()> {
+        use axum::{
+            extract::State,
+            http::{HeaderMap, StatusCode},
+            routing::post,
+            Json, Router,
+        };
+
+        #[derive(Clone)]
+        struct AppState {
+            secret: String,
+            updates: tokio::sync::mpsc::Sender<serde_json::Value>,
+        }
+
+        async fn handle_update(
+            State(state): State<AppState>,
+            headers: HeaderMap,
+            Json(update): Json<serde_json::Value>,
+        ) -> StatusCode {
+            let provided = headers
+                .get(TELEGRAM_WEBHOOK_SECRET_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("");
+            if !crate::security::pairing::constant_time_eq(provided, &state.secret) {
+                return StatusCode::UNAUTHORIZED;
+            }
+            if state.updates.send(update).await.is_err() {
+                return StatusCode::SERVICE_UNAVAILABLE;
+            }
+            StatusCode::OK
+        }
+
+        let port = self.webhook_port.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Telegram webhook mode requires `webhook_port` to be set in [channels_config.telegram]"
+            )
+        })?;
+        let secret = self
+            .webhook_secret
+            .clone()
+            .filter(|secret| !secret.trim().is_empty())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Telegram webhook mode requires `webhook_secret` to be set in [channels_config.telegram]"
+                )
+            })?;
+
+        if self.mention_only {
+            let _ = self.get_bot_username().await;
+        }
+        if let Err(e) = self.register_commands().await {
+            tracing::warn!("Failed to register Telegram bot commands: {e}");
+        }
+        if let Some(url) = &self.webhook_url {
+            self.set_webhook(url).await?;
+        }
+
+        let (updates_tx, mut updates_rx) = tokio::sync::mpsc::channel(64);
+        let app = Router::new()
+            .route("/telegram", post(handle_update))
+            .with_state(AppState {
+                secret,
+                updates: updates_tx,
+            });
+
+        let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        tracing::info!("Telegram webhook server listening on {addr}");
+        let mut server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        loop {
+            tokio::select! {
+                result = &mut server => {
+                    result??;
+                    return Ok(());
+                }
+                Some(update) = updates_rx.recv() => {
+                    if !self.dispatch_update(&update, &tx).await {
+                        server.abort();
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Convert one Bot API update into a [`ChannelMessage`] and forward it.
+    ///
+    /// Returns `false` once the receiving side of `tx` is gone.
+    async fn dispatch_update(
+        &self,
+        update: &serde_json::Value,
+        tx: &tokio::sync::mpsc::Sender<ChannelEvent>,
+    ) -> bool {
+        let msg = if let Some(m) = self.parse_update_message(update) {
+            m
+        } else if let Some(m) = self.try_parse_approval_callback_query(update) {
+            m
+        } else if let Some(m) = self.try_parse_voice_message(update).await {
+            m
+        } else if let Some(m) = self.try_parse_attachment_message(update).await {
+            m
+        } else {
+            self.handle_unauthorized_message(update).await;
+            return true;
+        };
+
+        if let Some((reaction_chat_id, reaction_message_id, chat_type, sender_id)) =
+            Self::extract_update_message_ack_target(update)
+        {
+            let reaction_ctx = AckReactionContext {
+                text: &msg.content,
+                sender_id: sender_id.as_deref(),
+                chat_id: Some(&reaction_chat_id),
+                chat_type,
+                locale_hint: None,
+            };
+            if let Some(emoji) = select_ack_reaction(
+                self.ack_reaction.as_ref(),
+                TELEGRAM_ACK_REACTIONS,
+                &reaction_ctx,
+            ) {
+                self.try_add_ack_reaction_nonblocking(reaction_chat_id, reaction_message_id, emoji);
+            }
+        }
+
+        // Send "typing" indicator immediately when we receive a message
+        let typing_body = Self::build_typing_action_body(&msg.reply_target);
+        let _ = self
+            .http_client()
+            .post(self.api_url("sendChatAction"))
+            .json(&typing_body)
+            .send()
+            .await; // Ignore errors for typing indicator
+
+        tx.send(msg.into()).await.is_ok()
+    }
 }
 
 #[async_trait]
@@ -2922,7 +3333,7 @@ impl Channel for TelegramChannel {
         self.stream_mode != StreamMode::Off
     }
 
-    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+    async fn send_draft(&self, message: &SendMessage) -> ChannelResult<Option<String>> {
         if self.stream_mode == StreamMode::Off {
             return Ok(None);
         }
@@ -2971,7 +3382,7 @@ impl Channel for TelegramChannel {
         if !resp.status().is_success() {
             let err = resp.text().await.unwrap_or_default();
             let sanitized = Self::sanitize_telegram_error(&err);
-            anyhow::bail!("Telegram sendMessage (draft) failed: {sanitized}");
+            return Err(anyhow::anyhow!("Telegram sendMessage (draft) failed: {sanitized}").into());
         }
 
         let resp_json: serde_json::Value = resp.json().await?;
@@ -2993,7 +3404,7 @@ impl Channel for TelegramChannel {
         recipient: &str,
         message_id: &str,
         text: &str,
-    ) -> anyhow::Result<Option<String>> {
+    ) -> ChannelResult<Option<String>> {
         let (chat_id, thread_id) = Self::parse_reply_target(recipient);
 
         // Truncate to Telegram limit for mid-stream edits (UTF-8 safe)
@@ -3027,9 +3438,11 @@ impl Channel for TelegramChannel {
                         draft_id = parsed_draft_id,
                         "Telegram sendMessageDraft update failed: {error}"
                     );
-                    return Err(error).context(format!(
-                        "Telegram sendMessageDraft update failed for chat {chat_id} draft_id {parsed_draft_id}"
-                    ));
+                    return Err(error
+                        .context(format!(
+                            "Telegram sendMessageDraft update failed for chat {chat_id} draft_id {parsed_draft_id}"
+                        ))
+                        .into());
                 }
                 return Ok(None);
             }
@@ -3086,7 +3499,7 @@ impl Channel for TelegramChannel {
         recipient: &str,
         message_id: &str,
         text: &str,
-    ) -> anyhow::Result<()> {
+    ) -> ChannelResult<()> {
         let text = &strip_tool_call_tags(text);
         let (chat_id, thread_id) = Self::parse_reply_target(recipient);
 
@@ -3168,15 +3581,15 @@ impl Channel for TelegramChannel {
             }
 
             // Fall back to chunked send
-            return self
+            return Ok(self
                 .send_text_chunks(text, &chat_id, thread_id.as_deref())
-                .await;
+                .await?);
         }
 
         let Some(id) = msg_id else {
-            return self
+            return Ok(self
                 .send_text_chunks(text, &chat_id, thread_id.as_deref())
-                .await;
+                .await?);
         };
 
         // Try editing with HTML formatting
@@ -3258,8 +3671,9 @@ impl Channel for TelegramChannel {
         match del_resp {
             Ok(r) if r.status().is_success() => {
                 // Draft deleted — safe to send fresh message without duplication
-                self.send_text_chunks(text, &chat_id, thread_id.as_deref())
-                    .await
+                Ok(self
+                    .send_text_chunks(text, &chat_id, thread_id.as_deref())
+                    .await?)
             }
             Ok(r) => {
                 let status = r.status();
@@ -3277,7 +3691,7 @@ impl Channel for TelegramChannel {
         }
     }
 
-    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> ChannelResult<()> {
         let (chat_id, thread_id) = Self::parse_reply_target(recipient);
         self.last_draft_edit.lock().remove(&chat_id);
 
@@ -3319,7 +3733,7 @@ impl Channel for TelegramChannel {
         Ok(())
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         // Strip tool_call tags before processing to prevent Markdown parsing failures
         let content = strip_tool_call_tags(&message.content);
 
@@ -3341,16 +3755,17 @@ impl Channel for TelegramChannel {
                 self.send_attachment(chat_id, thread_id, attachment).await?;
             }
 
-            return Ok(());
+            return Ok(SendReceipt::default());
         }
 
         if let Some(attachment) = parse_path_only_attachment(&content) {
             self.send_attachment(chat_id, thread_id, &attachment)
                 .await?;
-            return Ok(());
+            return Ok(SendReceipt::default());
         }
 
-        self.send_text_chunks(&content, chat_id, thread_id).await
+        self.send_text_chunks(&content, chat_id, thread_id).await?;
+        Ok(SendReceipt::default())
     }
 
     async fn send_approval_prompt(
@@ -3360,7 +3775,7 @@ impl Channel for TelegramChannel {
         tool_name: &str,
         arguments: &serde_json::Value,
         thread_ts: Option<String>,
-    ) -> anyhow::Result<()> {
+    ) -> ChannelResult<()> {
         let (chat_id, parsed_thread_id) = Self::parse_reply_target(recipient);
         let thread_id = parsed_thread_id.or(thread_ts);
 
@@ -3390,229 +3805,24 @@ impl Channel for TelegramChannel {
             let status = response.status();
             let err = response.text().await.unwrap_or_default();
             let sanitized = Self::sanitize_telegram_error(&err);
-            anyhow::bail!("Telegram approval prompt failed ({status}): {sanitized}");
+            return Err(
+                anyhow::anyhow!("Telegram approval prompt failed ({status}): {sanitized}").into(),
+            );
         }
 
         Ok(())
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
-        let mut offset: i64 = 0;
-        let mut consecutive_poll_transport_failures = 0u32;
-
-        if self.mention_only {
-            let _ = self.get_bot_username().await;
-        }
-
-        if let Err(e) = self.register_commands().await {
-            tracing::warn!("Failed to register Telegram bot commands: {e}");
-        }
-
-        tracing::info!("Telegram channel listening for messages...");
-
-        // Startup probe: claim the getUpdates slot before entering the long-poll loop.
-        // A previous daemon's 30-second poll may still be active on Telegram's server.
-        // We retry with timeout=0 until we receive a successful (non-409) response,
-        // confirming the slot is ours. This prevents the long-poll loop from entering
-        // a self-sustaining 409 cycle where each rejected request is immediately retried.
-        loop {
-            let url = self.api_url("getUpdates");
-            let probe = serde_json::json!({
-                "offset": offset,
-                "timeout": 0,
-                "allowed_updates": ["message", "callback_query"]
-            });
-            match self.http_client().post(&url).json(&probe).send().await {
-                Err(e) => {
-                    let sanitized = Self::sanitize_telegram_error(&e.to_string());
-                    tracing::warn!("Telegram startup probe error: {sanitized}; retrying in 5s");
-                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                }
-                Ok(resp) => {
-                    match resp.json::<serde_json::Value>().await {
-                        Err(e) => {
-                            let sanitized = Self::sanitize_telegram_error(&e.to_string());
-                            tracing::warn!(
-                                "Telegram startup probe parse error: {sanitized}; retrying in 5s"
-                            );
-                            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                        }
-                        Ok(data) => {
-                            let ok = data
-                                .get("ok")
-                                .and_then(serde_json::Value::as_bool)
-                                .unwrap_or(false);
-                            if ok {
-                                // Slot claimed — advance offset past any queued updates.
-                                if let Some(results) =
-                                    data.get("result").and_then(serde_json::Value::as_array)
-                                {
-                                    for update in results {
-                                        if let Some(uid) = update
-                                            .get("update_id")
-                                            .and_then(serde_json::Value::as_i64)
-                                        {
-                                            offset = uid + 1;
-                                        }
-                                    }
-                                }
-                                break; // Probe succeeded; enter the long-poll loop.
-                            }
-
-                            let error_code = data
-                                .get("error_code")
-                                .and_then(serde_json::Value::as_i64)
-                                .unwrap_or_default();
-                            if error_code == 409 {
-                                tracing::debug!("Startup probe: slot busy (409), retrying in 5s");
-                            } else {
-                                let desc = data
-                                    .get("description")
-                                    .and_then(serde_json::Value::as_str)
-                                    .unwrap_or("unknown");
-                                tracing::warn!(
-                                    "Startup probe: API error {error_code}: {desc}; retrying in 5s"
-                                );
-                            }
-                            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                        }
-                    }
-                }
-            }
-        }
-
-        tracing::debug!("Startup probe succeeded; entering main long-poll loop.");
-
-        loop {
-            if self.mention_only {
-                let missing_username = self.bot_username.lock().is_none();
-                if missing_username {
-                    let _ = self.get_bot_username().await;
-                }
-            }
-
-            let url = self.api_url("getUpdates");
-            let body = serde_json::json!({
-                "offset": offset,
-                "timeout": 30,
-                "allowed_updates": ["message", "callback_query"]
-            });
-
-            let resp = match self.http_client().post(&url).json(&body).send().await {
-                Ok(r) => r,
-                Err(e) => {
-                    let sanitized = Self::sanitize_telegram_error(&e.to_string());
-                    consecutive_poll_transport_failures =
-                        consecutive_poll_transport_failures.saturating_add(1);
-                    Self::log_poll_transport_error(&sanitized, consecutive_poll_transport_failures);
-                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                    continue;
-                }
-            };
-
-            consecutive_poll_transport_failures = 0;
-
-            let data: serde_json::Value = match resp.json().await {
-                Ok(d) => d,
-                Err(e) => {
-                    let sanitized = Self::sanitize_telegram_error(&e.to_string());
-                    tracing::warn!("Telegram parse error: {sanitized}");
-                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                    continue;
-                }
-            };
-
-            let ok = data
-                .get("ok")
-                .and_then(serde_json::Value::as_bool)
-                .unwrap_or(true);
-            if !ok {
-                let error_code = data
-                    .get("error_code")
-                    .and_then(serde_json::Value::as_i64)
-                    .unwrap_or_default();
-                let description = data
-                    .get("description")
-                    .and_then(serde_json::Value::as_str)
-                    .unwrap_or("unknown Telegram API error");
-
-                if error_code == 409 {
-                    tracing::warn!(
-                        "Telegram polling conflict (409): {description}. \
-Ensure only one `zeroclaw` process is using this bot token."
-                    );
-                    // Back off for 35 seconds — longer than Telegram's 30-second poll
-                    // timeout — so any competing session (e.g. a stale connection from
-                    // a previous daemon) has time to expire before we retry.
-                    tokio::time::sleep(std::time::Duration::from_secs(35)).await;
-                } else {
-                    tracing::warn!(
-                        "Telegram getUpdates API error (code={}): {description}",
-                        error_code
-                    );
-                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                }
-                continue;
-            }
-
-            if let Some(results) = data.get("result").and_then(serde_json::Value::as_array) {
-                for update in results {
-                    // Advance offset past this update
-                    if let Some(uid) = update.get("update_id").and_then(serde_json::Value::as_i64) {
-                        offset = uid + 1;
-                    }
-
-                    let msg = if let Some(m) = self.parse_update_message(update) {
-                        m
-                    } else if let Some(m) = self.try_parse_approval_callback_query(update) {
-                        m
-                    } else if let Some(m) = self.try_parse_voice_message(update).await {
-                        m
-                    } else if let Some(m) = self.try_parse_attachment_message(update).await {
-                        m
-                    } else {
-                        self.handle_unauthorized_message(update).await;
-                        continue;
-                    };
-
-                    if let Some((reaction_chat_id, reaction_message_id, chat_type, sender_id)) =
-                        Self::extract_update_message_ack_target(update)
-                    {
-                        let reaction_ctx = AckReactionContext {
-                            text: &msg.content,
-                            sender_id: sender_id.as_deref(),
-                            chat_id: Some(&reaction_chat_id),
-                            chat_type,
-                            locale_hint: None,
-                        };
-                        if let Some(emoji) = select_ack_reaction(
-                            self.ack_reaction.as_ref(),
-                            TELEGRAM_ACK_REACTIONS,
-                            &reaction_ctx,
-                        ) {
-                            self.try_add_ack_reaction_nonblocking(
-                                reaction_chat_id,
-                                reaction_message_id,
-                                emoji,
-                            );
-                        }
-                    }
-
-                    // Send "typing" indicator immediately when we receive a message
-                    let typing_body = Self::build_typing_action_body(&msg.reply_target);
-                    let _ = self
-                        .http_client()
-                        .post(self.api_url("sendChatAction"))
-                        .json(&typing_body)
-                        .send()
-                        .await; // Ignore errors for typing indicator
-
-                    if tx.send(msg).await.is_err() {
-                        return Ok(());
-                    }
-                }
-            }
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
+        match self.receive_mode {
+            TelegramReceiveMode::Polling => self.listen_polling(tx).await?,
+            TelegramReceiveMode::Webhook => self.listen_webhook(tx).await?,
         }
+        Ok(())
     }
 
     async fn health_check(&self) -> bool {
@@ -3637,7 +3847,7 @@ Ensure only one `zeroclaw` process is using this bot token."
         }
     }
 
-    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+    async fn start_typing(&self, recipient: &str) -> ChannelResult<()> {
         self.stop_typing(recipient).await?;
 
         let client = self.http_client();
@@ -3662,7 +3872,7 @@ Ensure only one `zeroclaw` process is using this bot token."
         Ok(())
     }
 
-    async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+    async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
         let mut guard = self.typing_handle.lock();
         if let Some(handle) = guard.take() {
             handle.abort();
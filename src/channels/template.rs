@@ -0,0 +1,227 @@
+//! `{name}`-style templates for outbound notification text.
+//!
+//! Values are escaped before substitution (`&`, `<`, `>` become HTML
+//! entities) so user-supplied text cannot inject Feishu/Lark `<at>` tags or
+//! markup, and a value is never re-scanned for placeholders. Write `{{` and
+//! `}}` for literal braces in the template itself.
+
+use std::collections::HashMap;
+
+/// Why a template could not be parsed or rendered.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TemplateError {
+    /// A `{` without a matching `}`.
+    #[error("unclosed `{{` at byte {offset}")]
+    Unclosed { offset: usize },
+    /// A `}` that does not close a placeholder; write `}}` for a literal brace.
+    #[error("unmatched `}}` at byte {offset} (write `}}}}` for a literal brace)")]
+    UnmatchedClose { offset: usize },
+    /// A placeholder name that is empty or not made of `[A-Za-z0-9_.-]`.
+    #[error("invalid variable name {name:?} at byte {offset}")]
+    InvalidName { name: String, offset: usize },
+    /// Placeholders with no value, in template order.
+    #[error("template variables not provided: {}", .0.join(", "))]
+    MissingVariables(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Variable(String),
+}
+
+/// A parsed `{name}` template.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use zeroclaw::channels::template::Template;
+///
+/// let template = Template::parse("Deploy {service} to {env}: {status}").unwrap();
+/// let vars = HashMap::from([
+///     ("service".to_string(), "api".to_string()),
+///     ("env".to_string(), "prod".to_string()),
+///     ("status".to_string(), "<ok>".to_string()),
+/// ]);
+/// assert_eq!(template.render(&vars).unwrap(), "Deploy api to prod: &lt;ok&gt;");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Parse `source`, rejecting unbalanced braces and invalid names.
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.char_indices().peekable();
+        while let Some((offset, ch)) = chars.next() {
+            match ch {
+                '{' if chars.next_if(|&(_, next)| next == '{').is_some() => literal.push('{'),
+                '}' if chars.next_if(|&(_, next)| next == '}').is_some() => literal.push('}'),
+                '}' => return Err(TemplateError::UnmatchedClose { offset }),
+                '{' => {
+                    let start = offset + 1;
+                    let end = source[start..]
+                        .find('}')
+                        .map(|len| start + len)
+                        .ok_or(TemplateError::Unclosed { offset })?;
+                    let name = &source[start..end];
+                    if !is_valid_name(name) {
+                        return Err(TemplateError::InvalidName {
+                            name: name.to_string(),
+                            offset,
+                        });
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Variable(name.to_string()));
+                    while chars.next_if(|&(index, _)| index <= end).is_some() {}
+                }
+                _ => literal.push(ch),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Placeholder names in template order, repeats included.
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Variable(name) => Some(name.as_str()),
+            Part::Literal(_) => None,
+        })
+    }
+
+    /// Substitute escaped `vars`; fails listing every placeholder without a value.
+    pub fn render(&self, vars: &HashMap<String, String>) -> Result<String, TemplateError> {
+        let mut missing: Vec<String> = Vec::new();
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => out.push_str(text),
+                Part::Variable(name) => match vars.get(name) {
+                    Some(value) => out.push_str(&escape_value(value)),
+                    None if missing.contains(name) => {}
+                    None => missing.push(name.clone()),
+                },
+            }
+        }
+        if missing.is_empty() {
+            Ok(out)
+        } else {
+            Err(TemplateError::MissingVariables(missing))
+        }
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-'))
+}
+
+/// Escape `&`, `<`, and `>` so a value renders as plain text.
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn substitutes_and_escapes_values() {
+        let template = Template::parse("Deploy {service} to {env} {status}").unwrap();
+        let rendered = template
+            .render(&vars(&[
+                ("service", "billing & auth"),
+                ("env", "prod"),
+                ("status", "<at user_id=\"all\"></at>"),
+            ]))
+            .unwrap();
+
+        assert_eq!(
+            rendered,
+            "Deploy billing &amp; auth to prod &lt;at user_id=\"all\"&gt;&lt;/at&gt;"
+        );
+        assert_eq!(
+            template.variables().collect::<Vec<_>>(),
+            ["service", "env", "status"]
+        );
+    }
+
+    #[test]
+    fn braces_in_values_are_not_expanded() {
+        let template = Template::parse("{a} then {b}").unwrap();
+        let rendered = template
+            .render(&vars(&[("a", "{b}"), ("b", "}{\"json\": 1}{")]))
+            .unwrap();
+
+        assert_eq!(rendered, "{b} then }{\"json\": 1}{");
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        let template = Template::parse("{{literal}} {name}}}").unwrap();
+        assert_eq!(
+            template.render(&vars(&[("name", "x")])).unwrap(),
+            "{literal} x}"
+        );
+    }
+
+    #[test]
+    fn missing_variables_are_all_reported_once() {
+        let template = Template::parse("{a} {b} {a} {c}").unwrap();
+        let err = template.render(&vars(&[("b", "ok")])).unwrap_err();
+
+        assert_eq!(
+            err,
+            TemplateError::MissingVariables(vec!["a".into(), "c".into()])
+        );
+        assert_eq!(err.to_string(), "template variables not provided: a, c");
+    }
+
+    #[test]
+    fn malformed_templates_are_rejected() {
+        assert_eq!(
+            Template::parse("hi {name").unwrap_err(),
+            TemplateError::Unclosed { offset: 3 }
+        );
+        assert_eq!(
+            Template::parse("hi name}").unwrap_err(),
+            TemplateError::UnmatchedClose { offset: 7 }
+        );
+        assert_eq!(
+            Template::parse("hi {}").unwrap_err(),
+            TemplateError::InvalidName {
+                name: String::new(),
+                offset: 3
+            }
+        );
+        assert!(matches!(
+            Template::parse("{first name}").unwrap_err(),
+            TemplateError::InvalidName { .. }
+        ));
+    }
+}
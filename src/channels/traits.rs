@@ -1,4 +1,151 @@
+//! The [`Channel`] trait and the message types every channel shares.
+//!
+//! # Object safety
+//!
+//! [`Channel`] must stay dyn-compatible: the runtime and
+//! [`ChannelRegistry`](super::registry::ChannelRegistry) hold every channel as
+//! `Box<dyn Channel>` / `Arc<dyn Channel>`. New methods must not be generic,
+//! return `Self` or take `self` by value; put such helpers on the concrete
+//! type or bound them with `where Self: Sized`. A compile-time check next to
+//! the trait breaks the build if this stops holding.
+
+use super::feishu_errors::FeishuErrorCode;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Failure modes callers of [`Channel`] methods may want to branch on.
+///
+/// Anything without a dedicated variant is carried by [`ChannelError::Other`],
+/// so implementations can keep using `anyhow` internally and `?` into this type.
+#[derive(Debug, thiserror::Error)]
+pub enum ChannelError {
+    /// Credentials were rejected or the access token is invalid.
+    #[error("channel authentication failed: {0}")]
+    Unauthorized(String),
+    /// The platform throttled the request.
+    #[error("channel rate limited{}", retry_after.map(|d| format!(" (retry after {}s)", d.as_secs())).unwrap_or_default())]
+    RateLimited { retry_after: Option<Duration> },
+    /// The sender or recipient is not on the allowlist.
+    #[error("user not allowed: {0}")]
+    UserNotAllowed(String),
+    /// The operation is not implemented by this channel.
+    #[error("{0} not supported by this channel")]
+    NotSupported(&'static str),
+    /// Transport-level failure talking to the platform.
+    #[error("channel network error: {0}")]
+    Network(#[from] reqwest::Error),
+    /// The platform answered with a server error (5xx); likely transient.
+    #[error("channel service unavailable: {0}")]
+    Unavailable(String),
+    /// The platform answered with a non-success business code.
+    ///
+    /// `kind` names documented Feishu/Lark codes; for other platforms, or an
+    /// undocumented code, it is [`FeishuErrorCode::Other`]. Build with
+    /// [`ChannelError::api`] to fill it from `code`.
+    #[error("channel API error {code}: {msg}")]
+    Api {
+        code: i64,
+        kind: FeishuErrorCode,
+        msg: String,
+    },
+    /// The channel's circuit breaker is open after repeated failures.
+    #[error("channel {channel} circuit open (retry after {}s)", retry_after.as_secs())]
+    CircuitOpen {
+        channel: String,
+        retry_after: Duration,
+    },
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl From<anyhow::Error> for ChannelError {
+    /// Unwraps typed errors that were carried through `anyhow` internally.
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<ChannelError>() {
+            Ok(typed) => return typed,
+            Err(err) => err,
+        };
+        // Only a bare failure to reach the platform becomes `Network`; one
+        // wrapped in context stays `Other` so the message is kept, and
+        // `is_network` still sees through it.
+        let top: &(dyn std::error::Error + 'static) = &*err;
+        if !top
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(is_transport_failure)
+        {
+            return Self::Other(err);
+        }
+        match err.downcast::<reqwest::Error>() {
+            Ok(network) => Self::Network(network),
+            Err(err) => Self::Other(err),
+        }
+    }
+}
+
+/// Connect and timeout errors; other reqwest errors (bad bodies, redirects)
+/// are not transport failures.
+fn is_transport_failure(err: &reqwest::Error) -> bool {
+    err.is_connect() || err.is_timeout()
+}
+
+impl ChannelError {
+    /// An [`Api`](Self::Api) error with `kind` looked up from `code`.
+    pub fn api(code: i64, msg: impl Into<String>) -> Self {
+        Self::Api {
+            code,
+            kind: FeishuErrorCode::from(code),
+            msg: msg.into(),
+        }
+    }
+
+    /// Whether retrying the same call later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.is_network()
+            || matches!(
+                self,
+                Self::RateLimited { .. } | Self::Unavailable(_) | Self::CircuitOpen { .. }
+            )
+    }
+
+    /// Whether the platform could not be reached: [`Network`](Self::Network),
+    /// or [`Other`](Self::Other) caused by a connect or timeout error.
+    pub fn is_network(&self) -> bool {
+        match self {
+            Self::Network(_) => true,
+            Self::Other(err) => err
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(is_transport_failure),
+            _ => false,
+        }
+    }
+
+    /// Downcast the wrapped `anyhow` error of an [`ChannelError::Other`].
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: std::fmt::Display + std::fmt::Debug + Send + Sync + 'static,
+    {
+        match self {
+            Self::Other(err) => err.downcast_ref::<E>(),
+            _ => None,
+        }
+    }
+}
+
+/// Result type returned by [`Channel`] methods.
+pub type ChannelResult<T> = std::result::Result<T, ChannelError>;
+
+/// Resolve once `shutdown` is set to `true`.
+///
+/// If the sender is dropped without signalling this never resolves, so a
+/// listener keeps running rather than treating a lost handle as a stop request.
+pub async fn wait_for_shutdown(shutdown: &mut tokio::sync::watch::Receiver<bool>) {
+    if shutdown.wait_for(|stop| *stop).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
 
 /// A message received from or sent to a channel
 #[derive(Debug, Clone)]
@@ -12,19 +159,513 @@ pub struct ChannelMessage {
     /// Platform thread identifier (e.g. Slack `ts`, Discord thread ID).
     /// When set, replies should be posted as threaded responses.
     pub thread_ts: Option<String>,
+    /// Platform message ID a threaded reply to this message should target
+    /// (e.g. Feishu `message_id`). Pass it to [`SendMessage::reply_to`].
+    pub reply_to_message_id: Option<String>,
+    /// Authored by this bot's own account (self-echo). Channels that can tell
+    /// drop these before forwarding; the flag lets other consumers decide.
+    pub is_from_self: bool,
+    /// Users @mentioned in the message, in platform order. Channels without
+    /// mention metadata leave this empty.
+    pub mentions: Vec<Mention>,
+    /// Media received with the message. Fetch the bytes lazily via
+    /// [`Channel::download_attachment`].
+    pub attachments: Vec<ReceivedAttachment>,
+    /// Whether the message arrived in a one-to-one chat or a group. Channels
+    /// without chat-type metadata report [`ChatType::Group`].
+    pub chat_type: ChatType,
+    /// The message @mentioned this bot, e.g. `@bot /deploy prod` in a group.
+    /// The leading mention itself is already stripped from `content`, so
+    /// commands parse as if sent directly. Channels that cannot tell leave it
+    /// `false`.
+    pub triggered_by_mention: bool,
+    /// When the platform created the message, to the millisecond where it
+    /// reports that (e.g. Feishu `create_time`). Channels that only know the
+    /// whole-second `timestamp` leave it `None`.
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When this process received the message from the platform.
+    pub received_at: chrono::DateTime<chrono::Utc>,
 }
 
-/// Message to send through a channel
+impl ChannelMessage {
+    /// Time from [`created_at`](Self::created_at) to
+    /// [`received_at`](Self::received_at), or `None` without a creation
+    /// time. Clock skew that puts creation after receipt reads as zero.
+    pub fn delivery_latency(&self) -> Option<Duration> {
+        let created_at = self.created_at?;
+        Some((self.received_at - created_at).to_std().unwrap_or_default())
+    }
+}
+
+/// Kind of conversation a [`ChannelMessage`] arrived in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChatType {
+    /// One-to-one chat between a user and the bot.
+    P2p,
+    /// Group chat with several members.
+    #[default]
+    Group,
+}
+
+/// What a channel listener forwards to the runtime through `listen`'s `tx`.
 #[derive(Debug, Clone)]
+pub enum ChannelEvent {
+    /// An inbound message.
+    Message(ChannelMessage),
+    /// The listener (re)established its platform connection.
+    Connected { channel: String },
+    /// The platform connection dropped; the listener may reconnect.
+    Disconnected { channel: String, reason: String },
+    /// A user clicked a button on an interactive card the bot sent.
+    CardAction {
+        channel: String,
+        /// Platform ID of the card message that was clicked.
+        message_id: String,
+        /// The clicked element's `value` payload, as set on the card.
+        action_value: serde_json::Value,
+        /// Platform user ID of the clicker (Feishu/Lark `open_id`).
+        operator_open_id: String,
+    },
+    /// A user submitted a form on an interactive card the bot sent.
+    FormSubmit {
+        channel: String,
+        /// Platform ID of the card message holding the form.
+        message_id: String,
+        /// Submitted input values keyed by each input's `name`.
+        fields: std::collections::HashMap<String, serde_json::Value>,
+        /// Platform user ID of the submitter (Feishu/Lark `open_id`).
+        operator_open_id: String,
+    },
+}
+
+impl ChannelEvent {
+    /// The carried message, if this is [`ChannelEvent::Message`].
+    pub fn as_message(&self) -> Option<&ChannelMessage> {
+        match self {
+            Self::Message(message) => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Take the carried message, if this is [`ChannelEvent::Message`].
+    pub fn into_message(self) -> Option<ChannelMessage> {
+        match self {
+            Self::Message(message) => Some(message),
+            _ => None,
+        }
+    }
+}
+
+impl From<ChannelMessage> for ChannelEvent {
+    fn from(message: ChannelMessage) -> Self {
+        Self::Message(message)
+    }
+}
+
+/// A user @mentioned in an incoming message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    /// Platform user ID (e.g. Feishu/Lark `open_id`).
+    pub user_id: String,
+    /// Display name shown in the client.
+    pub name: String,
+    /// Placeholder the platform put in the raw text (e.g. Feishu `@_user_1`).
+    pub key: String,
+}
+
+/// A prefixed command parsed by [`ChannelMessage::parse_command`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    /// Command name without its prefix, e.g. `deploy` for `/deploy`.
+    pub name: String,
+    /// Arguments split on whitespace; quoted segments stay together.
+    pub args: Vec<String>,
+    /// Raw text after the command name, trimmed but otherwise untouched.
+    pub rest: String,
+}
+
+impl ChannelMessage {
+    /// Parse `content` as a command starting with one of `prefixes`
+    /// (e.g. `&["/", "!"]`).
+    ///
+    /// Arguments may be quoted with `"` or `'`, and `\` escapes the next
+    /// character outside single quotes. A Telegram-style `/cmd@botname`
+    /// suffix is dropped from the name. Returns `None` when the message does
+    /// not start with a prefix or the name is empty. Channels that set
+    /// [`triggered_by_mention`](Self::triggered_by_mention) already removed
+    /// the bot's leading mention; otherwise use [`Self::parse_bot_command`].
+    pub fn parse_command(&self, prefixes: &[&str]) -> Option<Command> {
+        parse_command_text(self.content.trim(), prefixes)
+    }
+
+    /// Like [`Self::parse_command`], but first drops a leading @mention of
+    /// `bot_id` (the bot's own platform user ID), as in `@bot /deploy prod`.
+    /// A leading mention of anyone else is kept, so the message is not a
+    /// command.
+    pub fn parse_bot_command(&self, bot_id: &str, prefixes: &[&str]) -> Option<Command> {
+        let text = strip_leading_bot_mention(self.content.trim(), &self.mentions, bot_id);
+        parse_command_text(text, prefixes)
+    }
+
+    /// Whether the message is a direct (one-to-one) message.
+    pub fn is_direct(&self) -> bool {
+        self.chat_type == ChatType::P2p
+    }
+
+    /// Whether `bot_id` (the bot's own platform user ID) is among [`Self::mentions`].
+    pub fn is_bot_mentioned(&self, bot_id: &str) -> bool {
+        !bot_id.is_empty()
+            && self
+                .mentions
+                .iter()
+                .any(|mention| mention.user_id == bot_id)
+    }
+}
+
+/// Split `text` into a [`Command`] when it starts with one of `prefixes`.
+fn parse_command_text(text: &str, prefixes: &[&str]) -> Option<Command> {
+    let body = prefixes
+        .iter()
+        .filter(|prefix| !prefix.is_empty())
+        .find_map(|prefix| text.strip_prefix(prefix))?;
+    let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+    let (name, rest) = body.split_at(name_end);
+    let name = name.split('@').next().unwrap_or_default();
+    if name.is_empty() {
+        return None;
+    }
+    let rest = rest.trim();
+    Some(Command {
+        name: name.to_string(),
+        args: split_command_args(rest),
+        rest: rest.to_string(),
+    })
+}
+
+/// Drop a leading mention of `bot_id`: its placeholder (Feishu `@_user_1`),
+/// `@name`, or Slack `<@U123>`. Mentions of other users are left alone.
+fn strip_leading_bot_mention<'a>(text: &'a str, mentions: &[Mention], bot_id: &str) -> &'a str {
+    if bot_id.is_empty() {
+        return text;
+    }
+    let candidates = mentions
+        .iter()
+        .filter(|mention| mention.user_id == bot_id)
+        .flat_map(|mention| [mention.key.clone(), format!("@{}", mention.name)])
+        .chain(std::iter::once(format!("<@{bot_id}>")));
+    for candidate in candidates {
+        // Skip empty keys and a bare `@` from an empty name.
+        if candidate.len() < 2 {
+            continue;
+        }
+        if let Some(rest) = text.strip_prefix(candidate.as_str()) {
+            if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                return rest.trim_start();
+            }
+        }
+    }
+    text
+}
+
+fn split_command_args(text: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') => quote = None,
+            (Some('\''), _) => current.push(c),
+            (_, '\\') => {
+                in_arg = true;
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            (Some('"'), '"') => quote = None,
+            (Some(_), _) => current.push(c),
+            (None, '"' | '\'') => {
+                in_arg = true;
+                quote = Some(c);
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, _) => {
+                in_arg = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// Kind of media carried by a [`ReceivedAttachment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceivedAttachmentKind {
+    Image,
+    File,
+    Audio,
+}
+
+/// Reference to media attached to an incoming message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedAttachment {
+    /// Platform ID of the message carrying the attachment.
+    pub message_id: String,
+    /// Platform resource key (e.g. Feishu/Lark `image_key` or `file_key`).
+    pub key: String,
+    pub kind: ReceivedAttachmentKind,
+}
+
+/// Upload name for [`Attachment::Audio`], which carries no filename of its own.
+pub const AUDIO_ATTACHMENT_FILENAME: &str = "voice.opus";
+
+/// Binary attachment sent alongside a message. `bytes` serialize as base64.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Attachment {
+    Image {
+        #[serde(with = "base64_bytes")]
+        bytes: Vec<u8>,
+        filename: String,
+    },
+    File {
+        #[serde(with = "base64_bytes")]
+        bytes: Vec<u8>,
+        filename: String,
+        mime: String,
+    },
+    /// Voice clip, sent as a playable audio message where supported.
+    /// Feishu/Lark only accept Ogg/Opus; convert other formats first, e.g.
+    /// `ffmpeg -i in.mp3 -c:a libopus -ac 1 -ar 16000 out.opus`.
+    Audio {
+        #[serde(with = "base64_bytes")]
+        bytes: Vec<u8>,
+        /// Playback length shown in the client.
+        duration_ms: u64,
+    },
+}
+
+/// Serde adapter storing bytes as a standard base64 string.
+mod base64_bytes {
+    use base64::Engine as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Attachment {
+    pub fn filename(&self) -> &str {
+        match self {
+            Self::Image { filename, .. } | Self::File { filename, .. } => filename,
+            Self::Audio { .. } => AUDIO_ATTACHMENT_FILENAME,
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            Self::Image { bytes, .. } | Self::File { bytes, .. } | Self::Audio { bytes, .. } => {
+                bytes
+            }
+        }
+    }
+}
+
+/// Inline text decoration for [`RichTextElement::Text`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RichTextStyle {
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+}
+
+/// One inline run inside a [`RichText`] paragraph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RichTextElement {
+    Text {
+        text: String,
+        styles: Vec<RichTextStyle>,
+    },
+    Link {
+        text: String,
+        href: String,
+    },
+    /// Platform image key of an already uploaded image.
+    Image {
+        image_key: String,
+    },
+    /// Mention a user by platform user ID (`"all"` mentions everyone).
+    At {
+        user_id: String,
+    },
+}
+
+/// Formatted message body: an optional title and paragraphs of inline runs.
+///
+/// Modeled on Feishu/Lark `post` messages. Build it fluently: the inline
+/// methods append to the current paragraph and [`RichText::paragraph`]
+/// starts a new one.
+///
+/// ```
+/// use zeroclaw::channels::traits::RichText;
+///
+/// let body = RichText::new()
+///     .bold("Deploy finished")
+///     .paragraph()
+///     .text("See ")
+///     .link("the dashboard", "https://example.com/deploys/42");
+/// assert_eq!(body.paragraphs.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RichText {
+    pub title: Option<String>,
+    pub paragraphs: Vec<Vec<RichTextElement>>,
+}
+
+impl RichText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the heading shown above the paragraphs.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Start a new paragraph; following inline runs go there.
+    pub fn paragraph(mut self) -> Self {
+        self.paragraphs.push(Vec::new());
+        self
+    }
+
+    /// Append an inline run to the current paragraph.
+    pub fn push(mut self, element: RichTextElement) -> Self {
+        match self.paragraphs.last_mut() {
+            Some(paragraph) => paragraph.push(element),
+            None => self.paragraphs.push(vec![element]),
+        }
+        self
+    }
+
+    /// Append plain text.
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.styled(text, &[])
+    }
+
+    /// Append bold text.
+    pub fn bold(self, text: impl Into<String>) -> Self {
+        self.styled(text, &[RichTextStyle::Bold])
+    }
+
+    /// Append text with the given decorations.
+    pub fn styled(self, text: impl Into<String>, styles: &[RichTextStyle]) -> Self {
+        self.push(RichTextElement::Text {
+            text: text.into(),
+            styles: styles.to_vec(),
+        })
+    }
+
+    /// Append a hyperlink.
+    pub fn link(self, text: impl Into<String>, href: impl Into<String>) -> Self {
+        self.push(RichTextElement::Link {
+            text: text.into(),
+            href: href.into(),
+        })
+    }
+
+    /// Append an uploaded image by its platform key.
+    pub fn image(self, image_key: impl Into<String>) -> Self {
+        self.push(RichTextElement::Image {
+            image_key: image_key.into(),
+        })
+    }
+
+    /// Append a user mention.
+    pub fn at(self, user_id: impl Into<String>) -> Self {
+        self.push(RichTextElement::At {
+            user_id: user_id.into(),
+        })
+    }
+}
+
+/// Message to send through a channel
+///
+/// Prefer [`SendMessage::builder`] (or [`SendMessage::new`] for plain text)
+/// over a struct literal, so new fields do not break your code. The fields
+/// stay public for inspection and pattern matching.
+///
+/// ```
+/// use zeroclaw::channels::SendMessage;
+///
+/// let reply = SendMessage::builder()
+///     .to("oc_team_chat")
+///     .text("Deployed to production.")
+///     .reply_to("om_original_question")
+///     .build();
+/// assert_eq!(reply.content, "Deployed to production.");
+/// assert_eq!(reply.reply_to_message_id.as_deref(), Some("om_original_question"));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SendMessage {
     pub content: String,
     pub recipient: String,
     pub subject: Option<String>,
     /// Platform thread identifier for threaded replies (e.g. Slack `thread_ts`).
     pub thread_ts: Option<String>,
+    /// Reply to this platform message ID instead of posting a new message
+    /// (e.g. Feishu `/im/v1/messages/{id}/reply`). Ignored by channels without
+    /// reply support.
+    pub reply_to_message_id: Option<String>,
+    /// Platform-native interactive card payload (e.g. Feishu/Lark card JSON).
+    /// Channels without card support send `content` instead.
+    pub card: Option<serde_json::Value>,
+    /// Formatted body (e.g. a Feishu/Lark `post`). Channels without rich-text
+    /// support send `content` instead.
+    pub rich_text: Option<RichText>,
+    /// `content` is Markdown. Channels that can render it natively convert
+    /// it (Lark/Feishu send an interactive card); others send it verbatim.
+    pub markdown: bool,
+    /// User IDs to @mention ahead of the text (e.g. Feishu/Lark `open_id`s).
+    /// Channels without mention support ignore them.
+    pub mentions: Vec<String>,
+    /// Files/images uploaded and sent after the text content.
+    /// Channels without attachment support ignore them.
+    pub attachments: Vec<Attachment>,
+    /// Hosted image to fetch and send after the text content.
+    /// Channels without image support ignore it.
+    pub image_url: Option<String>,
+    /// Caller-chosen key that makes resending the same logical message safe,
+    /// e.g. from another process after a crash (Feishu/Lark `uuid`). Unset =
+    /// a fresh key per send. Channels without idempotent sends ignore it.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
 impl SendMessage {
+    /// Start building a message; see [`SendMessageBuilder`].
+    pub fn builder() -> SendMessageBuilder {
+        SendMessageBuilder::default()
+    }
+
     /// Create a new message with content and recipient
     pub fn new(content: impl Into<String>, recipient: impl Into<String>) -> Self {
         Self {
@@ -32,9 +673,69 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: None,
             thread_ts: None,
+            reply_to_message_id: None,
+            card: None,
+            rich_text: None,
+            markdown: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            image_url: None,
+            idempotency_key: None,
+        }
+    }
+
+    /// Create a new message whose content is Markdown.
+    pub fn markdown(content: impl Into<String>, recipient: impl Into<String>) -> Self {
+        Self {
+            markdown: true,
+            ..Self::new(content, recipient)
+        }
+    }
+
+    /// Create a message whose content is `template` rendered with `vars`.
+    ///
+    /// Placeholders are `{name}`; values are escaped so they cannot inject
+    /// markup or placeholders (see [`Template`](super::template::Template)).
+    /// The recipient is left empty; set it with [`Self::with_recipient`].
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use zeroclaw::channels::traits::SendMessage;
+    ///
+    /// let vars = HashMap::from([
+    ///     ("service".to_string(), "api".to_string()),
+    ///     ("env".to_string(), "prod".to_string()),
+    /// ]);
+    /// let message = SendMessage::from_template("Deploy {service} to {env} done", &vars)
+    ///     .unwrap()
+    ///     .with_recipient("oc_deploys");
+    /// assert_eq!(message.content, "Deploy api to prod done");
+    /// assert!(SendMessage::from_template("{missing}", &vars).is_err());
+    /// ```
+    pub fn from_template(
+        template: &str,
+        vars: &std::collections::HashMap<String, String>,
+    ) -> Result<Self, super::template::TemplateError> {
+        let content = super::template::Template::parse(template)?.render(vars)?;
+        Ok(Self::new(content, ""))
+    }
+
+    /// Create a message that sends the image hosted at `url`.
+    ///
+    /// The recipient is left empty; set it with [`Self::with_recipient`].
+    pub fn image_url(url: impl Into<String>) -> Self {
+        Self {
+            image_url: Some(url.into()),
+            ..Self::new("", "")
         }
     }
 
+    /// Address the message to `recipient`.
+    pub fn with_recipient(mut self, recipient: impl Into<String>) -> Self {
+        self.recipient = recipient.into();
+        self
+    }
+
     /// Create a new message with content, recipient, and subject
     pub fn with_subject(
         content: impl Into<String>,
@@ -46,6 +747,14 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: Some(subject.into()),
             thread_ts: None,
+            reply_to_message_id: None,
+            card: None,
+            rich_text: None,
+            markdown: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            image_url: None,
+            idempotency_key: None,
         }
     }
 
@@ -54,33 +763,269 @@ impl SendMessage {
         self.thread_ts = thread_ts;
         self
     }
+
+    /// Send as a reply to the given platform message ID.
+    pub fn reply_to(mut self, message_id: Option<String>) -> Self {
+        self.reply_to_message_id = message_id;
+        self
+    }
+
+    /// Attach an interactive card; `content` remains the plain-text fallback.
+    pub fn with_card(mut self, card: serde_json::Value) -> Self {
+        self.card = Some(card);
+        self
+    }
+
+    /// Attach a formatted body; `content` remains the plain-text fallback.
+    pub fn with_rich_text(mut self, rich_text: RichText) -> Self {
+        self.rich_text = Some(rich_text);
+        self
+    }
+
+    /// @mention the given user ID in the message.
+    pub fn with_mention(mut self, user_id: impl Into<String>) -> Self {
+        self.mentions.push(user_id.into());
+        self
+    }
+
+    /// Append a file or image attachment.
+    pub fn with_attachment(mut self, attachment: Attachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+}
+
+/// Chainable constructor for [`SendMessage`], from [`SendMessage::builder`].
+///
+/// Every part is optional; channels ignore what they cannot render and fall
+/// back to the `text`.
+///
+/// ```
+/// use zeroclaw::channels::traits::{Attachment, SendMessage};
+///
+/// let message = SendMessage::builder()
+///     .to("oc_team_chat")
+///     .text("Nightly report attached")
+///     .mention("ou_oncall")
+///     .attach(Attachment::File {
+///         filename: "report.csv".into(),
+///         bytes: b"day,errors\n2026-10-14,0\n".to_vec(),
+///         mime: "text/csv".into(),
+///     })
+///     .build();
+/// assert_eq!(message.recipient, "oc_team_chat");
+/// assert_eq!(message.mentions, ["ou_oncall"]);
+/// assert_eq!(message.attachments.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct SendMessageBuilder {
+    message: SendMessage,
+}
+
+impl Default for SendMessageBuilder {
+    fn default() -> Self {
+        Self {
+            message: SendMessage::new("", ""),
+        }
+    }
+}
+
+impl SendMessageBuilder {
+    /// Recipient: chat, channel, or user ID, depending on the platform.
+    pub fn to(mut self, recipient: impl Into<String>) -> Self {
+        self.message.recipient = recipient.into();
+        self
+    }
+
+    /// Plain-text body; also the fallback for cards and rich text.
+    pub fn text(mut self, content: impl Into<String>) -> Self {
+        self.message.content = content.into();
+        self
+    }
+
+    /// Markdown body, rendered natively where the channel supports it.
+    pub fn markdown(mut self, content: impl Into<String>) -> Self {
+        self.message.content = content.into();
+        self.message.markdown = true;
+        self
+    }
+
+    /// Subject line (e.g. email).
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.message.subject = Some(subject.into());
+        self
+    }
+
+    /// Interactive card payload.
+    pub fn card(mut self, card: serde_json::Value) -> Self {
+        self.message.card = Some(card);
+        self
+    }
+
+    /// Formatted body.
+    pub fn rich_text(mut self, rich_text: RichText) -> Self {
+        self.message.rich_text = Some(rich_text);
+        self
+    }
+
+    /// Append a file or image attachment.
+    pub fn attach(mut self, attachment: Attachment) -> Self {
+        self.message.attachments.push(attachment);
+        self
+    }
+
+    /// Image to fetch from `url` and send.
+    pub fn image_url(mut self, url: impl Into<String>) -> Self {
+        self.message.image_url = Some(url.into());
+        self
+    }
+
+    /// Reply to the given platform message ID.
+    pub fn reply_to(mut self, message_id: impl Into<String>) -> Self {
+        self.message.reply_to_message_id = Some(message_id.into());
+        self
+    }
+
+    /// Post in the given platform thread (e.g. Slack `thread_ts`).
+    pub fn thread(mut self, thread_ts: impl Into<String>) -> Self {
+        self.message.thread_ts = Some(thread_ts.into());
+        self
+    }
+
+    /// @mention the given user ID.
+    pub fn mention(mut self, user_id: impl Into<String>) -> Self {
+        self.message.mentions.push(user_id.into());
+        self
+    }
+
+    /// Deduplicate resends of this message under `key`.
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.message.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Finish the message.
+    pub fn build(self) -> SendMessage {
+        self.message
+    }
+}
+
+/// Delivery receipt returned by [`Channel::send`].
+///
+/// Platforms that do not report an identifier for outbound messages return
+/// `SendReceipt::default()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SendReceipt {
+    /// Platform-assigned ID of the sent message, usable for edits/reactions.
+    pub message_id: Option<String>,
+    /// Conversation the message was delivered to, when reported.
+    pub chat_id: Option<String>,
+}
+
+impl SendReceipt {
+    /// Create a receipt carrying a platform message ID and conversation ID.
+    pub fn new(message_id: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        Self {
+            message_id: Some(message_id.into()),
+            chat_id: Some(chat_id.into()),
+        }
+    }
+}
+
+/// What a running channel did with a reloaded config (see
+/// [`Channel::reload_config`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigReload {
+    /// Nothing the channel can hot-swap changed, or it does not support reload.
+    Unchanged,
+    /// Access lists or rate limits were updated in place.
+    Applied,
+    /// Connection settings changed; the listener is reconnecting.
+    Reconnecting,
+}
+
+/// Optional features a channel supports, from [`Channel::capabilities`].
+///
+/// Lets callers pick a fallback (e.g. plain text instead of a card) before
+/// calling a method that would fail with [`ChannelError::NotSupported`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChannelCapabilities {
+    /// [`Channel::edit_message`] replaces sent messages.
+    pub supports_edit: bool,
+    /// [`Channel::add_reaction`] and [`Channel::remove_reaction`] work.
+    pub supports_reactions: bool,
+    /// [`SendMessage::card`] is rendered as an interactive card.
+    pub supports_cards: bool,
+    /// [`SendMessage::rich_text`] is rendered with its formatting.
+    pub supports_rich_text: bool,
+    /// [`Channel::start_typing`] shows an indicator.
+    pub supports_typing: bool,
+    /// Replies can be posted in a thread (`thread_ts`/`reply_to_message_id`).
+    pub supports_threads: bool,
+    /// Longest text sent as one platform message; longer text is split.
+    /// `None` when the channel has no known limit.
+    pub max_text_len: Option<usize>,
 }
 
 /// Core channel trait — implement for any messaging platform
+///
+/// Stays on `#[async_trait]`: the runtime holds every channel as
+/// `Arc<dyn Channel>`, and native `async fn` in traits is not dyn-compatible.
+/// The per-call future box costs ~100 ns against network sends
+/// measured in milliseconds (see `channel_send_*` in `benches/agent_benchmarks.rs`).
 #[async_trait]
 pub trait Channel: Send + Sync {
     /// Human-readable channel name
     fn name(&self) -> &str;
 
-    /// Send a message through this channel
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()>;
+    /// Send a message through this channel.
+    ///
+    /// Returns a receipt with the platform message ID when one is available.
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt>;
 
-    /// Start listening for incoming messages (long-running)
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()>;
+    /// Start listening for incoming messages (long-running).
+    ///
+    /// Received messages are forwarded to `tx`. When `shutdown` flips to
+    /// `true` the listener should stop accepting new input, close its
+    /// connection cleanly and return `Ok(())`; see [`wait_for_shutdown`].
+    /// Listeners that ignore it are dropped by the supervisor after a grace
+    /// period.
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()>;
 
     /// Check if channel is healthy
     async fn health_check(&self) -> bool {
         true
     }
 
+    /// Apply a reloaded config without dropping the connection where
+    /// possible. Channels look up their own section in `config`.
+    async fn reload_config(&self, _config: &crate::config::Config) -> ConfigReload {
+        ConfigReload::Unchanged
+    }
+
+    /// Language for system messages (errors, timeouts) sent on this channel.
+    fn locale(&self) -> super::locale::Locale {
+        super::locale::Locale::En
+    }
+
+    /// Optional features this channel supports. Defaults to none.
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities::default()
+    }
+
     /// Signal that the bot is processing a response (e.g. "typing" indicator).
     /// Implementations should repeat the indicator as needed for their platform.
-    async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+    async fn start_typing(&self, _recipient: &str) -> ChannelResult<()> {
         Ok(())
     }
 
     /// Stop any active typing indicator.
-    async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+    async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
         Ok(())
     }
 
@@ -90,7 +1035,7 @@ pub trait Channel: Send + Sync {
     }
 
     /// Send an initial draft message. Returns a platform-specific message ID for later edits.
-    async fn send_draft(&self, _message: &SendMessage) -> anyhow::Result<Option<String>> {
+    async fn send_draft(&self, _message: &SendMessage) -> ChannelResult<Option<String>> {
         Ok(None)
     }
 
@@ -104,7 +1049,7 @@ pub trait Channel: Send + Sync {
         _recipient: &str,
         _message_id: &str,
         _text: &str,
-    ) -> anyhow::Result<Option<String>> {
+    ) -> ChannelResult<Option<String>> {
         Ok(None)
     }
 
@@ -114,15 +1059,56 @@ pub trait Channel: Send + Sync {
         _recipient: &str,
         _message_id: &str,
         _text: &str,
-    ) -> anyhow::Result<()> {
+    ) -> ChannelResult<()> {
         Ok(())
     }
 
     /// Cancel and remove a previously sent draft message if the channel supports it.
-    async fn cancel_draft(&self, _recipient: &str, _message_id: &str) -> anyhow::Result<()> {
+    async fn cancel_draft(&self, _recipient: &str, _message_id: &str) -> ChannelResult<()> {
         Ok(())
     }
 
+    /// Replace the content of a message this bot previously sent.
+    ///
+    /// Channels without edit support return an error so callers can fall back
+    /// to sending a new message.
+    async fn edit_message(
+        &self,
+        _recipient: &str,
+        _message_id: &str,
+        _new_content: &SendMessage,
+    ) -> ChannelResult<()> {
+        Err(ChannelError::NotSupported("editing"))
+    }
+
+    /// Recall a message this bot previously sent, e.g. a "processing…"
+    /// placeholder once the real answer is out.
+    ///
+    /// Channels without deletion return [`ChannelError::NotSupported`].
+    async fn delete_message(&self, _message_id: &str) -> ChannelResult<()> {
+        Err(ChannelError::NotSupported("deletion"))
+    }
+
+    /// Download the bytes of an attachment received on this channel.
+    ///
+    /// `message_id` and `key` come from a [`ReceivedAttachment`].
+    async fn download_attachment(&self, _message_id: &str, _key: &str) -> ChannelResult<Vec<u8>> {
+        Err(ChannelError::NotSupported("attachment download"))
+    }
+
+    /// Fetch up to `limit` of the most recent messages in `chat_id`, oldest
+    /// first.
+    ///
+    /// `chat_id` is the same identifier inbound messages carry as
+    /// `reply_target`.
+    async fn fetch_history(
+        &self,
+        _chat_id: &str,
+        _limit: usize,
+    ) -> ChannelResult<Vec<ChannelMessage>> {
+        Err(ChannelError::NotSupported("history"))
+    }
+
     /// Send an interactive approval prompt, if supported by the channel.
     ///
     /// Default behavior sends a plain-text fallback with slash-command actions.
@@ -133,7 +1119,7 @@ pub trait Channel: Send + Sync {
         tool_name: &str,
         arguments: &serde_json::Value,
         thread_ts: Option<String>,
-    ) -> anyhow::Result<()> {
+    ) -> ChannelResult<()> {
         let raw_args = arguments.to_string();
         let args_preview = if raw_args.len() > 220 {
             let end = crate::util::floor_utf8_char_boundary(&raw_args, 220);
@@ -145,31 +1131,94 @@ pub trait Channel: Send + Sync {
             "Approval required for tool `{tool_name}`.\nRequest ID: `{request_id}`\nArgs: `{args_preview}`\nApprove: `/approve-allow {request_id}`\nDeny: `/approve-deny {request_id}`"
         );
         self.send(&SendMessage::new(message, recipient).in_thread(thread_ts))
-            .await
+            .await?;
+        Ok(())
     }
 
-    /// Add a reaction (emoji) to a message.
+    /// Add an emoji reaction to `message_id` and return the reaction's ID,
+    /// which [`Self::remove_reaction`] takes to undo it.
     ///
-    /// `channel_id` is the platform channel/conversation identifier (e.g. Discord channel ID).
-    /// `message_id` is the platform-scoped message identifier (e.g. `discord_<snowflake>`).
-    /// `emoji` is the Unicode emoji to react with (e.g. "👀", "✅").
-    async fn add_reaction(
-        &self,
-        _channel_id: &str,
-        _message_id: &str,
-        _emoji: &str,
-    ) -> anyhow::Result<()> {
-        Ok(())
+    /// `emoji` is a Unicode emoji (e.g. "👀", "✅"); channels with their own
+    /// emoji keys also accept those (e.g. Feishu `DONE`). Channels without
+    /// reactions return [`ChannelError::NotSupported`].
+    async fn add_reaction(&self, _message_id: &str, _emoji: &str) -> ChannelResult<String> {
+        Err(ChannelError::NotSupported("reactions"))
     }
 
-    /// Remove a reaction (emoji) from a message previously added by this bot.
-    async fn remove_reaction(
-        &self,
-        _channel_id: &str,
-        _message_id: &str,
-        _emoji: &str,
-    ) -> anyhow::Result<()> {
-        Ok(())
+    /// Remove a reaction previously returned by [`Self::add_reaction`].
+    async fn remove_reaction(&self, _message_id: &str, _reaction_id: &str) -> ChannelResult<()> {
+        Err(ChannelError::NotSupported("reactions"))
+    }
+}
+
+// Fails to compile if `Channel` stops being dyn-compatible.
+const _: fn() = || {
+    let _: Box<dyn Channel>;
+};
+
+/// How often [`TypingGuard`] re-issues [`Channel::start_typing`]. Kept below the
+/// ~5s lifetime of Telegram and Feishu typing indicators.
+pub const TYPING_REFRESH_INTERVAL: Duration = Duration::from_secs(4);
+
+/// Keeps a channel's typing indicator alive for the lifetime of the guard.
+///
+/// A background task calls [`Channel::start_typing`] right away and then every
+/// [`TYPING_REFRESH_INTERVAL`], and calls [`Channel::stop_typing`] once the guard
+/// is dropped. Dropping does not wait for the indicator to clear; use
+/// [`TypingGuard::stop`] when ordering matters.
+#[must_use = "the typing indicator stops as soon as the guard is dropped"]
+pub struct TypingGuard {
+    cancellation: CancellationToken,
+    task: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl TypingGuard {
+    /// Spawn the refresh task. Takes an owned handle because the task outlives
+    /// the caller's borrow.
+    pub fn start(channel: Arc<dyn Channel>, recipient: impl Into<String>) -> Self {
+        let recipient = recipient.into();
+        let cancellation = CancellationToken::new();
+        let stop_signal = cancellation.clone();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TYPING_REFRESH_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    () = stop_signal.cancelled() => break,
+                    _ = interval.tick() => {
+                        if let Err(e) = channel.start_typing(&recipient).await {
+                            tracing::debug!("Failed to start typing on {}: {e}", channel.name());
+                        }
+                    }
+                }
+            }
+
+            if let Err(e) = channel.stop_typing(&recipient).await {
+                tracing::debug!("Failed to stop typing on {}: {e}", channel.name());
+            }
+        });
+
+        Self {
+            cancellation,
+            task: Some(task),
+        }
+    }
+
+    /// Stop refreshing and wait until [`Channel::stop_typing`] has returned.
+    pub async fn stop(mut self) {
+        self.cancellation.cancel();
+        if let Some(task) = self.task.take() {
+            if let Err(error) = task.await {
+                tracing::error!("Typing indicator task crashed: {error}");
+            }
+        }
+    }
+}
+
+impl Drop for TypingGuard {
+    fn drop(&mut self) {
+        self.cancellation.cancel();
     }
 }
 
@@ -185,28 +1234,112 @@ mod tests {
             "dummy"
         }
 
-        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
-            Ok(())
+        async fn send(&self, _message: &SendMessage) -> ChannelResult<SendReceipt> {
+            Ok(SendReceipt::default())
         }
 
         async fn listen(
             &self,
-            tx: tokio::sync::mpsc::Sender<ChannelMessage>,
-        ) -> anyhow::Result<()> {
-            tx.send(ChannelMessage {
-                id: "1".into(),
-                sender: "tester".into(),
-                reply_target: "tester".into(),
-                content: "hello".into(),
-                channel: "dummy".into(),
-                timestamp: 123,
-                thread_ts: None,
-            })
+            tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+            _shutdown: tokio::sync::watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
+            tx.send(
+                ChannelMessage {
+                    id: "1".into(),
+                    sender: "tester".into(),
+                    reply_target: "tester".into(),
+                    content: "hello".into(),
+                    channel: "dummy".into(),
+                    timestamp: 123,
+                    thread_ts: None,
+                    reply_to_message_id: None,
+                    is_from_self: false,
+                    mentions: Vec::new(),
+                    attachments: Vec::new(),
+                    chat_type: ChatType::Group,
+                    triggered_by_mention: false,
+                    created_at: None,
+                    received_at: chrono::Utc::now(),
+                }
+                .into(),
+            )
             .await
-            .map_err(|e| anyhow::anyhow!(e.to_string()))
+            .map_err(|e| anyhow::anyhow!(e.to_string()).into())
         }
     }
 
+    #[derive(Default)]
+    struct TypingCounter {
+        starts: std::sync::atomic::AtomicUsize,
+        stops: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Channel for TypingCounter {
+        fn name(&self) -> &str {
+            "typing"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> ChannelResult<SendReceipt> {
+            Ok(SendReceipt::default())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+            _shutdown: tokio::sync::watch::Receiver<bool>,
+        ) -> ChannelResult<()> {
+            Ok(())
+        }
+
+        async fn start_typing(&self, _recipient: &str) -> ChannelResult<()> {
+            self.starts
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
+            self.stops.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn typing_guard_starts_immediately_and_stops_once() {
+        use std::sync::atomic::Ordering;
+
+        let channel = Arc::new(TypingCounter::default());
+        let guard = TypingGuard::start(channel.clone(), "alice");
+
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while channel.starts.load(Ordering::SeqCst) == 0 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("start_typing should run without waiting for the first refresh");
+        guard.stop().await;
+
+        assert_eq!(channel.starts.load(Ordering::SeqCst), 1);
+        assert_eq!(channel.stops.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn typing_guard_stops_typing_on_drop() {
+        use std::sync::atomic::Ordering;
+
+        let channel = Arc::new(TypingCounter::default());
+        drop(TypingGuard::start(channel.clone(), "alice"));
+
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while channel.stops.load(Ordering::SeqCst) == 0 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("stop_typing should run after the guard is dropped");
+    }
+
     #[test]
     fn channel_message_clone_preserves_fields() {
         let message = ChannelMessage {
@@ -217,6 +1350,14 @@ mod tests {
             channel: "dummy".into(),
             timestamp: 999,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         let cloned = message.clone();
@@ -228,6 +1369,140 @@ mod tests {
         assert_eq!(cloned.timestamp, 999);
     }
 
+    fn text_message(content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "1".into(),
+            sender: "alice".into(),
+            reply_target: "alice".into(),
+            content: content.into(),
+            channel: "dummy".into(),
+            timestamp: 0,
+            thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn channel_event_exposes_only_messages() {
+        let event: ChannelEvent = text_message("hi").into();
+        assert_eq!(event.as_message().map(|m| m.content.as_str()), Some("hi"));
+        assert_eq!(event.into_message().map(|m| m.content), Some("hi".into()));
+
+        let connected = ChannelEvent::Connected {
+            channel: "dummy".into(),
+        };
+        assert!(connected.as_message().is_none());
+        assert!(connected.into_message().is_none());
+    }
+
+    #[test]
+    fn send_message_builder_sets_every_part() {
+        let message = SendMessage::builder()
+            .to("oc_chat")
+            .markdown("**done**")
+            .subject("Deploy")
+            .card(serde_json::json!({ "elements": [] }))
+            .rich_text(RichText::new().text("done"))
+            .reply_to("om_1")
+            .thread("t_1")
+            .mention("ou_a")
+            .mention("ou_b")
+            .build();
+
+        assert_eq!(message.recipient, "oc_chat");
+        assert_eq!(message.content, "**done**");
+        assert!(message.markdown);
+        assert_eq!(message.subject.as_deref(), Some("Deploy"));
+        assert!(message.card.is_some());
+        assert!(message.rich_text.is_some());
+        assert_eq!(message.reply_to_message_id.as_deref(), Some("om_1"));
+        assert_eq!(message.thread_ts.as_deref(), Some("t_1"));
+        assert_eq!(message.mentions, ["ou_a", "ou_b"]);
+        assert!(message.attachments.is_empty());
+
+        let plain = SendMessage::builder().to("bob").text("hi").build();
+        assert!(!plain.markdown);
+        assert!(plain.reply_to_message_id.is_none());
+    }
+
+    #[test]
+    fn parse_command_splits_quoted_args() {
+        let command = text_message(r#"/deploy prod "us-east-1""#)
+            .parse_command(&["/"])
+            .expect("command");
+        assert_eq!(
+            command,
+            Command {
+                name: "deploy".into(),
+                args: vec!["prod".into(), "us-east-1".into()],
+                rest: r#"prod "us-east-1""#.into(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_bot_command_strips_only_the_bot_mention() {
+        let mention = |user_id: &str, name: &str, key: &str| Mention {
+            user_id: user_id.into(),
+            name: name.into(),
+            key: key.into(),
+        };
+        let mut message = text_message("@_user_1 /status now");
+        message.mentions = vec![mention("ou_bot", "Zero Claw", "@_user_1")];
+        for content in [
+            "@_user_1 /status now",
+            "@Zero Claw /status now",
+            "<@ou_bot> /status now",
+        ] {
+            message.content = content.into();
+            let command = message.parse_bot_command("ou_bot", &["/"]).unwrap();
+            assert_eq!(command.name, "status", "{content}");
+            assert_eq!(command.args, vec!["now"], "{content}");
+        }
+
+        // Someone else's mention, or no bot ID, leaves the text alone.
+        message.content = "@_user_1 /status now".into();
+        message.mentions = vec![mention("ou_alice", "Alice", "@_user_1")];
+        assert_eq!(message.parse_bot_command("ou_bot", &["/"]), None);
+        assert_eq!(message.parse_bot_command("", &["/"]), None);
+        assert_eq!(message.parse_command(&["/"]), None);
+        message.content = "<@U999> /status".into();
+        assert_eq!(message.parse_bot_command("U123", &["/"]), None);
+    }
+
+    #[test]
+    fn parse_command_drops_telegram_bot_suffix() {
+        let command = text_message("/status@zeroclaw_bot now")
+            .parse_command(&["/"])
+            .unwrap();
+        assert_eq!(command.name, "status");
+        assert_eq!(command.args, vec!["now"]);
+    }
+
+    #[test]
+    fn parse_command_handles_quotes_escapes_and_empty_args() {
+        let command = text_message(r#"!say 'it''s' "a \"b\"" c\ d """#)
+            .parse_command(&["/", "!"])
+            .unwrap();
+        assert_eq!(command.name, "say");
+        assert_eq!(command.args, vec!["its", r#"a "b""#, "c d", ""]);
+    }
+
+    #[test]
+    fn parse_command_rejects_plain_text_and_bare_prefix() {
+        assert_eq!(text_message("deploy prod").parse_command(&["/"]), None);
+        assert_eq!(text_message("/ deploy").parse_command(&["/"]), None);
+        assert_eq!(text_message("@bot hello").parse_command(&["/"]), None);
+        assert_eq!(text_message("/deploy").parse_command(&[]), None);
+    }
+
     #[tokio::test]
     async fn default_trait_methods_return_success() {
         let channel = DummyChannel;
@@ -242,17 +1517,17 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn default_reaction_methods_return_success() {
+    async fn default_reaction_methods_are_unsupported() {
         let channel = DummyChannel;
 
-        assert!(channel
-            .add_reaction("chan_1", "msg_1", "\u{1F440}")
-            .await
-            .is_ok());
-        assert!(channel
-            .remove_reaction("chan_1", "msg_1", "\u{1F440}")
-            .await
-            .is_ok());
+        assert!(matches!(
+            channel.add_reaction("msg_1", "\u{1F440}").await,
+            Err(ChannelError::NotSupported("reactions"))
+        ));
+        assert!(matches!(
+            channel.remove_reaction("msg_1", "reaction_1").await,
+            Err(ChannelError::NotSupported("reactions"))
+        ));
     }
 
     #[tokio::test]
@@ -273,14 +1548,110 @@ mod tests {
         assert!(channel.cancel_draft("bob", "msg_1").await.is_ok());
     }
 
+    #[tokio::test]
+    async fn default_edit_message_is_unsupported() {
+        let channel = DummyChannel;
+
+        let err = channel
+            .edit_message("bob", "msg_1", &SendMessage::new("edited", "bob"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not supported"));
+        assert!(matches!(
+            channel.delete_message("msg_1").await,
+            Err(ChannelError::NotSupported("deletion"))
+        ));
+    }
+
+    #[test]
+    fn channel_error_from_anyhow_preserves_typed_variants() {
+        let typed: anyhow::Error = ChannelError::RateLimited {
+            retry_after: Some(Duration::from_secs(3)),
+        }
+        .into();
+        let err = ChannelError::from(typed.context("while sending"));
+        assert!(matches!(
+            err,
+            ChannelError::RateLimited {
+                retry_after: Some(d)
+            } if d == Duration::from_secs(3)
+        ));
+        assert!(err.is_retryable());
+
+        assert!(ChannelError::Unavailable("status=503".into()).is_retryable());
+
+        let err = ChannelError::from(anyhow::anyhow!("boom"));
+        assert!(matches!(err, ChannelError::Other(_)));
+        assert!(!err.is_retryable());
+        assert_eq!(err.to_string(), "boom");
+    }
+
+    #[tokio::test]
+    async fn channel_error_from_anyhow_classifies_transport_failures() {
+        // Nothing listens on the discard port, so the connect fails.
+        let refused = reqwest::Client::new()
+            .get("http://127.0.0.1:9/")
+            .send()
+            .await
+            .unwrap_err();
+        assert!(refused.is_connect());
+        let err = ChannelError::from(anyhow::Error::new(refused));
+        assert!(matches!(err, ChannelError::Network(_)), "{err:?}");
+        let ChannelError::Network(refused) = err else {
+            unreachable!()
+        };
+
+        // Context is kept, and the failure is still a retryable network one.
+        let err = ChannelError::from(anyhow::Error::new(refused).context("while sending"));
+        assert!(matches!(err, ChannelError::Other(_)), "{err:?}");
+        assert_eq!(err.to_string(), "while sending");
+        assert!(err.is_network());
+        assert!(err.is_retryable());
+
+        let bad_url = reqwest::Client::new()
+            .get("http://[::1")
+            .send()
+            .await
+            .unwrap_err();
+        let err = ChannelError::from(anyhow::Error::new(bad_url));
+        assert!(matches!(err, ChannelError::Other(_)), "{err:?}");
+        assert!(!err.is_network());
+    }
+
+    #[tokio::test]
+    async fn default_download_attachment_is_unsupported() {
+        let channel = DummyChannel;
+
+        let err = channel
+            .download_attachment("msg_1", "file_1")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not supported"));
+    }
+
+    #[tokio::test]
+    async fn default_fetch_history_is_unsupported() {
+        let channel = DummyChannel;
+
+        let err = channel.fetch_history("chat_1", 10).await.unwrap_err();
+        assert!(matches!(err, ChannelError::NotSupported("history")));
+    }
+
     #[tokio::test]
     async fn listen_sends_message_to_channel() {
         let channel = DummyChannel;
         let (tx, mut rx) = tokio::sync::mpsc::channel(1);
 
-        channel.listen(tx).await.unwrap();
+        channel
+            .listen(tx, tokio::sync::watch::channel(false).1)
+            .await
+            .unwrap();
 
-        let received = rx.recv().await.expect("message should be sent");
+        let received = rx
+            .recv()
+            .await
+            .and_then(ChannelEvent::into_message)
+            .expect("message should be sent");
         assert_eq!(received.sender, "tester");
         assert_eq!(received.content, "hello");
         assert_eq!(received.channel, "dummy");
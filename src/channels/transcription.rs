@@ -122,6 +122,37 @@ pub async fn transcribe_audio(
     Ok(text)
 }
 
+/// Speech-to-text extension point for received voice messages.
+///
+/// Channels that accept a transcriber (e.g. `LarkChannel::with_transcriber`)
+/// call it for inbound audio and forward the text to the agent. Implement it
+/// for any provider; [`WhisperTranscriber`] wraps [`transcribe_audio`].
+#[async_trait::async_trait]
+pub trait Transcriber: Send + Sync {
+    /// Turn `audio` into text. `file_name` carries the format, e.g.
+    /// `voice.opus` for Feishu/Lark voice messages.
+    async fn transcribe(&self, audio: Vec<u8>, file_name: &str) -> Result<String>;
+}
+
+/// [`Transcriber`] backed by a Whisper-compatible API (see [`transcribe_audio`]).
+#[derive(Debug, Clone)]
+pub struct WhisperTranscriber {
+    config: TranscriptionConfig,
+}
+
+impl WhisperTranscriber {
+    pub fn new(config: TranscriptionConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl Transcriber for WhisperTranscriber {
+    async fn transcribe(&self, audio: Vec<u8>, file_name: &str) -> Result<String> {
+        transcribe_audio(audio, file_name, &self.config).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -163,6 +165,14 @@ impl WatiChannel {
             channel: "wati".to_string(),
             timestamp,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         });
 
         messages
@@ -175,7 +185,7 @@ impl Channel for WatiChannel {
         "wati"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let target = self.build_target(&message.recipient);
 
         let body = serde_json::json!({
@@ -199,13 +209,17 @@ impl Channel for WatiChannel {
             let error_body = resp.text().await.unwrap_or_default();
             let sanitized = crate::providers::sanitize_api_error(&error_body);
             tracing::error!("WATI send failed: {status} — {sanitized}");
-            anyhow::bail!("WATI API error: {status}");
+            return Err(anyhow::anyhow!("WATI API error: {status}").into());
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        _tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         // WATI uses webhooks (push-based), not polling.
         // Messages are received via the gateway's /wati endpoint.
         tracing::info!(
@@ -231,12 +245,12 @@ impl Channel for WatiChannel {
             .unwrap_or(false)
     }
 
-    async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+    async fn start_typing(&self, _recipient: &str) -> ChannelResult<()> {
         // WATI API does not support typing indicators
         Ok(())
     }
 
-    async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+    async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
         // WATI API does not support typing indicators
         Ok(())
     }
@@ -0,0 +1,267 @@
+//! Shared HTTP listener for channels that receive events by webhook.
+//!
+//! Without it every webhook-mode channel binds its own port, which is awkward
+//! behind a single ingress. A [`WebhookServer`] owns one listener and routes
+//! each request by exact path to the handler a channel registered with
+//! [`WebhookServer::register`]. The listener is bound on the first
+//! registration and stopped once the last [`WebhookRoute`] is dropped, so
+//! channels can come and go while the server is running.
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::MethodRouter;
+use axum::Router;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tower::Service;
+
+/// One listener shared by every webhook-mode channel.
+#[derive(Clone)]
+pub struct WebhookServer {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    addr: SocketAddr,
+    /// Prefix prepended to every registered path (`""` or `/something`).
+    base_path: String,
+    state: Mutex<ServerState>,
+}
+
+#[derive(Default)]
+struct ServerState {
+    routes: HashMap<String, MethodRouter>,
+    /// Set while the listener task is running.
+    stop: Option<CancellationToken>,
+}
+
+impl std::fmt::Debug for WebhookServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.inner.state.lock();
+        f.debug_struct("WebhookServer")
+            .field("addr", &self.inner.addr)
+            .field("base_path", &self.inner.base_path)
+            .field("routes", &state.routes.keys().collect::<Vec<_>>())
+            .field("running", &state.stop.is_some())
+            .finish()
+    }
+}
+
+impl WebhookServer {
+    /// Create a server that will listen on `addr` once a route is registered.
+    pub fn new(addr: SocketAddr, base_path: &str) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                addr,
+                base_path: normalize_base_path(base_path),
+                state: Mutex::new(ServerState::default()),
+            }),
+        }
+    }
+
+    /// Build from `[channels_config.webhook_server]`; listens on all interfaces.
+    pub fn from_config(config: &crate::config::schema::WebhookServerConfig) -> Self {
+        Self::new(
+            SocketAddr::from(([0, 0, 0, 0], config.port)),
+            &config.base_path,
+        )
+    }
+
+    /// Address the listener binds to.
+    pub fn addr(&self) -> SocketAddr {
+        self.inner.addr
+    }
+
+    /// Full request path a handler registered at `path` is served on.
+    pub fn full_path(&self, path: &str) -> String {
+        format!("{}{}", self.inner.base_path, normalize_route_path(path))
+    }
+
+    /// Serve `handler` at `path` (below the configured base path) until the
+    /// returned [`WebhookRoute`] is dropped.
+    ///
+    /// Binds the listener if this is the first live route. Fails when the
+    /// path is already taken or the port cannot be bound.
+    pub fn register(&self, path: &str, handler: MethodRouter) -> anyhow::Result<WebhookRoute> {
+        let full_path = self.full_path(path);
+        let mut state = self.inner.state.lock();
+        if state.routes.contains_key(&full_path) {
+            anyhow::bail!("webhook path {full_path} is already registered");
+        }
+        if state.stop.is_none() {
+            state.stop = Some(self.spawn_listener()?);
+        }
+        state.routes.insert(full_path.clone(), handler);
+        tracing::info!("Webhook server routing {full_path} on {}", self.inner.addr);
+        Ok(WebhookRoute {
+            server: Arc::clone(&self.inner),
+            path: full_path,
+        })
+    }
+
+    fn spawn_listener(&self) -> anyhow::Result<CancellationToken> {
+        let listener = std::net::TcpListener::bind(self.inner.addr)?;
+        listener.set_nonblocking(true)?;
+        let listener = tokio::net::TcpListener::from_std(listener)?;
+        let stop = CancellationToken::new();
+        let app = Router::new()
+            .fallback(dispatch)
+            .with_state(Arc::clone(&self.inner));
+        let addr = self.inner.addr;
+        let cancelled = stop.clone();
+        tokio::spawn(async move {
+            tracing::info!("Webhook server listening on {addr}");
+            if let Err(err) = axum::serve(listener, app)
+                .with_graceful_shutdown(async move { cancelled.cancelled().await })
+                .await
+            {
+                tracing::warn!("Webhook server on {addr} failed: {err}");
+            }
+            tracing::info!("Webhook server on {addr} stopped");
+        });
+        Ok(stop)
+    }
+}
+
+/// Registration handle; dropping it removes the route, and stops the
+/// listener when no routes remain.
+#[must_use = "the route is removed as soon as the handle is dropped"]
+pub struct WebhookRoute {
+    server: Arc<Inner>,
+    path: String,
+}
+
+impl std::fmt::Debug for WebhookRoute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookRoute")
+            .field("addr", &self.server.addr)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl WebhookRoute {
+    /// Full request path this route is served on.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Drop for WebhookRoute {
+    fn drop(&mut self) {
+        let mut state = self.server.state.lock();
+        state.routes.remove(&self.path);
+        if state.routes.is_empty() {
+            if let Some(stop) = state.stop.take() {
+                stop.cancel();
+            }
+        }
+    }
+}
+
+async fn dispatch(State(inner): State<Arc<Inner>>, request: Request<Body>) -> Response {
+    let handler = inner.state.lock().routes.get(request.uri().path()).cloned();
+    let Some(mut handler) = handler else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    match handler.call(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    }
+}
+
+fn normalize_base_path(base_path: &str) -> String {
+    let trimmed = base_path.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{trimmed}")
+    }
+}
+
+fn normalize_route_path(path: &str) -> String {
+    format!("/{}", path.trim().trim_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::post;
+
+    fn local_server(base_path: &str) -> WebhookServer {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .map(|addr| addr.port())
+            .expect("ephemeral port");
+        WebhookServer::new(SocketAddr::from(([127, 0, 0, 1], port)), base_path)
+    }
+
+    async fn post_to(server: &WebhookServer, path: &str) -> reqwest::Result<reqwest::Response> {
+        reqwest::Client::new()
+            .post(format!("http://{}{path}", server.addr()))
+            .body("{}")
+            .send()
+            .await
+    }
+
+    #[test]
+    fn paths_are_normalized_under_base_path() {
+        assert_eq!(
+            local_server("").full_path("feishu/events"),
+            "/feishu/events"
+        );
+        assert_eq!(local_server("/hooks/").full_path("/lark/"), "/hooks/lark");
+        assert_eq!(
+            local_server(" hooks ").full_path("/telegram"),
+            "/hooks/telegram"
+        );
+    }
+
+    #[tokio::test]
+    async fn routes_share_one_listener() {
+        let server = local_server("/hooks");
+        let _feishu = server
+            .register("/feishu/events", post(|| async { "feishu" }))
+            .unwrap();
+        let _lark = server
+            .register("/lark/events", post(|| async { "lark" }))
+            .unwrap();
+
+        let resp = post_to(&server, "/hooks/feishu/events").await.unwrap();
+        assert_eq!(resp.text().await.unwrap(), "feishu");
+        let resp = post_to(&server, "/hooks/lark/events").await.unwrap();
+        assert_eq!(resp.text().await.unwrap(), "lark");
+        let resp = post_to(&server, "/lark/events").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn duplicate_path_is_rejected() {
+        let server = local_server("");
+        let _first = server.register("/feishu", post(|| async {})).unwrap();
+        let err = server.register("feishu/", post(|| async {})).unwrap_err();
+        assert!(err.to_string().contains("already registered"));
+    }
+
+    #[tokio::test]
+    async fn dropping_route_unregisters_it() {
+        let server = local_server("");
+        let _keep = server.register("/keep", post(|| async {})).unwrap();
+        let route = server.register("/gone", post(|| async {})).unwrap();
+        assert_eq!(route.path(), "/gone");
+        drop(route);
+
+        let resp = post_to(&server, "/gone").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let resp = post_to(&server, "/keep").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // The path can be claimed again once released.
+        let _again = server.register("/gone", post(|| async {})).unwrap();
+    }
+}
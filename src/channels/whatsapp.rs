@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -142,6 +144,14 @@ impl WhatsAppChannel {
                         channel: "whatsapp".to_string(),
                         timestamp,
                         thread_ts: None,
+                        reply_to_message_id: None,
+                        is_from_self: false,
+                        mentions: Vec::new(),
+                        attachments: Vec::new(),
+                        chat_type: ChatType::Group,
+                        triggered_by_mention: false,
+                        created_at: None,
+                        received_at: chrono::Utc::now(),
                     });
                 }
             }
@@ -157,7 +167,7 @@ impl Channel for WhatsAppChannel {
         "whatsapp"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         // WhatsApp Cloud API: POST to /v18.0/{phone_number_id}/messages
         let url = format!(
             "https://graph.facebook.com/v18.0/{}/messages",
@@ -197,13 +207,17 @@ impl Channel for WhatsAppChannel {
             let error_body = resp.text().await.unwrap_or_default();
             let sanitized = crate::providers::sanitize_api_error(&error_body);
             tracing::error!("WhatsApp send failed: {status} — {sanitized}");
-            anyhow::bail!("WhatsApp API error: {status}");
+            return Err(anyhow::anyhow!("WhatsApp API error: {status}").into());
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+    async fn listen(
+        &self,
+        _tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         // WhatsApp uses webhooks (push-based), not polling.
         // Messages are received via the gateway's /whatsapp endpoint.
         // This method keeps the channel "alive" but doesn't actively poll.
@@ -26,7 +26,9 @@
 //! This channel is automatically selected when `session_path` is set in the config.
 //! The Cloud API channel is used when `phone_number_id` is set.
 
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 use super::whatsapp_storage::RusqliteStore;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
@@ -184,7 +186,7 @@ pub struct WhatsAppWebChannel {
     /// Client handle for sending messages and typing indicators
     client: Arc<Mutex<Option<Arc<wa_rs::Client>>>>,
     /// Message sender channel
-    tx: Arc<Mutex<Option<tokio::sync::mpsc::Sender<ChannelMessage>>>>,
+    tx: Arc<Mutex<Option<tokio::sync::mpsc::Sender<ChannelEvent>>>>,
     /// Voice transcription configuration (Groq Whisper)
     transcription: Option<crate::config::TranscriptionConfig>,
 }
@@ -434,7 +436,7 @@ impl Channel for WhatsAppWebChannel {
         "whatsapp"
     }
 
-    async fn send(&self, message: &SendMessage) -> Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         let client = self.client.lock().clone();
         let Some(client) = client else {
             anyhow::bail!("WhatsApp Web client not connected. Initialize the bot first.");
@@ -448,7 +450,7 @@ impl Channel for WhatsAppWebChannel {
                     "WhatsApp Web: recipient {} not in allowed list",
                     message.recipient
                 );
-                return Ok(());
+                return Ok(SendReceipt::default());
             }
         }
 
@@ -506,10 +508,14 @@ impl Channel for WhatsAppWebChannel {
             );
         }
 
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         // Store the sender channel for incoming messages
         *self.tx.lock() = Some(tx.clone());
 
@@ -679,7 +685,15 @@ impl Channel for WhatsAppWebChannel {
                                         content,
                                         timestamp: chrono::Utc::now().timestamp() as u64,
                                         thread_ts: None,
-                                    })
+                                        reply_to_message_id: None,
+                                        is_from_self: false,
+                                        mentions: Vec::new(),
+                                        attachments: Vec::new(),
+                                        chat_type: ChatType::Group,
+                                        triggered_by_mention: false,
+                                        created_at: None,
+                                        received_at: chrono::Utc::now(),
+                                    }.into())
                                     .await
                                 {
                                     tracing::error!("Failed to send message to channel: {}", e);
@@ -779,7 +793,7 @@ impl Channel for WhatsAppWebChannel {
         bot_handle_guard.is_some()
     }
 
-    async fn start_typing(&self, recipient: &str) -> Result<()> {
+    async fn start_typing(&self, recipient: &str) -> ChannelResult<()> {
         let client = self.client.lock().clone();
         let Some(client) = client else {
             anyhow::bail!("WhatsApp Web client not connected. Initialize the bot first.");
@@ -807,7 +821,7 @@ impl Channel for WhatsAppWebChannel {
         Ok(())
     }
 
-    async fn stop_typing(&self, recipient: &str) -> Result<()> {
+    async fn stop_typing(&self, recipient: &str) -> ChannelResult<()> {
         let client = self.client.lock().clone();
         let Some(client) = client else {
             anyhow::bail!("WhatsApp Web client not connected. Initialize the bot first.");
@@ -861,14 +875,18 @@ impl Channel for WhatsAppWebChannel {
         "whatsapp"
     }
 
-    async fn send(&self, _message: &SendMessage) -> Result<()> {
+    async fn send(&self, _message: &SendMessage) -> ChannelResult<SendReceipt> {
         anyhow::bail!(
             "WhatsApp Web channel requires the 'whatsapp-web' feature. \
             Enable with: cargo build --features whatsapp-web"
         );
     }
 
-    async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+    async fn listen(
+        &self,
+        _tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
         anyhow::bail!(
             "WhatsApp Web channel requires the 'whatsapp-web' feature. \
             Enable with: cargo build --features whatsapp-web"
@@ -879,14 +897,14 @@ impl Channel for WhatsAppWebChannel {
         false
     }
 
-    async fn start_typing(&self, _recipient: &str) -> Result<()> {
+    async fn start_typing(&self, _recipient: &str) -> ChannelResult<()> {
         anyhow::bail!(
             "WhatsApp Web channel requires the 'whatsapp-web' feature. \
             Enable with: cargo build --features whatsapp-web"
         );
     }
 
-    async fn stop_typing(&self, _recipient: &str) -> Result<()> {
+    async fn stop_typing(&self, _recipient: &str) -> ChannelResult<()> {
         anyhow::bail!(
             "WhatsApp Web channel requires the 'whatsapp-web' feature. \
             Enable with: cargo build --features whatsapp-web"
@@ -4,19 +4,20 @@ pub mod traits;
 #[allow(unused_imports)]
 pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
-    build_runtime_proxy_client_with_timeouts, default_model_fallback_for_provider,
+    build_runtime_proxy_client_with_timeouts, default_model_fallback_for_provider, load_from_path,
     resolve_default_model_id, runtime_proxy_config, set_runtime_proxy_config,
     AckReactionChannelsConfig, AckReactionChatType, AckReactionConfig, AckReactionRuleAction,
     AckReactionRuleConfig, AckReactionStrategy, AgentConfig, AgentLoadBalanceStrategy,
     AgentSessionBackend, AgentSessionConfig, AgentSessionStrategy, AgentTeamsConfig,
     AgentsIpcConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
-    BuiltinHooksConfig, ChannelsConfig, ClassificationRule, CommandContextRuleAction,
-    CommandContextRuleConfig, ComposioConfig, Config, CoordinationConfig, CostConfig, CronConfig,
-    DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EconomicConfig, EconomicTokenPricing,
-    EmbeddingRouteConfig, EstopConfig, FeishuConfig, GatewayConfig, GroupReplyConfig,
-    GroupReplyMode, HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig,
-    HttpRequestConfig, HttpRequestCredentialProfile, IMessageConfig, IdentityConfig, LarkConfig,
-    MatrixConfig, MemoryConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig,
+    BuiltinHooksConfig, CapabilityDowngradePolicy, ChannelsConfig, ClassificationRule,
+    CommandContextRuleAction, CommandContextRuleConfig, ComposioConfig, Config, ConfigError,
+    CoordinationConfig, CostConfig, CronConfig, DelegateAgentConfig, DiscordConfig,
+    DockerRuntimeConfig, EconomicConfig, EconomicTokenPricing, EmbeddingRouteConfig, EstopConfig,
+    FeishuConfig, GatewayConfig, GroupReplyConfig, GroupReplyMode, HardwareConfig,
+    HardwareTransport, HeartbeatConfig, HooksConfig, HttpRequestConfig,
+    HttpRequestCredentialProfile, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig,
+    MemoryConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig,
     NonCliNaturalLanguageApprovalMode, ObservabilityConfig, OtpChallengeDelivery, OtpConfig,
     OtpMethod, OutboundLeakGuardAction, OutboundLeakGuardConfig, PeripheralBoardConfig,
     PeripheralsConfig, PerplexityFilterConfig, PluginEntryConfig, PluginsConfig, ProgressMode,
@@ -25,9 +26,10 @@ pub use schema::{
     SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig,
     SecurityRoleConfig, SkillsConfig, SkillsPromptInjectionMode, SlackConfig, StorageConfig,
     StorageProviderConfig, StorageProviderSection, StreamMode, SubAgentsConfig,
-    SyscallAnomalyConfig, TelegramConfig, TranscriptionConfig, TunnelConfig, UrlAccessConfig,
-    WasmCapabilityEscalationMode, WasmConfig, WasmModuleHashPolicy, WasmRuntimeConfig,
-    WasmSecurityConfig, WebFetchConfig, WebSearchConfig, WebhookConfig, DEFAULT_MODEL_FALLBACK,
+    SyscallAnomalyConfig, TelegramConfig, TelegramReceiveMode, TranscriptionConfig, TunnelConfig,
+    UrlAccessConfig, WasmCapabilityEscalationMode, WasmConfig, WasmModuleHashPolicy,
+    WasmRuntimeConfig, WasmSecurityConfig, WebFetchConfig, WebSearchConfig, WebhookConfig,
+    DEFAULT_MODEL_FALLBACK,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: Option<&T>) -> (&'static str, bool) {
@@ -60,6 +62,10 @@ mod tests {
             group_reply: None,
             base_url: None,
             ack_enabled: true,
+            receive_mode: TelegramReceiveMode::Polling,
+            webhook_port: None,
+            webhook_url: None,
+            webhook_secret: None,
         };
 
         let discord = DiscordConfig {
@@ -69,6 +75,7 @@ mod tests {
             listen_to_bots: false,
             mention_only: false,
             group_reply: None,
+            intents: crate::config::schema::default_discord_intents(),
         };
 
         let lark = LarkConfig {
@@ -85,6 +92,28 @@ mod tests {
             draft_update_interval_ms: crate::config::schema::default_lark_draft_update_interval_ms(
             ),
             max_draft_edits: crate::config::schema::default_lark_max_draft_edits(),
+            max_reconnect_attempts: None,
+            rate_limit_per_second: None,
+            allowed_users_regex: false,
+            dedup_window_secs: crate::config::schema::default_lark_dedup_window_secs(),
+            heartbeat_interval_secs: crate::config::schema::default_lark_heartbeat_interval_secs(),
+            backpressure: crate::config::schema::BackpressurePolicy::Block,
+            proxy: None,
+            extra_headers: std::collections::HashMap::new(),
+            user_agent: None,
+            health_check_timeout_secs: 5,
+            health_check_cache_secs: 10,
+            retry: crate::config::schema::RetryPolicy::default(),
+            log_message_content: false,
+            ordered_sends: false,
+            base_url: None,
+            outbound_dedup_window_secs: None,
+            message_prefix: None,
+            message_suffix: None,
+            verify_chat_reachability: false,
+            debug_dump_events: false,
+            debug_dump_file: None,
+            max_event_age_secs: None,
         };
         let feishu = FeishuConfig {
             app_id: "app-id".into(),
@@ -98,6 +127,33 @@ mod tests {
             draft_update_interval_ms: crate::config::schema::default_lark_draft_update_interval_ms(
             ),
             max_draft_edits: crate::config::schema::default_lark_max_draft_edits(),
+            max_reconnect_attempts: None,
+            rate_limit_per_second: None,
+            app_secret_file: None,
+            verification_token_file: None,
+            denied_users: Vec::new(),
+            allowed_users_regex: false,
+            allowed_chats: vec!["*".into()],
+            dedup_window_secs: crate::config::schema::default_lark_dedup_window_secs(),
+            heartbeat_interval_secs: crate::config::schema::default_lark_heartbeat_interval_secs(),
+            backpressure: crate::config::schema::BackpressurePolicy::Block,
+            proxy: None,
+            extra_headers: std::collections::HashMap::new(),
+            user_agent: None,
+            health_check_timeout_secs: 5,
+            health_check_cache_secs: 10,
+            retry: crate::config::schema::RetryPolicy::default(),
+            log_message_content: false,
+            locale: None,
+            ordered_sends: false,
+            base_url: None,
+            outbound_dedup_window_secs: None,
+            message_prefix: None,
+            message_suffix: None,
+            verify_chat_reachability: false,
+            debug_dump_events: false,
+            debug_dump_file: None,
+            max_event_age_secs: None,
         };
 
         let nextcloud_talk = NextcloudTalkConfig {
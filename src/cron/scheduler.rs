@@ -538,6 +538,23 @@ pub(crate) async fn deliver_announcement(
                 anyhow::bail!("matrix delivery channel requires `channel-matrix` feature");
             }
         }
+        other if other.starts_with("feishu:") => {
+            #[cfg(feature = "channel-lark")]
+            {
+                let app = &channel["feishu:".len()..];
+                let feishu_cfg = config.channels_config.feishu_apps.get(app).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "feishu app not configured: [channels_config.feishu_apps.{app}] is missing"
+                    )
+                })?;
+                let channel = LarkChannel::from_feishu_config(feishu_cfg).with_name(channel);
+                channel.send(&SendMessage::new(output, target)).await?;
+            }
+            #[cfg(not(feature = "channel-lark"))]
+            {
+                anyhow::bail!("feishu delivery channel requires `channel-lark` feature");
+            }
+        }
         other => anyhow::bail!("unsupported delivery channel: {other}"),
     }
 
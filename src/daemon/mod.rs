@@ -106,6 +106,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         ));
     }
 
+    let (channels_shutdown_tx, channels_shutdown_rx) = tokio::sync::watch::channel(false);
     {
         if has_supervised_channels(&config) {
             let channels_cfg = config.clone();
@@ -115,7 +116,10 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 max_backoff,
                 move || {
                     let cfg = channels_cfg.clone();
-                    async move { Box::pin(crate::channels::start_channels(cfg)).await }
+                    let shutdown = channels_shutdown_rx.clone();
+                    async move {
+                        Box::pin(crate::channels::start_channels_with_shutdown(cfg, shutdown)).await
+                    }
                 },
             ));
         } else {
@@ -160,6 +164,8 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
 
     let signal = wait_for_shutdown_signal().await?;
     crate::health::mark_component_error("daemon", shutdown_reason(signal));
+    // Let channel listeners close their connections and drain in-flight messages.
+    let _ = channels_shutdown_tx.send(true);
     let aborted =
         shutdown_handles_with_grace(handles, Duration::from_secs(SHUTDOWN_GRACE_SECONDS)).await;
     if aborted > 0 {
@@ -693,6 +699,10 @@ mod tests {
             ack_enabled: true,
             group_reply: None,
             base_url: None,
+            receive_mode: crate::config::TelegramReceiveMode::Polling,
+            webhook_port: None,
+            webhook_url: None,
+            webhook_secret: None,
         });
         assert!(has_supervised_channels(&config));
     }
@@ -926,6 +936,10 @@ mod tests {
             ack_enabled: true,
             group_reply: None,
             base_url: None,
+            receive_mode: crate::config::TelegramReceiveMode::Polling,
+            webhook_port: None,
+            webhook_url: None,
+            webhook_secret: None,
         });
 
         let target = heartbeat_delivery_target(&config).unwrap();
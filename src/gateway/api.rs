@@ -178,10 +178,16 @@ pub async fn handle_api_config_put(
     let current_config = state.config.lock().clone();
     let new_config = hydrate_config_for_save(incoming, &current_config);
 
-    if let Err(e) = new_config.validate() {
+    // Same checks as loading, so a saved config always loads again.
+    if let Err(errors) = new_config.validate_all() {
+        let details = errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
         return (
             StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": format!("Invalid config: {e}")})),
+            Json(serde_json::json!({"error": format!("Invalid config: {details}")})),
         )
             .into_response();
     }
@@ -1064,6 +1070,7 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
 
     if let Some(telegram) = masked.channels_config.telegram.as_mut() {
         mask_required_secret(&mut telegram.bot_token);
+        mask_optional_secret(&mut telegram.webhook_secret);
     }
     if let Some(discord) = masked.channels_config.discord.as_mut() {
         mask_required_secret(&mut discord.bot_token);
@@ -1114,11 +1121,18 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
         mask_required_secret(&mut lark.app_secret);
         mask_optional_secret(&mut lark.encrypt_key);
         mask_optional_secret(&mut lark.verification_token);
+        mask_optional_secret(&mut lark.proxy);
     }
-    if let Some(feishu) = masked.channels_config.feishu.as_mut() {
+    for feishu in masked
+        .channels_config
+        .feishu
+        .iter_mut()
+        .chain(masked.channels_config.feishu_apps.values_mut())
+    {
         mask_required_secret(&mut feishu.app_secret);
         mask_optional_secret(&mut feishu.encrypt_key);
         mask_optional_secret(&mut feishu.verification_token);
+        mask_optional_secret(&mut feishu.proxy);
     }
     if let Some(dingtalk) = masked.channels_config.dingtalk.as_mut() {
         mask_required_secret(&mut dingtalk.client_secret);
@@ -1209,6 +1223,7 @@ fn restore_masked_sensitive_fields(
         current.channels_config.telegram.as_ref(),
     ) {
         restore_required_secret(&mut incoming_ch.bot_token, &current_ch.bot_token);
+        restore_optional_secret(&mut incoming_ch.webhook_secret, &current_ch.webhook_secret);
     }
     if let (Some(incoming_ch), Some(current_ch)) = (
         incoming.channels_config.discord.as_mut(),
@@ -1307,6 +1322,7 @@ fn restore_masked_sensitive_fields(
             &mut incoming_ch.verification_token,
             &current_ch.verification_token,
         );
+        restore_optional_secret(&mut incoming_ch.proxy, &current_ch.proxy);
     }
     if let (Some(incoming_ch), Some(current_ch)) = (
         incoming.channels_config.feishu.as_mut(),
@@ -1318,6 +1334,18 @@ fn restore_masked_sensitive_fields(
             &mut incoming_ch.verification_token,
             &current_ch.verification_token,
         );
+        restore_optional_secret(&mut incoming_ch.proxy, &current_ch.proxy);
+    }
+    for (name, incoming_ch) in &mut incoming.channels_config.feishu_apps {
+        if let Some(current_ch) = current.channels_config.feishu_apps.get(name) {
+            restore_required_secret(&mut incoming_ch.app_secret, &current_ch.app_secret);
+            restore_optional_secret(&mut incoming_ch.encrypt_key, &current_ch.encrypt_key);
+            restore_optional_secret(
+                &mut incoming_ch.verification_token,
+                &current_ch.verification_token,
+            );
+            restore_optional_secret(&mut incoming_ch.proxy, &current_ch.proxy);
+        }
     }
     if let (Some(incoming_ch), Some(current_ch)) = (
         incoming.channels_config.dingtalk.as_mut(),
@@ -1360,6 +1388,7 @@ fn hydrate_config_for_save(
     // These are runtime-computed fields skipped from TOML serialization.
     incoming.config_path = current.config_path.clone();
     incoming.workspace_dir = current.workspace_dir.clone();
+    incoming.channels_config.env_secret_refs = current.channels_config.env_secret_refs.clone();
     incoming
 }
 
@@ -1481,6 +1510,33 @@ mod tests {
             draft_update_interval_ms: crate::config::schema::default_lark_draft_update_interval_ms(
             ),
             max_draft_edits: crate::config::schema::default_lark_max_draft_edits(),
+            max_reconnect_attempts: None,
+            rate_limit_per_second: None,
+            app_secret_file: None,
+            verification_token_file: None,
+            denied_users: Vec::new(),
+            allowed_users_regex: false,
+            allowed_chats: vec!["*".into()],
+            dedup_window_secs: crate::config::schema::default_lark_dedup_window_secs(),
+            heartbeat_interval_secs: crate::config::schema::default_lark_heartbeat_interval_secs(),
+            backpressure: crate::config::schema::BackpressurePolicy::Block,
+            proxy: None,
+            extra_headers: std::collections::HashMap::new(),
+            user_agent: None,
+            health_check_timeout_secs: 5,
+            health_check_cache_secs: 10,
+            retry: crate::config::schema::RetryPolicy::default(),
+            log_message_content: false,
+            locale: None,
+            ordered_sends: false,
+            base_url: None,
+            outbound_dedup_window_secs: None,
+            message_prefix: None,
+            message_suffix: None,
+            verify_chat_reachability: false,
+            debug_dump_events: false,
+            debug_dump_file: None,
+            max_event_age_secs: None,
         });
 
         let masked = mask_sensitive_fields(&cfg);
@@ -1598,6 +1654,33 @@ mod tests {
             draft_update_interval_ms: crate::config::schema::default_lark_draft_update_interval_ms(
             ),
             max_draft_edits: crate::config::schema::default_lark_max_draft_edits(),
+            max_reconnect_attempts: None,
+            rate_limit_per_second: None,
+            app_secret_file: None,
+            verification_token_file: None,
+            denied_users: Vec::new(),
+            allowed_users_regex: false,
+            allowed_chats: vec!["*".into()],
+            dedup_window_secs: crate::config::schema::default_lark_dedup_window_secs(),
+            heartbeat_interval_secs: crate::config::schema::default_lark_heartbeat_interval_secs(),
+            backpressure: crate::config::schema::BackpressurePolicy::Block,
+            proxy: None,
+            extra_headers: std::collections::HashMap::new(),
+            user_agent: None,
+            health_check_timeout_secs: 5,
+            health_check_cache_secs: 10,
+            retry: crate::config::schema::RetryPolicy::default(),
+            log_message_content: false,
+            locale: None,
+            ordered_sends: false,
+            base_url: None,
+            outbound_dedup_window_secs: None,
+            message_prefix: None,
+            message_suffix: None,
+            verify_chat_reachability: false,
+            debug_dump_events: false,
+            debug_dump_file: None,
+            max_event_age_secs: None,
         });
 
         let incoming = mask_sensitive_fields(&current);
@@ -1691,6 +1774,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn named_feishu_app_secrets_are_masked_and_restored() {
+        let mut current = crate::config::Config::default();
+        current.channels_config.feishu_apps.insert(
+            "prod".to_string(),
+            serde_json::from_value(serde_json::json!({
+                "app_id": "cli_prod",
+                "app_secret": "prod-real-secret",
+                "verification_token": "prod-verify-token",
+            }))
+            .unwrap(),
+        );
+
+        let masked = mask_sensitive_fields(&current);
+        let masked_app = &masked.channels_config.feishu_apps["prod"];
+        assert_eq!(masked_app.app_secret, MASKED_SECRET);
+        assert_eq!(
+            masked_app.verification_token.as_deref(),
+            Some(MASKED_SECRET)
+        );
+
+        let restored = hydrate_config_for_save(masked, &current);
+        let restored_app = &restored.channels_config.feishu_apps["prod"];
+        assert_eq!(restored_app.app_secret, "prod-real-secret");
+        assert_eq!(
+            restored_app.verification_token.as_deref(),
+            Some("prod-verify-token")
+        );
+    }
+
     #[test]
     fn provider_key_from_integration_id_maps_dashboard_ids() {
         assert_eq!(provider_key_from_integration_id("openai"), Some("openai"));
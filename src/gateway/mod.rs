@@ -991,6 +991,8 @@ async fn handle_metrics(
     } else {
         String::from("# Prometheus backend not enabled. Set [observability] backend = \"prometheus\" in config.\n")
     };
+    #[cfg(feature = "prometheus")]
+    let body = body + &crate::channels::metrics::encode();
 
     (
         StatusCode::OK,
@@ -3416,6 +3418,14 @@ mod tests {
             channel: "whatsapp".into(),
             timestamp: 1,
             thread_ts: None,
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: crate::channels::traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         let key = whatsapp_memory_key(&msg);
@@ -3432,6 +3442,14 @@ mod tests {
             channel: "qq".into(),
             timestamp: 1,
             thread_ts: Some("msg-123".into()),
+            reply_to_message_id: None,
+            is_from_self: false,
+            mentions: Vec::new(),
+            attachments: Vec::new(),
+            chat_type: crate::channels::traits::ChatType::Group,
+            triggered_by_mention: false,
+            created_at: None,
+            received_at: chrono::Utc::now(),
         };
 
         let key = qq_memory_key(&msg);
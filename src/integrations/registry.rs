@@ -997,7 +997,7 @@ mod tests {
     use super::*;
     use crate::config::schema::{
         IMessageConfig, MatrixConfig, NextcloudTalkConfig, NostrConfig, ProgressMode, StreamMode,
-        TelegramConfig,
+        TelegramConfig, TelegramReceiveMode,
     };
     use crate::config::Config;
 
@@ -1069,6 +1069,10 @@ mod tests {
             ack_enabled: true,
             group_reply: None,
             base_url: None,
+            receive_mode: TelegramReceiveMode::Polling,
+            webhook_port: None,
+            webhook_url: None,
+            webhook_secret: None,
         });
         let entries = all_integrations();
         let tg = entries.iter().find(|e| e.name == "Telegram").unwrap();
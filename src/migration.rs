@@ -1311,6 +1311,7 @@ mod tests {
     use super::*;
     use crate::config::{
         Config, DelegateAgentConfig, MemoryConfig, ProgressMode, StreamMode, TelegramConfig,
+        TelegramReceiveMode,
     };
     use crate::memory::{Memory, SqliteMemory};
     use rusqlite::params;
@@ -1480,6 +1481,10 @@ mod tests {
             ack_enabled: true,
             group_reply: None,
             base_url: None,
+            receive_mode: TelegramReceiveMode::Polling,
+            webhook_port: None,
+            webhook_url: None,
+            webhook_secret: None,
         });
         config.agents.insert(
             "researcher".to_string(),
@@ -1,7 +1,7 @@
 use crate::config::schema::{CloudflareTunnelConfig, NgrokTunnelConfig};
 use crate::config::{
     default_model_fallback_for_provider, ChannelsConfig, Config, DiscordConfig, ProgressMode,
-    StreamMode, TelegramConfig, TunnelConfig,
+    StreamMode, TelegramConfig, TelegramReceiveMode, TunnelConfig,
 };
 use crate::onboard::wizard::{run_quick_setup_with_migration, OpenClawOnboardMigrationOptions};
 use anyhow::{bail, Context, Result};
@@ -2280,6 +2280,10 @@ fn apply_channel_overrides(config: &mut Config, plan: &TuiOnboardPlan) {
             group_reply: None,
             base_url: None,
             ack_enabled: true,
+            receive_mode: TelegramReceiveMode::Polling,
+            webhook_port: None,
+            webhook_url: None,
+            webhook_secret: None,
         });
     }
 
@@ -2296,6 +2300,7 @@ fn apply_channel_overrides(config: &mut Config, plan: &TuiOnboardPlan) {
             listen_to_bots: false,
             mention_only: false,
             group_reply: None,
+            intents: crate::config::schema::default_discord_intents(),
         });
     }
 
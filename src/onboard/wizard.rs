@@ -1,7 +1,7 @@
 use crate::config::schema::{
     default_nostr_relays, DingTalkConfig, IrcConfig, LarkReceiveMode, LinqConfig, NapcatConfig,
     NextcloudTalkConfig, NostrConfig, ProgressMode, QQConfig, QQEnvironment, QQReceiveMode,
-    SignalConfig, StreamMode, WhatsAppConfig,
+    SignalConfig, StreamMode, TelegramReceiveMode, WhatsAppConfig,
 };
 use crate::config::{
     AutonomyConfig, BrowserConfig, ChannelsConfig, ComposioConfig, Config, DiscordConfig,
@@ -4711,6 +4711,10 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     group_reply: None,
                     base_url: None,
                     ack_enabled: true,
+                    receive_mode: TelegramReceiveMode::Polling,
+                    webhook_port: None,
+                    webhook_url: None,
+                    webhook_secret: None,
                 });
             }
             ChannelMenuChoice::Discord => {
@@ -4813,6 +4817,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     listen_to_bots: false,
                     mention_only: false,
                     group_reply: None,
+                    intents: crate::config::schema::default_discord_intents(),
                 });
             }
             ChannelMenuChoice::Slack => {
@@ -6071,25 +6076,22 @@ fn setup_channels() -> Result<ChannelsConfig> {
 
                 let verification_token = if receive_mode == LarkReceiveMode::Webhook {
                     let token: String = Input::with_theme(wizard_theme())
-                        .with_prompt("  Verification Token (optional, for Webhook mode)")
+                        .with_prompt("  Verification Token (from Events & Callbacks)")
                         .allow_empty(true)
                         .interact_text()?;
+                    let token = token.trim().to_string();
                     if token.is_empty() {
-                        None
-                    } else {
-                        Some(token)
+                        println!(
+                            "  {} Verification Token is required for Webhook mode",
+                            style("❌").red().bold()
+                        );
+                        continue;
                     }
+                    Some(token)
                 } else {
                     None
                 };
 
-                if receive_mode == LarkReceiveMode::Webhook && verification_token.is_none() {
-                    println!(
-                        "  {} Verification Token is empty — webhook authenticity checks are reduced.",
-                        style("⚠").yellow().bold()
-                    );
-                }
-
                 let port = if receive_mode == LarkReceiveMode::Webhook {
                     let p: String = Input::with_theme(wizard_theme())
                         .with_prompt("  Webhook Port")
@@ -6113,9 +6115,10 @@ fn setup_channels() -> Result<ChannelsConfig> {
 
                 if allowed_users.is_empty() {
                     println!(
-                        "  {} No users allowlisted — Lark/Feishu inbound messages will be denied until you add Open IDs or '*'.",
-                        style("⚠").yellow().bold()
+                        "  {} At least one Open ID (or '*' for all) is required",
+                        style("❌").red().bold()
                     );
+                    continue;
                 }
 
                 config.lark = Some(LarkConfig {
@@ -6131,6 +6134,28 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     port,
                     draft_update_interval_ms: 3000,
                     max_draft_edits: 20,
+                    max_reconnect_attempts: None,
+                    rate_limit_per_second: None,
+                    allowed_users_regex: false,
+                    dedup_window_secs: 300,
+                    heartbeat_interval_secs: 30,
+                    backpressure: crate::config::schema::BackpressurePolicy::Block,
+                    proxy: None,
+                    extra_headers: std::collections::HashMap::new(),
+                    user_agent: None,
+                    health_check_timeout_secs: 5,
+                    health_check_cache_secs: 10,
+                    retry: crate::config::schema::RetryPolicy::default(),
+                    log_message_content: false,
+                    ordered_sends: false,
+                    base_url: None,
+                    outbound_dedup_window_secs: None,
+                    message_prefix: None,
+                    message_suffix: None,
+                    verify_chat_reachability: false,
+                    debug_dump_events: false,
+                    debug_dump_file: None,
+                    max_event_age_secs: None,
                 });
             }
             ChannelMenuChoice::Nostr => {
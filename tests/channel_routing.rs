@@ -8,7 +8,9 @@
 //! Verifies sender/reply_target field contracts to prevent field swaps.
 
 use async_trait::async_trait;
-use zeroclaw::channels::traits::{Channel, ChannelMessage, SendMessage};
+use zeroclaw::channels::traits::{
+    Channel, ChannelEvent, ChannelMessage, ChannelResult, ChatType, SendMessage, SendReceipt,
+};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // ChannelMessage construction and field semantics
@@ -25,6 +27,14 @@ fn channel_message_sender_field_holds_platform_user_id() {
         channel: "telegram".into(),
         timestamp: 1700000000,
         thread_ts: None,
+        reply_to_message_id: None,
+        is_from_self: false,
+        mentions: Vec::new(),
+        attachments: Vec::new(),
+        chat_type: ChatType::Group,
+        triggered_by_mention: false,
+        created_at: None,
+        received_at: chrono::Utc::now(),
     };
 
     assert_eq!(msg.sender, "123456789");
@@ -47,6 +57,14 @@ fn channel_message_reply_target_distinct_from_sender() {
         channel: "discord".into(),
         timestamp: 1700000000,
         thread_ts: None,
+        reply_to_message_id: None,
+        is_from_self: false,
+        mentions: Vec::new(),
+        attachments: Vec::new(),
+        chat_type: ChatType::Group,
+        triggered_by_mention: false,
+        created_at: None,
+        received_at: chrono::Utc::now(),
     };
 
     assert_ne!(
@@ -67,6 +85,14 @@ fn channel_message_fields_not_swapped() {
         channel: "test".into(),
         timestamp: 1700000000,
         thread_ts: None,
+        reply_to_message_id: None,
+        is_from_self: false,
+        mentions: Vec::new(),
+        attachments: Vec::new(),
+        chat_type: ChatType::Group,
+        triggered_by_mention: false,
+        created_at: None,
+        received_at: chrono::Utc::now(),
     };
 
     assert_eq!(
@@ -93,6 +119,14 @@ fn channel_message_preserves_all_fields_on_clone() {
         channel: "test_channel".into(),
         timestamp: 1700000001,
         thread_ts: None,
+        reply_to_message_id: None,
+        is_from_self: false,
+        mentions: Vec::new(),
+        attachments: Vec::new(),
+        chat_type: ChatType::Group,
+        triggered_by_mention: false,
+        created_at: None,
+        received_at: chrono::Utc::now(),
     };
 
     let cloned = original.clone();
@@ -172,23 +206,38 @@ impl Channel for CapturingChannel {
         "capturing"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+    async fn send(&self, message: &SendMessage) -> ChannelResult<SendReceipt> {
         self.sent.lock().unwrap().push(message.clone());
-        Ok(())
+        Ok(SendReceipt::default())
     }
 
-    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
-        tx.send(ChannelMessage {
-            id: "listen_1".into(),
-            sender: "test_sender".into(),
-            reply_target: "test_target".into(),
-            content: "incoming".into(),
-            channel: "capturing".into(),
-            timestamp: 1700000000,
-            thread_ts: None,
-        })
+    async fn listen(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelEvent>,
+        _shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> ChannelResult<()> {
+        tx.send(
+            ChannelMessage {
+                id: "listen_1".into(),
+                sender: "test_sender".into(),
+                reply_target: "test_target".into(),
+                content: "incoming".into(),
+                channel: "capturing".into(),
+                timestamp: 1700000000,
+                thread_ts: None,
+                reply_to_message_id: None,
+                is_from_self: false,
+                mentions: Vec::new(),
+                attachments: Vec::new(),
+                chat_type: ChatType::Group,
+                triggered_by_mention: false,
+                created_at: None,
+                received_at: chrono::Utc::now(),
+            }
+            .into(),
+        )
         .await
-        .map_err(|e| anyhow::anyhow!(e.to_string()))
+        .map_err(|e| anyhow::anyhow!(e.to_string()).into())
     }
 }
 
@@ -210,8 +259,15 @@ async fn channel_listen_produces_correct_identity_fields() {
     let channel = CapturingChannel::new();
     let (tx, mut rx) = tokio::sync::mpsc::channel(1);
 
-    channel.listen(tx).await.unwrap();
-    let received = rx.recv().await.expect("should receive message");
+    channel
+        .listen(tx, tokio::sync::watch::channel(false).1)
+        .await
+        .unwrap();
+    let received = rx
+        .recv()
+        .await
+        .and_then(ChannelEvent::into_message)
+        .expect("should receive message");
 
     assert_eq!(received.sender, "test_sender");
     assert_eq!(received.reply_target, "test_target");
@@ -227,8 +283,15 @@ async fn channel_send_reply_uses_sender_from_listen() {
     let (tx, mut rx) = tokio::sync::mpsc::channel(1);
 
     // Simulate: listen() → receive message → send reply using sender
-    channel.listen(tx).await.unwrap();
-    let incoming = rx.recv().await.expect("should receive message");
+    channel
+        .listen(tx, tokio::sync::watch::channel(false).1)
+        .await
+        .unwrap();
+    let incoming = rx
+        .recv()
+        .await
+        .and_then(ChannelEvent::into_message)
+        .expect("should receive message");
 
     // Reply should go to the reply_target, not sender
     let reply = SendMessage::new("reply content", &incoming.reply_target);
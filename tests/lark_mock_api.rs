@@ -0,0 +1,3389 @@
+//! Lark/Feishu channel behavior against a mocked Open API server.
+#![cfg(feature = "channel-lark")]
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use wiremock::matchers::{
+    body_partial_json, body_string_contains, header, method, path, path_regex, query_param,
+};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+use zeroclaw::channels::feishu_errors::FeishuErrorCode;
+use zeroclaw::channels::lark::{markdown_to_card, TokenKind, TranscriptFormat, Urgency};
+use zeroclaw::channels::traits::{
+    Attachment, Channel, ChannelError, ChannelEvent, RichText, SendMessage,
+};
+use zeroclaw::channels::transcription::Transcriber;
+use zeroclaw::channels::LarkChannel;
+use zeroclaw::config::schema::{FeishuConfig, RetryPolicy};
+use zeroclaw::config::Config;
+
+/// Helper: create a LarkChannel pointing at a mock server.
+fn test_channel(mock_url: &str) -> LarkChannel {
+    LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        None,
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(mock_url.to_string())
+}
+
+/// Helper: mount the tenant access token endpoint.
+async fn mock_tenant_token(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/auth/v3/tenant_access_token/internal"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "msg": "ok",
+            "tenant_access_token": "t-test-token",
+            "expire": 7200
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn access_tokens_are_fetched_per_kind_and_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/auth/v3/tenant_access_token/internal"))
+        .and(body_partial_json(
+            serde_json::json!({ "app_id": "cli_test_app" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "tenant_access_token": "t-tenant",
+            "expire": 7200
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/auth/v3/app_access_token/internal"))
+        .and(body_partial_json(
+            serde_json::json!({ "app_id": "cli_test_app" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "app_access_token": "a-app",
+            "expire": 7200
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    for _ in 0..2 {
+        assert_eq!(
+            channel.access_token(TokenKind::Tenant).await.unwrap(),
+            "t-tenant"
+        );
+        assert_eq!(channel.access_token(TokenKind::App).await.unwrap(), "a-app");
+    }
+}
+
+#[tokio::test]
+async fn send_returns_message_id_from_api_response() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "msg": "success",
+            "data": {
+                "message_id": "om_dc13264520392913993dd051dba21dcf",
+                "chat_id": "oc_test_chat"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let receipt = channel
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .expect("send should succeed");
+
+    assert_eq!(
+        receipt.message_id.as_deref(),
+        Some("om_dc13264520392913993dd051dba21dcf")
+    );
+    assert_eq!(receipt.chat_id.as_deref(), Some("oc_test_chat"));
+}
+
+#[tokio::test]
+async fn custom_user_agent_and_headers_are_sent_on_api_calls() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/auth/v3/tenant_access_token/internal"))
+        .and(header("user-agent", "acme-bot/1.0"))
+        .and(header("x-correlation-id", "audit-42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "tenant_access_token": "t-test-token",
+            "expire": 7200
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(header("user-agent", "acme-bot/1.0"))
+        .and(header("x-correlation-id", "audit-42"))
+        .and(header("authorization", "Bearer t-test-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_ua" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let headers =
+        std::collections::HashMap::from([("X-Correlation-Id".to_string(), "audit-42".to_string())]);
+    let channel = test_channel(&server.uri()).with_extra_headers(&headers, Some("acme-bot/1.0"));
+    let receipt = channel
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .expect("send should succeed");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_ua"));
+}
+
+#[tokio::test]
+async fn add_reaction_returns_reaction_id() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages/om_target/reactions"))
+        .and(body_partial_json(serde_json::json!({
+            "reaction_type": { "emoji_type": "DONE" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "reaction_id": "rc_done_1", "reaction_type": { "emoji_type": "DONE" } }
+        })))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let reaction_id = channel
+        .add_reaction("om_target", "DONE")
+        .await
+        .expect("reaction should be added");
+    assert_eq!(reaction_id, "rc_done_1");
+    // Unicode emoji from the runtime map to the matching Feishu key.
+    channel
+        .add_reaction("om_target", "\u{2705}")
+        .await
+        .expect("unicode reaction should be added");
+    channel
+        .add_reaction("om_target", ":white_check_mark:")
+        .await
+        .expect("shortcode reaction should be added");
+    // Emoji without a Feishu key fail locally instead of hitting the API.
+    assert!(channel
+        .add_reaction("om_target", "\u{1F996}")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn add_reactions_runs_concurrently_and_isolates_failures() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path_regex(r"^/im/v1/messages/om_\w+/reactions$"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_millis(200))
+                .set_body_json(serde_json::json!({
+                    "code": 0,
+                    "data": { "reaction_id": "rc_bulk" }
+                })),
+        )
+        .expect(10)
+        .mount(&server)
+        .await;
+
+    let mut ops: Vec<(String, String)> = (0..10)
+        .map(|i| (format!("om_msg{i}"), "DONE".to_string()))
+        .collect();
+    ops.insert(3, ("not_a_message".into(), "DONE".into()));
+    ops.insert(7, ("om_msg_dino".into(), "\u{1F996}".into()));
+
+    let started = std::time::Instant::now();
+    let results = test_channel(&server.uri()).add_reactions(&ops).await;
+    let elapsed = started.elapsed();
+
+    assert_eq!(results.len(), 12);
+    assert!(results[3].is_err());
+    assert!(results[7].is_err());
+    for (i, result) in results.iter().enumerate() {
+        if i != 3 && i != 7 {
+            assert_eq!(result.as_deref().unwrap(), "rc_bulk");
+        }
+    }
+    // Ten delayed requests, five at a time: two rounds, not ten.
+    assert!(elapsed >= Duration::from_millis(400), "{elapsed:?}");
+    assert!(elapsed < Duration::from_millis(1_500), "{elapsed:?}");
+}
+
+#[tokio::test]
+async fn delete_message_calls_the_delete_endpoint() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("DELETE"))
+        .and(path("/im/v1/messages/om_processing"))
+        .and(header("Authorization", "Bearer t-test-token"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0, "msg": "ok" })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/im/v1/messages/om_old"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 230024,
+            "msg": "Reach the upper limit of recall time"
+        })))
+        .mount(&server)
+        .await;
+
+    let channel: Box<dyn Channel> = Box::new(test_channel(&server.uri()));
+    channel
+        .delete_message("om_processing")
+        .await
+        .expect("recall should succeed");
+    let err = channel.delete_message("om_old").await.unwrap_err();
+    assert!(
+        err.to_string().contains("can no longer be recalled"),
+        "{err}"
+    );
+    assert!(channel.delete_message("not_a_message").await.is_err());
+}
+
+#[tokio::test]
+async fn delete_messages_reports_each_result() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("DELETE"))
+        .and(path("/im/v1/messages/om_mine"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0, "msg": "ok" })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/im/v1/messages/om_recalled"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 230011,
+            "msg": "message has been recalled"
+        })))
+        .mount(&server)
+        .await;
+
+    let results = test_channel(&server.uri())
+        .delete_messages(&["om_mine".into(), "om_recalled".into(), "om_mine".into()])
+        .await;
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[2].is_ok());
+    match &results[1] {
+        Err(ChannelError::Api { kind, .. }) => {
+            assert_eq!(*kind, FeishuErrorCode::MessageRecalled);
+        }
+        other => panic!("expected an API error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn feishu_channel_is_usable_as_boxed_dyn_channel() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(wiremock::matchers::any())
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_dyn", "reaction_id": "rc_dyn", "items": [] }
+        })))
+        .mount(&server)
+        .await;
+
+    let config: FeishuConfig = toml::from_str(
+        r#"
+app_id = "cli_test_app"
+app_secret = "test_secret"
+allowed_users = ["*"]
+"#,
+    )
+    .unwrap();
+    let channel: Box<dyn Channel> =
+        Box::new(LarkChannel::from_feishu_config(&config).with_api_base(server.uri()));
+
+    // Every trait method must dispatch through the vtable; results only need
+    // to come back, not succeed.
+    assert_eq!(channel.name(), "feishu");
+    assert!(channel.capabilities().supports_reactions);
+    let _ = channel.locale();
+    let _ = channel.health_check().await;
+    let _ = channel.reload_config(&Config::default()).await;
+    let message = SendMessage::new("hello", "oc_test_chat");
+    channel.send(&message).await.expect("send should succeed");
+    let _ = channel.start_typing("oc_test_chat").await;
+    let _ = channel.stop_typing("oc_test_chat").await;
+    let _ = channel.supports_draft_updates();
+    let _ = channel.send_draft(&message).await;
+    let _ = channel.update_draft("oc_test_chat", "om_dyn", "more").await;
+    let _ = channel
+        .finalize_draft("oc_test_chat", "om_dyn", "done")
+        .await;
+    let _ = channel.cancel_draft("oc_test_chat", "om_dyn").await;
+    let _ = channel
+        .edit_message("oc_test_chat", "om_dyn", &message)
+        .await;
+    let _ = channel.download_attachment("om_dyn", "file_key").await;
+    let _ = channel.fetch_history("oc_test_chat", 5).await;
+    let _ = channel
+        .send_approval_prompt(
+            "oc_test_chat",
+            "req_1",
+            "shell",
+            &serde_json::json!({ "cmd": "ls" }),
+            None,
+        )
+        .await;
+    let _ = channel.add_reaction("om_dyn", "DONE").await;
+    let _ = channel.remove_reaction("om_dyn", "rc_dyn").await;
+
+    let (tx, _rx) = tokio::sync::mpsc::channel(1);
+    let (_shutdown_tx, shutdown) = tokio::sync::watch::channel(true);
+    let _ = tokio::time::timeout(Duration::from_secs(2), channel.listen(tx, shutdown)).await;
+}
+
+#[tokio::test]
+async fn remove_reaction_deletes_by_reaction_id() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/im/v1/messages/om_target/reactions/rc_app"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    channel
+        .remove_reaction("om_target", "rc_app")
+        .await
+        .expect("reaction should be removed");
+}
+
+fn open_ids(count: usize) -> Vec<String> {
+    (0..count).map(|i| format!("ou_member_{i}")).collect()
+}
+
+/// Lengths of the member lists sent to `path` by `method`, in request order.
+async fn member_batches(
+    server: &MockServer,
+    http_method: &str,
+    path: &str,
+    field: &str,
+) -> Vec<usize> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|req| req.method.as_str() == http_method && req.url.path() == path)
+        .map(|req| {
+            let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+            body[field].as_array().unwrap().len()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn create_chat_adds_members_beyond_the_first_batch() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/chats"))
+        .and(query_param("user_id_type", "open_id"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Onboarding: Ada",
+            "chat_mode": "group",
+            "chat_type": "private"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "chat_id": "oc_onboarding", "invalid_id_list": [] }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/chats/oc_onboarding/members"))
+        .and(query_param("member_id_type", "open_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "invalid_id_list": [], "not_existed_id_list": [] }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let mut members = open_ids(120);
+    members.push("ou_member_0".into());
+    let chat_id = test_channel(&server.uri())
+        .create_chat("Onboarding: Ada", &members)
+        .await
+        .unwrap();
+
+    assert_eq!(chat_id, "oc_onboarding");
+    assert_eq!(
+        member_batches(&server, "POST", "/im/v1/chats", "user_id_list").await,
+        [50]
+    );
+    assert_eq!(
+        member_batches(
+            &server,
+            "POST",
+            "/im/v1/chats/oc_onboarding/members",
+            "id_list"
+        )
+        .await,
+        [50, 20]
+    );
+}
+
+#[tokio::test]
+async fn remove_members_batches_and_surfaces_api_errors() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/im/v1/chats/oc_team/members"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "invalid_id_list": [] }
+        })))
+        .expect(3)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/chats/oc_team/members"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "code": 232_011,
+            "msg": "operator can NOT be out of the chat"
+        })))
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    channel
+        .remove_members("oc_team", &open_ids(101))
+        .await
+        .unwrap();
+    assert_eq!(
+        member_batches(&server, "DELETE", "/im/v1/chats/oc_team/members", "id_list").await,
+        [50, 50, 1]
+    );
+
+    let err = channel
+        .add_members("oc_team", &open_ids(2))
+        .await
+        .expect_err("API rejection should fail the call");
+    assert!(err.to_string().contains("232011"), "{err}");
+    assert!(channel
+        .add_members("ou_not_a_chat", &open_ids(1))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn read_status_lists_readers_and_tolerates_missing_scope() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages/om_alert/read_users"))
+        .and(query_param("user_id_type", "open_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": {
+                "items": [
+                    { "user_id_type": "open_id", "user_id": "ou_alice", "timestamp": "1700000000000" },
+                    { "user_id_type": "open_id", "user_id": "ou_bob", "timestamp": "1700000060000" }
+                ],
+                "has_more": false
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages/om_private/read_users"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "code": 99_991_672,
+            "msg": "Access denied. One of the following scopes is required: [im:message:readonly]"
+        })))
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let status = channel.read_status("om_alert").await.unwrap();
+    assert!(status.known);
+    assert_eq!(status.read_count(), 2);
+    assert!(status.has_read("ou_bob"));
+    assert!(!status.has_read("ou_carol"));
+    assert_eq!(status.readers[0].open_id, "ou_alice");
+    assert_eq!(
+        status.readers[0].read_at.map(|at| at.timestamp()),
+        Some(1_700_000_000)
+    );
+
+    let unknown = channel.read_status("om_private").await.unwrap();
+    assert!(!unknown.known);
+    assert_eq!(unknown.read_count(), 0);
+    assert!(channel.read_status("not_a_message").await.is_err());
+}
+
+#[tokio::test]
+async fn send_ephemeral_posts_card_to_one_user_and_rejects_attachments() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/ephemeral/v1/send"))
+        .and(body_partial_json(serde_json::json!({
+            "chat_id": "oc_team",
+            "open_id": "ou_clicker",
+            "msg_type": "interactive"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_ephemeral" }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let receipt = channel
+        .send_ephemeral(
+            "oc_team",
+            "ou_clicker",
+            &SendMessage::new("Pick an environment", "oc_team"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(receipt.message_id.as_deref(), Some("om_ephemeral"));
+    let card = serde_json::json!({ "elements": [{ "tag": "hr" }] });
+    channel
+        .send_ephemeral(
+            "oc_team",
+            "ou_clicker",
+            &SendMessage::builder()
+                .to("oc_team")
+                .card(card.clone())
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let bodies: Vec<serde_json::Value> = requests
+        .iter()
+        .filter(|request| request.url.path() == "/ephemeral/v1/send")
+        .map(|request| serde_json::from_slice(&request.body).unwrap())
+        .collect();
+    assert!(bodies[0]["card"]
+        .to_string()
+        .contains("Pick an environment"));
+    assert_eq!(bodies[1]["card"], card);
+
+    let with_file = SendMessage::new("report", "oc_team").with_attachment(Attachment::File {
+        bytes: b"data".to_vec(),
+        filename: "report.csv".into(),
+        mime: "text/csv".into(),
+    });
+    let err = channel
+        .send_ephemeral("oc_team", "ou_clicker", &with_file)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err.downcast_ref::<ChannelError>(),
+            Some(ChannelError::NotSupported(
+                "attachments in ephemeral messages"
+            ))
+        ),
+        "{err:?}"
+    );
+    assert!(channel
+        .send_ephemeral(
+            "oc_team",
+            "oc_not_a_user",
+            &SendMessage::new("hi", "oc_team")
+        )
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn pin_and_unpin_message_round_trip() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/pins"))
+        .and(body_partial_json(
+            serde_json::json!({ "message_id": "om_notice" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "pin": { "message_id": "om_notice", "chat_id": "oc_team" } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/im/v1/pins/om_notice"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let pin_id = channel.pin_message("om_notice").await.unwrap();
+    assert_eq!(pin_id, "om_notice");
+    channel.unpin_message(&pin_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn update_card_patches_message_with_new_card_content() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    let card = markdown_to_card("**Build** 3/5 steps done");
+    Mock::given(method("PATCH"))
+        .and(path("/im/v1/messages/om_status"))
+        .and(body_partial_json(
+            serde_json::json!({ "content": card.to_json().to_string() }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    channel.update_card("om_status", &card).await.unwrap();
+}
+
+#[tokio::test]
+async fn forward_message_posts_to_forward_endpoint() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages/om_user_question/forward"))
+        .and(query_param("receive_id_type", "chat_id"))
+        .and(body_partial_json(
+            serde_json::json!({ "receive_id": "oc_escalation" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_forwarded", "chat_id": "oc_escalation" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let forwarded = channel
+        .forward_message("om_user_question", "oc_escalation")
+        .await
+        .unwrap();
+    assert_eq!(forwarded, "om_forwarded");
+    assert!(channel
+        .forward_message("om_user_question", "ou_not_a_chat")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn quote_reply_quotes_original_with_sender_name() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages/om_user_question"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "items": [{
+                "message_id": "om_user_question",
+                "chat_id": "oc_support",
+                "msg_type": "text",
+                "create_time": "1700000000000",
+                "sender": { "id": "ou_ada", "id_type": "open_id", "sender_type": "user" },
+                "body": { "content": "{\"text\":\"Checkout fails\\nwith error 502\"}" }
+            }] }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/contact/v3/users/ou_ada"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "user": { "open_id": "ou_ada", "name": "Ada Lovelace" } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(query_param("receive_id_type", "chat_id"))
+        .and(body_partial_json(serde_json::json!({
+            "receive_id": "oc_escalation",
+            "msg_type": "text",
+            "content": serde_json::json!({
+                "text": "Ada Lovelace:\n> Checkout fails\n> with error 502\n\nEscalating to on-call."
+            })
+            .to_string()
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_quote" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let message_id = test_channel(&server.uri())
+        .quote_reply(
+            "om_user_question",
+            "oc_escalation",
+            "Escalating to on-call.",
+        )
+        .await
+        .unwrap();
+    assert_eq!(message_id, "om_quote");
+}
+
+#[tokio::test]
+async fn send_urgent_calls_the_endpoint_for_the_chosen_urgency() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_alert" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/im/v1/messages/om_alert/urgent_phone"))
+        .and(query_param("user_id_type", "open_id"))
+        .and(body_partial_json(serde_json::json!({
+            "user_id_list": ["ou_oncall", "ou_lead"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "invalid_user_id_list": [] }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    for other in ["urgent_app", "urgent_sms"] {
+        Mock::given(method("PATCH"))
+            .and(path(format!("/im/v1/messages/om_alert/{other}")))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 })),
+            )
+            .expect(0)
+            .mount(&server)
+            .await;
+    }
+
+    let channel = test_channel(&server.uri());
+    let message = SendMessage::builder()
+        .to("oc_ops")
+        .text("Database primary is down")
+        .mention("ou_oncall")
+        .mention("ou_lead")
+        .mention("ou_oncall")
+        .build();
+    let message_id = channel.send_urgent(&message, Urgency::Phone).await.unwrap();
+    assert_eq!(message_id, "om_alert");
+
+    let unmentioned = SendMessage::new("no one to buzz", "oc_ops");
+    assert!(channel
+        .send_urgent(&unmentioned, Urgency::App)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn send_card_posts_interactive_message() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(
+            serde_json::json!({ "msg_type": "interactive" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_card_1" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let card = serde_json::json!({
+        "header": { "title": { "tag": "plain_text", "content": "Build finished" } },
+        "elements": [{
+            "tag": "action",
+            "actions": [{
+                "tag": "button",
+                "text": { "tag": "plain_text", "content": "Open" },
+                "type": "primary",
+                "value": { "action": "open" }
+            }]
+        }]
+    });
+    let channel = test_channel(&server.uri());
+    let receipt = channel
+        .send(&SendMessage::new("Build finished", "oc_test_chat").with_card(card))
+        .await
+        .expect("card send should succeed");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_card_1"));
+}
+
+#[tokio::test]
+async fn send_rich_text_posts_post_message() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(serde_json::json!({ "msg_type": "post" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_post_1" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let rich_text = RichText::new()
+        .with_title("Build finished")
+        .paragraph()
+        .bold("main")
+        .text(" is green, see ")
+        .link("the run", "https://ci.example.com/runs/1");
+    let channel = test_channel(&server.uri());
+    let receipt = channel
+        .send(&SendMessage::new("Build finished", "oc_test_chat").with_rich_text(rich_text))
+        .await
+        .expect("post send should succeed");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_post_1"));
+
+    let requests = server.received_requests().await.unwrap();
+    let send = requests
+        .iter()
+        .find(|req| req.url.path() == "/im/v1/messages")
+        .expect("message request");
+    let body: serde_json::Value = serde_json::from_slice(&send.body).unwrap();
+    let content: serde_json::Value =
+        serde_json::from_str(body["content"].as_str().unwrap()).unwrap();
+    let post = &content["zh_cn"];
+    assert_eq!(post["title"], "Build finished");
+    assert_eq!(
+        post["content"][0],
+        serde_json::json!([
+            { "tag": "text", "text": "main", "style": ["bold"] },
+            { "tag": "text", "text": " is green, see " },
+            { "tag": "a", "text": "the run", "href": "https://ci.example.com/runs/1" }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn send_markdown_posts_converted_card() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(
+            serde_json::json!({ "msg_type": "interactive" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_md_1" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let receipt = channel
+        .send(&SendMessage::markdown(
+            "# Status\n- **all** green\n```\nok\n```",
+            "oc_test_chat",
+        ))
+        .await
+        .expect("markdown send should succeed");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_md_1"));
+
+    let requests = server.received_requests().await.unwrap();
+    let send = requests
+        .iter()
+        .find(|req| req.url.path() == "/im/v1/messages")
+        .expect("message request");
+    let body: serde_json::Value = serde_json::from_slice(&send.body).unwrap();
+    let card: serde_json::Value = serde_json::from_str(body["content"].as_str().unwrap()).unwrap();
+    let elements = card["elements"].as_array().unwrap();
+    assert_eq!(elements[0]["text"]["content"], "**Status**");
+    assert_eq!(elements[1]["text"]["content"], "• **all** green");
+    assert_eq!(elements[2]["tag"], "markdown");
+}
+
+#[tokio::test]
+async fn send_with_mention_prefixes_at_tag() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(serde_json::json!({
+            "msg_type": "text",
+            "content": "{\"text\":\"<at user_id=\\\"ou_alice\\\"></at> deploy finished\"}"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_at_1" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let receipt = channel
+        .send(&SendMessage::new("deploy finished", "oc_test_chat").with_mention("ou_alice"))
+        .await
+        .expect("send with mention should succeed");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_at_1"));
+}
+
+/// `(msg_type, parsed content)` of every message POSTed to the mock server.
+async fn sent_messages(server: &MockServer) -> Vec<(String, serde_json::Value)> {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == "/im/v1/messages")
+        .map(|request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let content = serde_json::from_str(body["content"].as_str().unwrap()).unwrap();
+            (body["msg_type"].as_str().unwrap().to_string(), content)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn mentions_are_added_to_card_markdown_and_rich_text_messages() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_mentioned" }
+        })))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let card = serde_json::json!({ "elements": [{ "tag": "hr" }] });
+    channel
+        .send(
+            &SendMessage::new("fallback", "oc_test_chat")
+                .with_card(card)
+                .with_mention("ou_alice"),
+        )
+        .await
+        .expect("card with mention should send");
+    channel
+        .send(
+            &SendMessage::builder()
+                .to("oc_test_chat")
+                .markdown("**deploy** finished")
+                .mention("ou_alice")
+                .build(),
+        )
+        .await
+        .expect("markdown with mention should send");
+    channel
+        .send(
+            &SendMessage::new("fallback", "oc_test_chat")
+                .with_rich_text(RichText::new().text("done"))
+                .with_mention("ou_alice"),
+        )
+        .await
+        .expect("rich text with mention should send");
+
+    let sent = sent_messages(&server).await;
+    assert_eq!(sent.len(), 3);
+    let mention = serde_json::json!({ "tag": "markdown", "content": "<at id=ou_alice></at>" });
+    assert_eq!(sent[0].0, "interactive");
+    assert_eq!(sent[0].1["elements"][0], mention);
+    assert_eq!(sent[1].0, "interactive");
+    assert_eq!(sent[1].1["elements"][0], mention);
+    assert_eq!(sent[2].0, "post");
+    assert_eq!(
+        sent[2].1["zh_cn"]["content"][0][0],
+        serde_json::json!({ "tag": "at", "user_id": "ou_alice" })
+    );
+}
+
+#[tokio::test]
+async fn message_prefix_and_suffix_wrap_text_chunks_and_rich_text_but_not_cards() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_wrapped" }
+        })))
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri()).with_message_affixes(
+        Some("[Automated] ".into()),
+        Some("\n-- sent by ops-bot".into()),
+    );
+    channel
+        .send(&SendMessage::new("deploy finished", "oc_test_chat"))
+        .await
+        .unwrap();
+    channel
+        .send(&SendMessage::new(
+            "line of text\n".repeat(2_500),
+            "oc_test_chat",
+        ))
+        .await
+        .unwrap();
+    channel
+        .send(
+            &SendMessage::builder()
+                .to("oc_test_chat")
+                .rich_text(RichText::new().text("done"))
+                .build(),
+        )
+        .await
+        .unwrap();
+    let card = serde_json::json!({ "elements": [{ "tag": "hr" }] });
+    channel
+        .send(
+            &SendMessage::builder()
+                .to("oc_test_chat")
+                .card(card.clone())
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    let sent = sent_messages(&server).await;
+    assert_eq!(sent.len(), 5);
+    assert_eq!(
+        sent[0].1["text"],
+        "[Automated] deploy finished\n-- sent by ops-bot"
+    );
+    // The ~32 KB text is split so that each wrapped chunk stays in the limit.
+    for (_, chunk) in &sent[1..3] {
+        let text = chunk["text"].as_str().unwrap();
+        assert!(text.starts_with("[Automated] line of text\n"));
+        assert!(text.ends_with("\n-- sent by ops-bot"));
+        assert!(text.len() <= 20 * 1024, "chunk is {} bytes", text.len());
+    }
+    assert_eq!(sent[3].0, "post");
+    let paragraph = sent[3].1["zh_cn"]["content"][0].as_array().unwrap();
+    assert_eq!(paragraph[0]["text"], "[Automated] ");
+    assert_eq!(paragraph[1]["text"], "done");
+    assert_eq!(paragraph[2]["text"], "\n-- sent by ops-bot");
+    assert_eq!(sent[4].0, "interactive");
+    assert_eq!(sent[4].1, card);
+}
+
+#[tokio::test]
+async fn send_at_delivers_when_due_and_honours_cancel() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(serde_json::json!({
+            "content": "{\"text\":\"standup in 5\"}"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_sched_1" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let due = chrono::Utc::now() + chrono::Duration::milliseconds(50);
+    channel.send_at(&SendMessage::new("standup in 5", "oc_test_chat"), due);
+    let cancelled = channel.send_at(
+        &SendMessage::new("never sent", "oc_test_chat"),
+        chrono::Utc::now() + chrono::Duration::hours(1),
+    );
+    assert_eq!(channel.pending_scheduled_sends(), 2);
+    assert!(channel.cancel_scheduled(&cancelled));
+    assert!(!channel.cancel_scheduled(&cancelled));
+
+    // Wait for the scheduled send to hit the mock; `expect(1)` checks there
+    // is exactly one on drop.
+    for _ in 0..100 {
+        let requests = server.received_requests().await.unwrap_or_default();
+        if requests.iter().any(|r| r.url.path() == "/im/v1/messages") {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(channel.pending_scheduled_sends(), 0);
+}
+
+#[tokio::test]
+async fn send_attachments_uploads_then_sends_by_key() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/images"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "image_key": "img_v2_chart" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "file_key": "file_v2_report" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(serde_json::json!({
+            "msg_type": "image",
+            "content": "{\"image_key\":\"img_v2_chart\"}"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_image_1" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(serde_json::json!({
+            "msg_type": "file",
+            "content": "{\"file_key\":\"file_v2_report\"}"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_file_1" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let message = SendMessage::new("", "oc_test_chat")
+        .with_attachment(Attachment::Image {
+            bytes: vec![0x89, b'P', b'N', b'G'],
+            filename: "chart.png".into(),
+        })
+        .with_attachment(Attachment::File {
+            bytes: b"%PDF-1.7".to_vec(),
+            filename: "report.pdf".into(),
+            mime: "application/pdf".into(),
+        });
+    let channel = test_channel(&server.uri());
+    let receipt = channel
+        .send(&message)
+        .await
+        .expect("attachment send should succeed");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_file_1"));
+}
+
+#[tokio::test]
+async fn audio_attachment_uploads_opus_then_sends_audio_message() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/files"))
+        .and(body_string_contains("voice.opus"))
+        .and(body_string_contains("1500"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "file_key": "file_v2_voice" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(serde_json::json!({
+            "msg_type": "audio",
+            "content": "{\"file_key\":\"file_v2_voice\"}"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_audio_1" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let message = SendMessage::new("", "oc_test_chat").with_attachment(Attachment::Audio {
+        bytes: b"OggS-opus".to_vec(),
+        duration_ms: 1500,
+    });
+    let receipt = test_channel(&server.uri())
+        .send(&message)
+        .await
+        .expect("audio send should succeed");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_audio_1"));
+}
+
+#[tokio::test]
+async fn image_url_is_downloaded_uploaded_once_and_sent_by_key() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/assets/cat.png"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "image/png")
+                .set_body_bytes(vec![0x89, b'P', b'N', b'G']),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/images"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "image_key": "img_v2_cat" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(serde_json::json!({
+            "msg_type": "image",
+            "content": "{\"image_key\":\"img_v2_cat\"}"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_cat" }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let message = SendMessage::image_url(format!("{}/assets/cat.png", server.uri()))
+        .with_recipient("oc_chat");
+    for _ in 0..2 {
+        let receipt = channel
+            .send(&message)
+            .await
+            .expect("image send should succeed");
+        assert_eq!(receipt.message_id.as_deref(), Some("om_cat"));
+    }
+}
+
+#[tokio::test]
+async fn image_url_download_failures_map_to_typed_errors() {
+    use zeroclaw::channels::lark::LarkError;
+
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/page.html"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw(b"<html></html>".to_vec(), "text/html; charset=utf-8"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/huge.png"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "image/png")
+                .set_body_bytes(vec![0_u8; 10 * 1024 * 1024 + 1]),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/missing.png"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/images"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let send_error = |file: &str| {
+        let message =
+            SendMessage::image_url(format!("{}/{file}", server.uri())).with_recipient("oc_chat");
+        let channel = channel.clone();
+        async move {
+            let err = channel.send(&message).await.unwrap_err();
+            err.downcast_ref::<LarkError>()
+                .cloned()
+                .expect("image URL failures should be typed LarkErrors")
+        }
+    };
+
+    assert!(matches!(
+        send_error("page.html").await,
+        LarkError::NotAnImage { content_type, .. } if content_type == "text/html"
+    ));
+    assert!(matches!(
+        send_error("huge.png").await,
+        LarkError::ImageTooLarge { limit, .. } if limit == 10 * 1024 * 1024
+    ));
+    assert!(matches!(
+        send_error("missing.png").await,
+        LarkError::ImageDownload { reason, .. } if reason.contains("404")
+    ));
+}
+
+#[tokio::test]
+async fn download_attachment_fetches_message_resource() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages/om_voice/resources/file_v3_voice"))
+        .and(query_param("type", "file"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "audio/opus")
+                .set_body_bytes(b"OggS-voice".to_vec()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let bytes = channel
+        .download_attachment("om_voice", "file_v3_voice")
+        .await
+        .expect("download should succeed");
+    assert_eq!(bytes, b"OggS-voice");
+}
+
+#[tokio::test]
+async fn tenant_token_is_cached_across_sends() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/auth/v3/tenant_access_token/internal"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "msg": "ok",
+            "tenant_access_token": "t-test-token",
+            "expire": 7200
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_cached" }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    for text in ["first", "second"] {
+        channel
+            .send(&SendMessage::new(text, "oc_test_chat"))
+            .await
+            .expect("send should succeed");
+    }
+}
+
+/// Answers each send after `delay` with `om_<receive_id>` as the message ID
+/// and records when the request arrived.
+struct SlowEchoSend {
+    delay: Duration,
+    arrivals: Arc<Mutex<Vec<Instant>>>,
+}
+
+impl Respond for SlowEchoSend {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        self.arrivals.lock().unwrap().push(Instant::now());
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap_or_default();
+        let recipient = body["receive_id"].as_str().unwrap_or_default();
+        ResponseTemplate::new(200)
+            .set_delay(self.delay)
+            .set_body_json(serde_json::json!({
+                "code": 0,
+                "data": { "message_id": format!("om_{recipient}") }
+            }))
+    }
+}
+
+#[tokio::test]
+async fn send_many_bounds_concurrency_and_keeps_input_order() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    let delay = Duration::from_millis(100);
+    let arrivals = Arc::new(Mutex::new(Vec::new()));
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(SlowEchoSend {
+            delay,
+            arrivals: Arc::clone(&arrivals),
+        })
+        .expect(12)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let recipients: Vec<String> = (0..12).map(|i| format!("oc_chat_{i}")).collect();
+    let started = Instant::now();
+    let results = channel
+        .send_many(&recipients, &SendMessage::new("notice", "ignored"), 3)
+        .await;
+
+    let ids: Vec<String> = results.into_iter().map(Result::unwrap).collect();
+    let expected: Vec<String> = recipients.iter().map(|r| format!("om_{r}")).collect();
+    assert_eq!(ids, expected);
+    // Four waves of three, not twelve sequential sends.
+    assert!(started.elapsed() < delay * 8);
+
+    // A request is still in flight until `delay` after it arrived, so no
+    // window shorter than `delay` may hold more than three arrivals.
+    let arrivals = arrivals.lock().unwrap().clone();
+    let window = delay * 4 / 5;
+    let peak = arrivals
+        .iter()
+        .map(|at| {
+            arrivals
+                .iter()
+                .filter(|other| *other <= at && at.duration_since(**other) < window)
+                .count()
+        })
+        .max()
+        .unwrap();
+    assert!(peak <= 3, "{peak} sends were in flight at once");
+}
+
+#[tokio::test]
+async fn ordered_sends_reach_the_server_in_issue_order() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_millis(30))
+                .set_body_json(serde_json::json!({
+                    "code": 0,
+                    "data": { "message_id": "om_ordered" }
+                })),
+        )
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri()).with_ordered_sends(true);
+    let send = |text: &'static str| {
+        let channel = channel.clone();
+        async move {
+            channel
+                .send(&SendMessage::new(text, "oc_test_chat"))
+                .await
+                .expect("send should succeed")
+        }
+    };
+    tokio::join!(send("one"), send("two"), send("three"));
+
+    let texts: Vec<String> = sent_messages(&server)
+        .await
+        .into_iter()
+        .map(|(_, content)| content["text"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(texts, ["one", "two", "three"]);
+}
+
+#[tokio::test]
+async fn outbound_dedup_suppresses_identical_send_within_window() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(
+            serde_json::json!({ "receive_id": "oc_test_chat" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_once" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(
+            serde_json::json!({ "receive_id": "oc_other_chat" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_other" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel =
+        test_channel(&server.uri()).with_outbound_dedup_window(Some(Duration::from_secs(60)));
+    let message = SendMessage::new("Deploy finished", "oc_test_chat");
+    let first = channel.send(&message).await.unwrap();
+    let second = channel.send(&message).await.unwrap();
+    assert_eq!(first.message_id.as_deref(), Some("om_once"));
+    assert_eq!(second, first);
+
+    // Same text to another chat is not a duplicate.
+    let other = channel
+        .send(&SendMessage::new("Deploy finished", "oc_other_chat"))
+        .await
+        .unwrap();
+    assert_eq!(other.message_id.as_deref(), Some("om_other"));
+}
+
+#[tokio::test]
+async fn send_retries_once_after_rate_limit() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_after_429" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri()).with_rate_limit(Some(5));
+    let receipt = channel
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .expect("send should succeed after one retry");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_after_429"));
+}
+
+#[tokio::test]
+async fn with_base_url_sends_through_open_apis_prefix() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/open-apis/auth/v3/tenant_access_token/internal"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "tenant_access_token": "t-test-token",
+            "expire": 7200
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/open-apis/im/v1/messages"))
+        .and(header("Authorization", "Bearer t-test-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_base_url" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        None,
+        vec!["*".into()],
+        false,
+    )
+    .with_base_url(format!("{}/", server.uri()));
+    let receipt = channel
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .expect("send should succeed");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_base_url"));
+}
+
+#[tokio::test]
+async fn injected_http_client_is_shared_by_every_channel() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/auth/v3/tenant_access_token/internal"))
+        .and(header("x-client-pool", "shared"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "tenant_access_token": "t-test-token",
+            "expire": 7200
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(header("x-client-pool", "shared"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_shared" }
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-client-pool", "shared".parse().unwrap());
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+    for app_id in ["cli_app_one", "cli_app_two"] {
+        let channel = LarkChannel::new(
+            app_id.into(),
+            "test_secret".into(),
+            String::new(),
+            None,
+            vec!["*".into()],
+            false,
+        )
+        .with_api_base(server.uri())
+        .with_http_client(client.clone());
+        channel
+            .send(&SendMessage::new("hello", "oc_test_chat"))
+            .await
+            .expect("send should use the injected client");
+    }
+}
+
+#[tokio::test]
+async fn low_rate_limit_quota_slows_the_next_send() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-ogw-ratelimit-limit", "50")
+                .insert_header("x-ogw-ratelimit-remaining", "0")
+                .insert_header("x-ogw-ratelimit-reset", "1")
+                .set_body_json(serde_json::json!({
+                    "code": 0,
+                    "data": { "message_id": "om_quota" }
+                })),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    assert!(channel.rate_limit_status().is_none());
+    let message = SendMessage::new("hello", "oc_test_chat");
+    channel.send(&message).await.expect("first send");
+
+    let status = channel.rate_limit_status().expect("headers are recorded");
+    assert_eq!(status.limit, Some(50));
+    assert_eq!(status.remaining, Some(0));
+    assert_eq!(status.reset_after, Some(Duration::from_secs(1)));
+
+    // No quota left: the next call waits for the window to reset.
+    let started = Instant::now();
+    channel.send(&message).await.expect("second send");
+    assert!(
+        started.elapsed() >= Duration::from_millis(800),
+        "send was not paced: {:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn low_rate_limit_quota_spaces_out_concurrent_sends() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-ogw-ratelimit-limit", "50")
+                .insert_header("x-ogw-ratelimit-remaining", "0")
+                .insert_header("x-ogw-ratelimit-reset", "1")
+                .set_body_json(serde_json::json!({
+                    "code": 0,
+                    "data": { "message_id": "om_quota" }
+                })),
+        )
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let message = SendMessage::new("hello", "oc_test_chat");
+    channel.send(&message).await.expect("first send");
+
+    // Callers share the limiter, so the second waiting send gets the next
+    // slot instead of firing together with the first.
+    let other = channel.clone();
+    let started = Instant::now();
+    let (first, second) = tokio::join!(channel.send(&message), other.send(&message));
+    first.expect("paced send");
+    second.expect("paced send");
+    assert!(
+        started.elapsed() >= Duration::from_millis(1800),
+        "concurrent sends were not spaced: {:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn persistent_rate_limit_surfaces_typed_error() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let err = test_channel(&server.uri())
+        .with_retry_policy(RetryPolicy::none())
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .expect_err("second 429 should fail the send");
+    assert!(
+        matches!(
+            err,
+            ChannelError::RateLimited {
+                retry_after: Some(wait)
+            } if wait.is_zero()
+        ),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn throttled_download_carries_retry_after() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages/om_busy/resources/file_busy"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "7"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = test_channel(&server.uri())
+        .download_attachment("om_busy", "file_busy")
+        .await
+        .expect_err("429 should fail the download");
+    assert!(
+        matches!(
+            err,
+            ChannelError::RateLimited {
+                retry_after: Some(wait)
+            } if wait == Duration::from_secs(7)
+        ),
+        "{err:?}"
+    );
+}
+
+fn fast_retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_retries: 3,
+        base_delay_ms: 10,
+        max_delay_ms: 50,
+        jitter: false,
+    }
+}
+
+#[tokio::test]
+async fn send_retries_transient_failures_then_succeeds() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("upstream unavailable"))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_after_retries" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let receipt = test_channel(&server.uri())
+        .with_retry_policy(fast_retry_policy())
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .expect("send should succeed after two transient failures");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_after_retries"));
+}
+
+#[tokio::test]
+async fn send_gives_up_after_max_retries() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(502))
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    let err = test_channel(&server.uri())
+        .with_retry_policy(fast_retry_policy())
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .expect_err("persistent 5xx should fail the send");
+    assert!(matches!(err, ChannelError::Unavailable(_)), "{err:?}");
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn send_keeps_uuid_stable_across_retries() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_sent" }
+        })))
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri()).with_retry_policy(fast_retry_policy());
+    channel
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .unwrap();
+    channel
+        .send(
+            &SendMessage::builder()
+                .to("oc_test_chat")
+                .text("again")
+                .idempotency_key("report-2026-10-15")
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    let uuids: Vec<String> = server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == "/im/v1/messages")
+        .map(|request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            body["uuid"]
+                .as_str()
+                .expect("every send carries a uuid")
+                .to_string()
+        })
+        .collect();
+    assert_eq!(uuids.len(), 3);
+    assert_eq!(uuids[0], uuids[1], "a retry reuses the uuid");
+    assert_ne!(uuids[0], uuids[2]);
+    assert_eq!(uuids[2], "report-2026-10-15");
+}
+
+#[tokio::test]
+async fn send_does_not_retry_permanent_failures() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "code": 230001,
+            "msg": "invalid message content"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = test_channel(&server.uri())
+        .with_retry_policy(fast_retry_policy())
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .expect_err("invalid payload should fail immediately");
+    assert!(
+        matches!(
+            err,
+            ChannelError::Api {
+                code: 230001,
+                kind: FeishuErrorCode::InvalidParameter,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn send_errors_redact_echoed_message_content_by_default() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    let secret_text = "my card number is 4111 1111 1111 1111, please keep it private";
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(500).set_body_json(serde_json::json!({
+            "code": 1,
+            "msg": "internal error",
+            "error": {
+                "log_id": "log_123",
+                "field_violations": [
+                    { "field": "content", "value": secret_text },
+                    { "field": "receive_id", "value": "oc_private_chat" }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let message = SendMessage::new(secret_text, "oc_private_chat");
+    let err = test_channel(&server.uri())
+        .with_retry_policy(RetryPolicy::none())
+        .send(&message)
+        .await
+        .expect_err("5xx should fail the send")
+        .to_string();
+    assert!(!err.contains(secret_text), "{err}");
+    assert!(!err.contains("4111"), "{err}");
+    assert!(!err.contains("oc_private_chat"), "{err}");
+    assert!(err.contains("log_123"), "{err}");
+
+    let err = test_channel(&server.uri())
+        .with_retry_policy(RetryPolicy::none())
+        .with_log_message_content(true)
+        .send(&message)
+        .await
+        .expect_err("5xx should fail the send")
+        .to_string();
+    assert!(err.contains("my card number"), "{err}");
+}
+
+#[tokio::test]
+async fn rejected_app_credentials_surface_unauthorized() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/auth/v3/tenant_access_token/internal"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 10014,
+            "msg": "app secret invalid"
+        })))
+        .mount(&server)
+        .await;
+
+    let err = test_channel(&server.uri())
+        .send(&SendMessage::new("hello", "oc_test_chat"))
+        .await
+        .expect_err("bad credentials should fail the send");
+    assert!(matches!(err, ChannelError::Unauthorized(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn health_status_times_out_on_hung_endpoint() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/auth/v3/tenant_access_token/internal"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_secs(5))
+                .set_body_json(serde_json::json!({
+                    "code": 0,
+                    "tenant_access_token": "t-test-token",
+                    "expire": 7200
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let channel =
+        test_channel(&server.uri()).with_health_check(Duration::from_millis(200), Duration::ZERO);
+    let started = std::time::Instant::now();
+    let status = channel.health_status().await;
+    assert!(!status.healthy);
+    assert!(status.latency >= Duration::from_millis(200));
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn health_status_is_cached_for_ttl() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/auth/v3/tenant_access_token/internal"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 10014,
+            "msg": "app secret invalid"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri())
+        .with_health_check(Duration::from_secs(5), Duration::from_secs(60));
+    let first = channel.health_status().await;
+    assert!(!first.healthy);
+    assert_eq!(channel.health_status().await, first);
+    assert!(!channel.health_check().await);
+}
+
+#[tokio::test]
+async fn webhook_listener_stops_on_shutdown() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        Some(0),
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri());
+    let (tx, _rx) = tokio::sync::mpsc::channel(1);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+    let listener = tokio::spawn(async move { channel.listen_http(tx, shutdown_rx).await });
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    shutdown_tx.send(true).unwrap();
+
+    let result = tokio::time::timeout(std::time::Duration::from_secs(2), listener)
+        .await
+        .expect("webhook server should stop after shutdown")
+        .unwrap();
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[tokio::test]
+async fn long_text_is_sent_in_multiple_chunks() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_chunk" }
+        })))
+        .mount(&server)
+        .await;
+
+    let paragraph = format!("{}\n\n", "x".repeat(999));
+    let text = paragraph.repeat(100);
+    assert_eq!(text.chars().count(), 100_100);
+
+    let channel = test_channel(&server.uri());
+    let receipt = channel
+        .send(&SendMessage::new(text, "oc_test_chat"))
+        .await
+        .expect("chunked send should succeed");
+    assert_eq!(receipt.message_id.as_deref(), Some("om_chunk"));
+
+    let sends: Vec<_> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|req| req.url.path() == "/im/v1/messages")
+        .collect();
+    assert!(
+        sends.len() > 1,
+        "expected multiple sends, got {}",
+        sends.len()
+    );
+
+    let mut delivered = 0;
+    for req in &sends {
+        let body: serde_json::Value = serde_json::from_slice(&req.body).unwrap();
+        let content: serde_json::Value =
+            serde_json::from_str(body["content"].as_str().unwrap()).unwrap();
+        let chunk = content["text"].as_str().unwrap();
+        assert!(chunk.len() <= 20 * 1024);
+        delivered += chunk.matches('x').count();
+    }
+    assert_eq!(delivered, 99_900);
+}
+
+#[tokio::test]
+async fn send_with_reply_to_posts_threaded_reply() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages/om_parent/reply"))
+        .and(body_partial_json(serde_json::json!({
+            "msg_type": "text",
+            "reply_in_thread": true
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_reply" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let receipt = channel
+        .send(&SendMessage::new("threaded", "oc_test_chat").reply_to(Some("om_parent".into())))
+        .await
+        .expect("reply should succeed");
+
+    assert_eq!(receipt.message_id.as_deref(), Some("om_reply"));
+}
+
+/// Helper: pick a free local port for a webhook listener.
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// Helper: an `im.message.receive_v1` webhook event.
+fn message_event(event_id: &str, open_id: &str, text: &str) -> serde_json::Value {
+    serde_json::json!({
+        "header": { "event_type": "im.message.receive_v1", "event_id": event_id },
+        "event": {
+            "sender": { "sender_id": { "open_id": open_id }, "sender_type": "user" },
+            "message": {
+                "message_id": format!("om_{event_id}"),
+                "chat_id": "oc_chat",
+                "chat_type": "p2p",
+                "message_type": "text",
+                "content": serde_json::json!({ "text": text }).to_string()
+            }
+        }
+    })
+}
+
+/// Helper: POST an event to the webhook, retrying until the server is up.
+async fn post_event(port: u16, body: &serde_json::Value) -> reqwest::StatusCode {
+    let client = reqwest::Client::new();
+    let url = format!("http://127.0.0.1:{port}/lark");
+    for _ in 0..50 {
+        if let Ok(resp) = client.post(&url).json(body).send().await {
+            return resp.status();
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    panic!("webhook server did not start");
+}
+
+#[tokio::test]
+async fn webhook_drops_self_authored_events() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/bot/v3/info"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "bot": { "open_id": "ou_bot" }
+        })))
+        .mount(&server)
+        .await;
+
+    let port = free_port();
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        Some(port),
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let listener = tokio::spawn(async move { channel.listen_http(tx, shutdown_rx).await });
+
+    let self_event = message_event("self", "ou_bot", "echo");
+    assert!(post_event(port, &self_event).await.is_success());
+    let user_event = message_event("user", "ou_user", "hello");
+    assert!(post_event(port, &user_event).await.is_success());
+
+    let forwarded = tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+        .await
+        .expect("user message should be forwarded")
+        .and_then(ChannelEvent::into_message)
+        .unwrap();
+    assert_eq!(forwarded.content, "hello");
+    assert!(!forwarded.is_from_self);
+    assert!(rx.try_recv().is_err());
+
+    shutdown_tx.send(true).unwrap();
+    listener.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_dumps_raw_events_with_secrets_redacted() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    let dir = tempfile::tempdir().unwrap();
+    let dump = dir.path().join("events.jsonl");
+
+    let port = free_port();
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        Some(port),
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri())
+    .with_event_dump(true, Some(dump.clone()));
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let listener = tokio::spawn(async move { channel.listen_http(tx, shutdown_rx).await });
+
+    let mut event = message_event("dumped", "ou_user", "hello");
+    event["header"]["token"] = "verification-secret".into();
+    event["header"]["event_type"] = "im.chat.new_type_v9".into();
+    assert!(post_event(port, &event).await.is_success());
+    assert!(
+        tokio::time::timeout(Duration::from_millis(200), rx.recv())
+            .await
+            .is_err(),
+        "unknown event types are not forwarded"
+    );
+
+    let lines = std::fs::read_to_string(&dump).unwrap();
+    let recorded: serde_json::Value = serde_json::from_str(lines.lines().next().unwrap()).unwrap();
+    assert_eq!(recorded["header"]["event_type"], "im.chat.new_type_v9");
+    assert_eq!(recorded["header"]["event_id"], "dumped");
+    assert_eq!(recorded["header"]["token"], "[REDACTED]");
+    assert!(!lines.contains("verification-secret"));
+
+    shutdown_tx.send(true).unwrap();
+    listener.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_requires_signature_unless_the_body_is_a_url_challenge() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    let port = free_port();
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        Some(port),
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri())
+    .with_encrypt_key(Some("encrypt-key".into()));
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let listener = tokio::spawn(async move { channel.listen_http(tx, shutdown_rx).await });
+
+    let challenge = serde_json::json!({ "challenge": "abc123", "type": "url_verification" });
+    assert_eq!(post_event(port, &challenge).await, reqwest::StatusCode::OK);
+
+    let mut smuggled = message_event("smuggled", "ou_user", "run the deploy");
+    smuggled["challenge"] = 1.into();
+    assert_eq!(
+        post_event(port, &smuggled).await,
+        reqwest::StatusCode::UNAUTHORIZED
+    );
+    assert!(
+        tokio::time::timeout(Duration::from_millis(200), rx.recv())
+            .await
+            .is_err(),
+        "unsigned events are not forwarded"
+    );
+
+    shutdown_tx.send(true).unwrap();
+    listener.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_drops_events_older_than_max_event_age() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    let port = free_port();
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        Some(port),
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri())
+    .with_max_event_age(Some(Duration::from_secs(60)));
+    let counter = channel.clone();
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let listener = tokio::spawn(async move { channel.listen_http(tx, shutdown_rx).await });
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let mut stale = message_event("stale", "ou_user", "restart the job");
+    stale["event"]["message"]["create_time"] = (now_ms - 3_600_000).to_string().into();
+    let mut fresh = message_event("fresh", "ou_user", "status?");
+    fresh["event"]["message"]["create_time"] = now_ms.to_string().into();
+    assert!(post_event(port, &stale).await.is_success());
+    assert!(post_event(port, &fresh).await.is_success());
+
+    let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.into_message().unwrap().content, "status?");
+    assert!(
+        tokio::time::timeout(Duration::from_millis(200), rx.recv())
+            .await
+            .is_err(),
+        "stale events are not forwarded"
+    );
+    assert_eq!(counter.dropped_stale_messages(), 1);
+
+    shutdown_tx.send(true).unwrap();
+    listener.await.unwrap().unwrap();
+}
+
+/// Transcriber stub that records what it was asked to transcribe.
+struct RecordingTranscriber(Mutex<Vec<(Vec<u8>, String)>>);
+
+#[async_trait::async_trait]
+impl Transcriber for RecordingTranscriber {
+    async fn transcribe(&self, audio: Vec<u8>, file_name: &str) -> anyhow::Result<String> {
+        self.0.lock().unwrap().push((audio, file_name.to_string()));
+        Ok("turn on the lights".into())
+    }
+}
+
+#[tokio::test]
+async fn webhook_voice_message_is_transcribed_before_forwarding() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages/om_voice/resources/file_voice"))
+        .and(query_param("type", "file"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"OggS-voice".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let transcriber = Arc::new(RecordingTranscriber(Mutex::new(Vec::new())));
+    let port = free_port();
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        Some(port),
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri())
+    .with_transcriber(transcriber.clone());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let listener = tokio::spawn(async move { channel.listen_http(tx, shutdown_rx).await });
+
+    let mut event = message_event("voice", "ou_user", "");
+    event["event"]["message"]["message_type"] = "audio".into();
+    event["event"]["message"]["content"] =
+        serde_json::json!({ "file_key": "file_voice", "duration": 1500 })
+            .to_string()
+            .into();
+    assert!(post_event(port, &event).await.is_success());
+
+    let forwarded = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("voice message should be forwarded")
+        .and_then(ChannelEvent::into_message)
+        .unwrap();
+    assert_eq!(forwarded.content, "turn on the lights");
+    assert_eq!(forwarded.attachments.len(), 1);
+    assert_eq!(
+        *transcriber.0.lock().unwrap(),
+        [(b"OggS-voice".to_vec(), "voice.opus".to_string())]
+    );
+
+    shutdown_tx.send(true).unwrap();
+    listener.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_forwards_card_action_and_answers_with_json() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/bot/v3/info"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "bot": { "open_id": "ou_bot" }
+        })))
+        .mount(&server)
+        .await;
+
+    let port = free_port();
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        Some(port),
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let listener = tokio::spawn(async move { channel.listen_http(tx, shutdown_rx).await });
+
+    let click = serde_json::json!({
+        "schema": "2.0",
+        "header": { "event_id": "ev_click_1", "event_type": "card.action.trigger" },
+        "event": {
+            "operator": { "open_id": "ou_approver" },
+            "action": { "tag": "button", "value": { "decision": "approve", "request": 42 } },
+            "context": { "open_message_id": "om_approval", "open_chat_id": "oc_team" }
+        }
+    });
+    assert!(post_event(port, &click).await.is_success());
+    let reply: serde_json::Value = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{port}/lark"))
+        .json(&click)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .expect("card callbacks must be answered with JSON");
+    assert_eq!(reply, serde_json::json!({}));
+
+    let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("card action should be forwarded")
+        .unwrap();
+    let ChannelEvent::CardAction {
+        message_id,
+        action_value,
+        operator_open_id,
+        ..
+    } = event
+    else {
+        panic!("expected a card action, got {event:?}");
+    };
+    assert_eq!(message_id, "om_approval");
+    assert_eq!(operator_open_id, "ou_approver");
+    assert_eq!(
+        action_value,
+        serde_json::json!({ "decision": "approve", "request": 42 })
+    );
+    // The second post redelivered the same event_id.
+    assert!(rx.try_recv().is_err());
+
+    shutdown_tx.send(true).unwrap();
+    listener.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_forwards_form_submission_fields() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    let port = free_port();
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        Some(port),
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let listener = tokio::spawn(async move { channel.listen_http(tx, shutdown_rx).await });
+
+    let submit = serde_json::json!({
+        "schema": "2.0",
+        "header": { "event_id": "ev_form_1", "event_type": "card.action.trigger" },
+        "event": {
+            "operator": { "open_id": "ou_reviewer" },
+            "action": {
+                "tag": "button",
+                "name": "feedback_submit",
+                "form_value": {
+                    "comment": "Fast and friendly",
+                    "rating": "5",
+                    "topics": ["speed", "tone"]
+                }
+            },
+            "context": { "open_message_id": "om_feedback", "open_chat_id": "oc_team" }
+        }
+    });
+    assert!(post_event(port, &submit).await.is_success());
+
+    let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("form submission should be forwarded")
+        .unwrap();
+    let ChannelEvent::FormSubmit {
+        message_id,
+        fields,
+        operator_open_id,
+        ..
+    } = event
+    else {
+        panic!("expected a form submission, got {event:?}");
+    };
+    assert_eq!(message_id, "om_feedback");
+    assert_eq!(operator_open_id, "ou_reviewer");
+    assert_eq!(fields.len(), 3);
+    assert_eq!(fields["comment"], "Fast and friendly");
+    assert_eq!(fields["rating"], "5");
+    assert_eq!(fields["topics"], serde_json::json!(["speed", "tone"]));
+
+    shutdown_tx.send(true).unwrap();
+    listener.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn webhook_drops_redelivered_event() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    let port = free_port();
+    let channel = LarkChannel::new(
+        "cli_test_app".into(),
+        "test_secret".into(),
+        String::new(),
+        Some(port),
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let listener = tokio::spawn(async move { channel.listen_http(tx, shutdown_rx).await });
+
+    let event = message_event("ev_retry", "ou_user", "once");
+    assert!(post_event(port, &event).await.is_success());
+    assert!(post_event(port, &event).await.is_success());
+
+    let first = tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+        .await
+        .expect("first delivery should be forwarded")
+        .and_then(ChannelEvent::into_message)
+        .unwrap();
+    assert_eq!(first.content, "once");
+
+    shutdown_tx.send(true).unwrap();
+    listener.await.unwrap().unwrap();
+    assert!(
+        rx.recv().await.is_none(),
+        "duplicate delivery was forwarded"
+    );
+}
+
+#[tokio::test]
+async fn lark_and_feishu_share_one_webhook_server() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    let port = free_port();
+    let webhook_server = zeroclaw::channels::webhook::WebhookServer::new(
+        std::net::SocketAddr::from(([127, 0, 0, 1], port)),
+        "/hooks",
+    );
+    let lark = LarkChannel::new(
+        "cli_lark_app".into(),
+        "test_secret".into(),
+        String::new(),
+        None,
+        vec!["*".into()],
+        false,
+    )
+    .with_api_base(server.uri())
+    .with_webhook_server(Some(webhook_server.clone()));
+    let feishu_config: zeroclaw::config::FeishuConfig = toml::from_str(
+        r#"
+        app_id = "cli_feishu_app"
+        app_secret = "test_secret"
+        allowed_users = ["*"]
+        receive_mode = "webhook"
+        "#,
+    )
+    .unwrap();
+    let feishu = LarkChannel::from_feishu_config(&feishu_config)
+        .with_api_base(server.uri())
+        .with_webhook_server(Some(webhook_server));
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+    let (lark_tx, mut lark_rx) = tokio::sync::mpsc::channel(4);
+    let (feishu_tx, mut feishu_rx) = tokio::sync::mpsc::channel(4);
+    let lark_listener = tokio::spawn({
+        let shutdown_rx = shutdown_rx.clone();
+        async move { lark.listen_http(lark_tx, shutdown_rx).await }
+    });
+    let feishu_listener =
+        tokio::spawn(async move { feishu.listen_http(feishu_tx, shutdown_rx).await });
+
+    let client = reqwest::Client::new();
+    let post = |route: &'static str, body: serde_json::Value| {
+        let client = client.clone();
+        async move {
+            let url = format!("http://127.0.0.1:{port}/hooks/{route}/events");
+            for _ in 0..50 {
+                if let Ok(resp) = client.post(&url).json(&body).send().await {
+                    if resp.status() != reqwest::StatusCode::NOT_FOUND {
+                        return resp.status();
+                    }
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+            panic!("{route} route was never registered");
+        }
+    };
+    assert!(post("lark", message_event("ev_lark", "ou_user", "to lark"))
+        .await
+        .is_success());
+    assert!(
+        post("feishu", message_event("ev_feishu", "ou_user", "to feishu"))
+            .await
+            .is_success()
+    );
+
+    let timeout = std::time::Duration::from_secs(2);
+    let lark_msg = tokio::time::timeout(timeout, lark_rx.recv())
+        .await
+        .unwrap()
+        .and_then(ChannelEvent::into_message)
+        .unwrap();
+    assert_eq!(lark_msg.content, "to lark");
+    assert_eq!(lark_msg.channel, "lark");
+    let feishu_msg = tokio::time::timeout(timeout, feishu_rx.recv())
+        .await
+        .unwrap()
+        .and_then(ChannelEvent::into_message)
+        .unwrap();
+    assert_eq!(feishu_msg.content, "to feishu");
+    assert_eq!(feishu_msg.channel, "feishu");
+
+    shutdown_tx.send(true).unwrap();
+    lark_listener.await.unwrap().unwrap();
+    feishu_listener.await.unwrap().unwrap();
+}
+
+/// Helper: one item of the `/im/v1/messages` list response.
+fn history_item(message_id: &str, create_time_ms: u64, text: &str) -> serde_json::Value {
+    serde_json::json!({
+        "message_id": message_id,
+        "msg_type": "text",
+        "create_time": create_time_ms.to_string(),
+        "chat_id": "oc_chat",
+        "sender": { "id": "ou_user", "id_type": "open_id", "sender_type": "user" },
+        "body": { "content": serde_json::json!({ "text": text }).to_string() }
+    })
+}
+
+#[tokio::test]
+async fn fetch_history_paginates_and_returns_oldest_first() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+
+    // Newest-first pages, as requested with sort_type=ByCreateTimeDesc.
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages"))
+        .and(query_param("container_id_type", "chat"))
+        .and(query_param("container_id", "oc_chat"))
+        .and(query_param("sort_type", "ByCreateTimeDesc"))
+        .and(query_param("page_token", "page_2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": {
+                "has_more": true,
+                "page_token": "page_3",
+                "items": [
+                    history_item("om_2", 2_000, "second"),
+                    history_item("om_1", 1_000, "first"),
+                ]
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages"))
+        .and(query_param("page_size", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": {
+                "has_more": true,
+                "page_token": "page_2",
+                "items": [
+                    history_item("om_4", 4_000, "fourth"),
+                    history_item("om_3", 3_000, "third"),
+                ]
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let history = channel.fetch_history("oc_chat", 3).await.unwrap();
+    let contents: Vec<&str> = history.iter().map(|m| m.content.as_str()).collect();
+    assert_eq!(contents, ["second", "third", "fourth"]);
+    assert_eq!(history[0].id, "om_2");
+    assert_eq!(history[2].timestamp, 4);
+}
+
+#[tokio::test]
+async fn export_transcript_pages_history_and_names_senders() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    let mut bot_item = history_item("om_4", 4_000, "fourth");
+    bot_item["sender"] =
+        serde_json::json!({ "id": "cli_bot", "id_type": "app_id", "sender_type": "app" });
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages"))
+        .and(query_param("start_time", "2"))
+        .and(query_param("page_token", "page_2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": {
+                "has_more": false,
+                "items": [
+                    history_item("om_2", 2_000, "second"),
+                    history_item("om_1", 1_000, "first"),
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages"))
+        .and(query_param("start_time", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": {
+                "has_more": true,
+                "page_token": "page_2",
+                "items": [bot_item, history_item("om_3", 3_000, "third")]
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/contact/v3/users/ou_user"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "user": { "open_id": "ou_user", "name": "Ada Lovelace" } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let since = chrono::DateTime::from_timestamp(2, 0);
+    let json = channel
+        .export_transcript("oc_chat", since, TranscriptFormat::Json)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["chat_id"], "oc_chat");
+    let rows: Vec<(&str, &str)> = json["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| {
+            (
+                m["sender_name"].as_str().unwrap(),
+                m["content"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    // om_1 predates `since` and is dropped; the bot keeps its raw id.
+    assert_eq!(
+        rows,
+        [
+            ("Ada Lovelace", "second"),
+            ("Ada Lovelace", "third"),
+            ("cli_bot", "fourth"),
+        ]
+    );
+    assert_eq!(
+        json["messages"][0]["timestamp"],
+        "1970-01-01T00:00:02+00:00"
+    );
+
+    let markdown = channel
+        .export_transcript("oc_chat", since, TranscriptFormat::Markdown)
+        .await
+        .unwrap();
+    assert!(markdown.starts_with("# Transcript of oc_chat\n"));
+    assert!(markdown.contains("\n### Ada Lovelace · 1970-01-01 00:00:02 UTC\n\nsecond\n"));
+    assert!(markdown.contains("\n### cli_bot · 1970-01-01 00:00:04 UTC\n\nfourth\n"));
+}
+
+#[tokio::test]
+async fn fetch_history_reports_missing_scope() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "code": 99_991_672,
+            "msg": "Access denied. One of the following scopes is required: [im:message:readonly]"
+        })))
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let err = channel.fetch_history("oc_chat", 10).await.unwrap_err();
+    let lark_err = err
+        .downcast_ref::<zeroclaw::channels::lark::LarkError>()
+        .expect("missing scope should be a typed LarkError");
+    assert!(matches!(
+        lark_err,
+        zeroclaw::channels::lark::LarkError::MissingScope {
+            code: 99_991_672,
+            ..
+        }
+    ));
+    assert!(err.to_string().contains("im:message:readonly"));
+}
+
+#[tokio::test]
+async fn get_user_info_parses_contact_profile_and_caches_it() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/contact/v3/users/ou_ada"))
+        .and(query_param("user_id_type", "open_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "user": {
+                "open_id": "ou_ada",
+                "name": "Ada Lovelace",
+                "en_name": "Ada",
+                "avatar": {
+                    "avatar_72": "https://example.com/ada_72.png",
+                    "avatar_240": "https://example.com/ada_240.png"
+                },
+                "email": "",
+                "enterprise_email": "ada@corp.example"
+            } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    let info = channel.get_user_info("ou_ada").await.unwrap();
+    assert_eq!(
+        info,
+        zeroclaw::channels::lark::UserInfo {
+            name: "Ada Lovelace".into(),
+            avatar_url: "https://example.com/ada_240.png".into(),
+            email: Some("ada@corp.example".into()),
+        }
+    );
+
+    // A clone shares the cache, so this does not hit the API again.
+    assert_eq!(channel.clone().get_user_info("ou_ada").await.unwrap(), info);
+    assert!(channel.get_user_info("oc_not_a_user").await.is_err());
+}
+
+#[tokio::test]
+async fn get_users_info_batches_lookups_and_fills_the_cache() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/contact/v3/users/batch"))
+        .and(query_param("user_id_type", "open_id"))
+        .and(query_param("user_ids", "ou_ada"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "items": [
+                { "open_id": "ou_ada", "name": "Ada Lovelace", "email": "ada@corp.example" },
+                { "open_id": "ou_bob", "name": "", "en_name": "Bob" }
+            ] }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/contact/v3/users/batch"))
+        .and(query_param("user_ids", "ou_u57"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "items": [ { "open_id": "ou_u57", "name": "Carol" } ] }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // 60 distinct open_ids need two batch calls; duplicates and chat IDs
+    // are dropped before any request.
+    let mut open_ids: Vec<String> = vec!["ou_ada".into(), "ou_bob".into(), " ou_ada ".into()];
+    open_ids.extend((0..58).map(|i| format!("ou_u{i}")));
+    open_ids.push("oc_not_a_user".into());
+
+    let channel = test_channel(&server.uri());
+    let users = channel.get_users_info(&open_ids).await.unwrap();
+    assert_eq!(users.len(), 3);
+    assert_eq!(users["ou_ada"].name, "Ada Lovelace");
+    assert_eq!(users["ou_ada"].email.as_deref(), Some("ada@corp.example"));
+    assert_eq!(users["ou_bob"].name, "Bob");
+    assert_eq!(users["ou_u57"].name, "Carol");
+
+    // Served from the cache the batch call filled.
+    assert_eq!(channel.get_user_info("ou_bob").await.unwrap().name, "Bob");
+    let cached = channel
+        .get_users_info(&["ou_ada".to_string(), "ou_u57".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(cached.len(), 2);
+}
+
+#[tokio::test]
+async fn get_user_info_reports_missing_contact_scope() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/contact/v3/users/ou_ada"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "code": 99_991_672,
+            "msg": "Access denied. One of the following scopes is required: [contact:user.base:readonly]"
+        })))
+        .mount(&server)
+        .await;
+
+    let err = test_channel(&server.uri())
+        .get_user_info("ou_ada")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<zeroclaw::channels::lark::LarkError>(),
+        Some(zeroclaw::channels::lark::LarkError::MissingScope {
+            scope: "contact:user.base:readonly",
+            ..
+        })
+    ));
+    assert!(err.to_string().contains("contact:user.base:readonly"));
+}
+
+#[tokio::test]
+async fn get_presence_maps_account_status_and_caches_it() {
+    use zeroclaw::channels::lark::Presence;
+
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    let user = |open_id: &str, status: serde_json::Value| {
+        Mock::given(method("GET"))
+            .and(path(format!("/contact/v3/users/{open_id}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "code": 0,
+                "data": { "user": { "open_id": open_id, "status": status } }
+            })))
+            .expect(1)
+    };
+    user(
+        "ou_active",
+        serde_json::json!({ "is_activated": true, "is_frozen": false, "is_resigned": false }),
+    )
+    .mount(&server)
+    .await;
+    user(
+        "ou_left",
+        serde_json::json!({ "is_activated": true, "is_frozen": false, "is_resigned": true }),
+    )
+    .mount(&server)
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/contact/v3/users/ou_hidden"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "code": 99_991_672,
+            "msg": "Access denied. One of the following scopes is required: [contact:user.base:readonly]"
+        })))
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri());
+    for _ in 0..2 {
+        assert_eq!(
+            channel.get_presence("ou_active").await.unwrap(),
+            Presence::Online
+        );
+        assert_eq!(
+            channel.get_presence("ou_left").await.unwrap(),
+            Presence::Offline
+        );
+    }
+    assert_eq!(
+        channel.get_presence("ou_hidden").await.unwrap(),
+        Presence::Unknown
+    );
+    assert!(channel.get_presence("oc_not_a_user").await.is_err());
+}
+
+/// Helper: current value of a channel counter, summed over matching labels.
+fn channel_counter(name: &str, labels: &[(&str, &str)]) -> u64 {
+    zeroclaw::channels::metrics::registry()
+        .gather()
+        .iter()
+        .filter(|family| family.name() == name)
+        .flat_map(|family| family.get_metric().iter())
+        .filter(|metric| {
+            labels.iter().all(|(key, value)| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.name() == *key && label.value() == *value)
+            })
+        })
+        .map(|metric| metric.get_counter().get_value() as u64)
+        .sum()
+}
+
+#[tokio::test]
+async fn send_records_channel_metrics() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(
+            serde_json::json!({ "receive_id": "oc_metrics_ok" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_metrics" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .and(body_partial_json(
+            serde_json::json!({ "receive_id": "oc_metrics_denied" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 230_013,
+            "msg": "user not available"
+        })))
+        .mount(&server)
+        .await;
+
+    let sent_before = channel_counter("zeroclaw_messages_sent_total", &[("channel", "lark")]);
+    let errors_before = channel_counter(
+        "zeroclaw_send_errors_total",
+        &[("channel", "lark"), ("reason", "user_not_allowed")],
+    );
+
+    let channel = test_channel(&server.uri());
+    channel
+        .send(&SendMessage::new("hi", "oc_metrics_ok"))
+        .await
+        .unwrap();
+    channel
+        .send(&SendMessage::new("hi", "oc_metrics_denied"))
+        .await
+        .unwrap_err();
+
+    assert!(channel_counter("zeroclaw_messages_sent_total", &[("channel", "lark")]) > sent_before);
+    assert!(
+        channel_counter(
+            "zeroclaw_send_errors_total",
+            &[("channel", "lark"), ("reason", "user_not_allowed")],
+        ) > errors_before
+    );
+}
+
+/// Helper: `MakeWriter` that captures formatted tracing output in memory.
+#[derive(Clone, Default)]
+struct CapturedLogs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for CapturedLogs {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[tokio::test]
+async fn send_span_records_channel_recipient_and_message_id() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_span" }
+        })))
+        .mount(&server)
+        .await;
+
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(logs.clone())
+        .with_ansi(false)
+        .with_max_level(tracing::Level::INFO)
+        .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    test_channel(&server.uri())
+        .send(&SendMessage::new("hello", "oc_span_chat"))
+        .await
+        .unwrap();
+
+    let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(
+        output.contains("channel.send{channel=\"lark\" recipient=oc_spa… message_id=\"om_span\"}"),
+        "unexpected span output: {output}"
+    );
+    assert!(!output.contains("test_secret"));
+    assert!(!output.contains("t-test-token"));
+}
+
+#[tokio::test]
+async fn verify_reachability_reports_chats_the_bot_left() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/chats/oc_member/members/is_in_chat"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "is_in_chat": true }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/im/v1/chats/oc_removed/members/is_in_chat"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "is_in_chat": false }
+        })))
+        .mount(&server)
+        .await;
+
+    let channel = test_channel(&server.uri())
+        .with_allowed_chats(vec!["*".into(), "oc_member".into(), "oc_removed".into()])
+        .with_health_check(Duration::from_secs(5), Duration::ZERO);
+    let reachable = channel
+        .verify_reachability(&[
+            "oc_member".into(),
+            "oc_removed".into(),
+            "oc_member".into(),
+            "not_a_chat".into(),
+        ])
+        .await;
+    assert_eq!(reachable.len(), 3);
+    assert!(reachable["oc_member"]);
+    assert!(!reachable["oc_removed"]);
+    assert!(!reachable["not_a_chat"]);
+
+    // The deep check only runs when enabled.
+    assert!(channel.health_status().await.unreachable_chats.is_empty());
+    let status = channel
+        .with_chat_reachability_check(true)
+        .health_status()
+        .await;
+    assert!(status.healthy);
+    assert_eq!(status.unreachable_chats, vec!["oc_removed".to_string()]);
+}
+
+/// Words arriving every 20ms, like tokens from a model.
+fn synthetic_token_stream(words: usize) -> impl futures_util::Stream<Item = String> {
+    use futures_util::StreamExt;
+
+    futures_util::stream::iter(0..words).then(|i| async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        format!("word{i} ")
+    })
+}
+
+#[tokio::test]
+async fn stream_reply_debounces_edits_and_ends_with_full_text() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_stream" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/im/v1/messages/om_stream"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 })))
+        .mount(&server)
+        .await;
+
+    let message_id = test_channel(&server.uri())
+        .with_draft_updates(Duration::from_millis(150), 20)
+        .stream_reply("oc_test_chat", synthetic_token_stream(30))
+        .await
+        .unwrap();
+    assert_eq!(message_id, "om_stream");
+
+    let edits: Vec<String> = server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.method.as_str() == "PATCH")
+        .map(|request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let content: serde_json::Value =
+                serde_json::from_str(body["content"].as_str().unwrap()).unwrap();
+            content["text"].as_str().unwrap().to_string()
+        })
+        .collect();
+    // ~600ms of output at one edit per 150ms, plus the final edit; far fewer
+    // than one edit per chunk.
+    assert!((2..=6).contains(&edits.len()), "{} edits", edits.len());
+    let full: String = (0..30).map(|i| format!("word{i} ")).collect();
+    assert_eq!(edits.last().unwrap(), full.trim());
+    assert!(edits.windows(2).all(|pair| pair[1].len() > pair[0].len()));
+}
+
+#[tokio::test]
+async fn stream_reply_sends_once_when_edits_are_refused() {
+    let server = MockServer::start().await;
+    mock_tenant_token(&server).await;
+    Mock::given(method("POST"))
+        .and(path("/im/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 0,
+            "data": { "message_id": "om_sent" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/im/v1/messages/om_sent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "code": 230025,
+            "msg": "message can not be edited"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/im/v1/messages/om_sent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "code": 0 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    test_channel(&server.uri())
+        .with_draft_updates(Duration::from_millis(50), 20)
+        .stream_reply("oc_test_chat", synthetic_token_stream(10))
+        .await
+        .unwrap();
+
+    let sent = sent_messages(&server).await;
+    assert_eq!(sent.len(), 2, "placeholder, then the complete reply");
+    let full: String = (0..10).map(|i| format!("word{i} ")).collect();
+    assert_eq!(sent[1].1["text"].as_str().unwrap().trim(), full.trim());
+}
@@ -0,0 +1,107 @@
+//! Telegram webhook receive mode: updates POSTed to `/telegram` reach `listen`.
+
+use std::time::Duration;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use zeroclaw::channels::telegram::TelegramChannel;
+use zeroclaw::channels::traits::{Channel, ChannelEvent};
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .expect("ephemeral port")
+}
+
+async fn post_update(
+    client: &reqwest::Client,
+    port: u16,
+    secret: &str,
+    update: &serde_json::Value,
+) -> reqwest::StatusCode {
+    let url = format!("http://127.0.0.1:{port}/telegram");
+    for _ in 0..50 {
+        match client
+            .post(&url)
+            .header("X-Telegram-Bot-Api-Secret-Token", secret)
+            .json(update)
+            .send()
+            .await
+        {
+            Ok(resp) => return resp.status(),
+            Err(_) => tokio::time::sleep(Duration::from_millis(50)).await,
+        }
+    }
+    panic!("webhook server never came up on port {port}");
+}
+
+#[tokio::test]
+async fn webhook_updates_are_forwarded_and_secret_is_enforced() {
+    let api = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "ok": true, "result": true })),
+        )
+        .mount(&api)
+        .await;
+
+    let port = free_port();
+    let channel = TelegramChannel::new("TEST_TOKEN".into(), vec!["*".into()], false, false)
+        .with_api_base(api.uri())
+        .with_webhook(Some(port), None, Some("hook-secret".into()));
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+    let listener = tokio::spawn(async move {
+        channel
+            .listen(tx, tokio::sync::watch::channel(false).1)
+            .await
+    });
+
+    let update = serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 7,
+            "date": 1_700_000_000,
+            "text": "hello via webhook",
+            "from": { "id": 42, "is_bot": false, "first_name": "Ada", "username": "ada" },
+            "chat": { "id": 42, "type": "private" }
+        }
+    });
+    let client = reqwest::Client::new();
+
+    let rejected = post_update(&client, port, "wrong-secret", &update).await;
+    assert_eq!(rejected, reqwest::StatusCode::UNAUTHORIZED);
+
+    let accepted = post_update(&client, port, "hook-secret", &update).await;
+    assert_eq!(accepted, reqwest::StatusCode::OK);
+
+    let msg = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("message forwarded in time")
+        .and_then(ChannelEvent::into_message)
+        .expect("listener still running");
+    assert_eq!(msg.content, "hello via webhook");
+    assert_eq!(msg.reply_target, "42");
+
+    listener.abort();
+}
+
+#[tokio::test]
+async fn webhook_mode_refuses_to_start_without_a_secret() {
+    let port = free_port();
+    let channel = TelegramChannel::new("TEST_TOKEN".into(), vec!["*".into()], false, false)
+        .with_api_base("http://127.0.0.1:9".into())
+        .with_webhook(Some(port), None, None);
+
+    let (tx, _rx) = tokio::sync::mpsc::channel(4);
+    let err = tokio::time::timeout(
+        Duration::from_secs(5),
+        channel.listen(tx, tokio::sync::watch::channel(false).1),
+    )
+    .await
+    .expect("listen returns promptly")
+    .unwrap_err();
+    assert!(err.to_string().contains("webhook_secret"), "{err}");
+    assert!(std::net::TcpListener::bind(("0.0.0.0", port)).is_ok());
+}